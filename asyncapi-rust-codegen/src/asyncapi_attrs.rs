@@ -1,15 +1,105 @@
 //! Utilities for parsing asyncapi attributes
 
-use syn::Attribute;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Token};
 
 /// AsyncAPI metadata extracted from attributes
 #[derive(Debug, Default, Clone)]
 pub struct AsyncApiMeta {
+    pub name: Option<String>,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub title: Option<String>,
     pub content_type: Option<String>,
     pub triggers_binary: bool,
+    pub no_payload: bool,
+    /// Default titles to the humanized Rust identifier, e.g. `User Join` for `UserJoin`
+    pub humanize_titles: bool,
+    pub deprecated: bool,
+    pub deprecated_reason: Option<String>,
+    /// `"send"` or `"receive"`, for operations using `messages_from = Type`
+    pub direction: Option<syn::LitStr>,
+    pub binary_payload: Option<syn::LitStr>,
+    /// Payload schema document, e.g. an Avro schema, used instead of schemars
+    pub schema: Option<syn::LitStr>,
+    /// Path of the payload schema document, relative to `CARGO_MANIFEST_DIR`
+    pub schema_file: Option<syn::LitStr>,
+    /// `schemaFormat` of the schema document, overriding the content type's
+    pub schema_format: Option<String>,
+    pub headers: Option<syn::Path>,
+    pub correlation_id: Option<String>,
+    pub max_schema_depth: Option<usize>,
+    pub nullable: Option<syn::LitStr>,
+    pub instantiate: Option<syn::LitStr>,
+    pub tags: Vec<String>,
+    pub external_docs: Option<ExternalDocsMeta>,
+}
+
+/// External documentation from `external_docs = "..."` or
+/// `external_docs(url = "...", description = "...")`
+#[derive(Debug, Clone)]
+pub struct ExternalDocsMeta {
+    /// Required; checked by [`external_docs_tokens`] so the error can point at `path`
+    pub url: Option<String>,
+    pub description: Option<String>,
+    /// The `external_docs` key itself, for error spans
+    pub path: syn::Path,
+}
+
+impl ExternalDocsMeta {
+    /// Parse the value of an `external_docs` key in either form
+    pub fn parse(nested: &ParseNestedMeta) -> syn::Result<Self> {
+        let mut docs = ExternalDocsMeta {
+            url: None,
+            description: None,
+            path: nested.path.clone(),
+        };
+        if nested.input.peek(Token![=]) {
+            let s: syn::LitStr = nested.value()?.parse()?;
+            docs.url = Some(s.value());
+        } else {
+            nested.parse_nested_meta(|inner| {
+                if inner.path.is_ident("url") {
+                    let s: syn::LitStr = inner.value()?.parse()?;
+                    docs.url = Some(s.value());
+                } else if inner.path.is_ident("description") {
+                    let s: syn::LitStr = inner.value()?.parse()?;
+                    docs.description = Some(s.value());
+                }
+                Ok(())
+            })?;
+        }
+        Ok(docs)
+    }
+}
+
+/// `Some(asyncapi_rust::ExternalDocumentation { .. })` for `docs`, or `None`
+///
+/// Fails if `external_docs(...)` was given without a `url`.
+pub fn external_docs_tokens(
+    docs: Option<&ExternalDocsMeta>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let Some(docs) = docs else {
+        return Ok(quote! { None });
+    };
+    let Some(url) = &docs.url else {
+        return Err(syn::Error::new_spanned(
+            &docs.path,
+            "external_docs requires a url: external_docs(url = \"...\")",
+        ));
+    };
+    let description = match &docs.description {
+        Some(description) => quote! { Some(#description.to_string()) },
+        None => quote! { None },
+    };
+    Ok(quote! {
+        Some(asyncapi_rust::ExternalDocumentation {
+            description: #description,
+            url: #url.to_string(),
+        })
+    })
 }
 
 /// Extract asyncapi metadata from `#[asyncapi(...)]` attributes
@@ -22,7 +112,11 @@ pub fn extract_asyncapi_meta(attrs: &[Attribute]) -> AsyncApiMeta {
         }
 
         let _ = attr.parse_nested_meta(|nested| {
-            if nested.path.is_ident("summary") {
+            if nested.path.is_ident("name") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.name = Some(s.value());
+            } else if nested.path.is_ident("summary") {
                 let value = nested.value()?;
                 let s: syn::LitStr = value.parse()?;
                 meta.summary = Some(s.value());
@@ -38,9 +132,75 @@ pub fn extract_asyncapi_meta(attrs: &[Attribute]) -> AsyncApiMeta {
                 let value = nested.value()?;
                 let s: syn::LitStr = value.parse()?;
                 meta.content_type = Some(s.value());
+            } else if nested.path.is_ident("binary_payload") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.binary_payload = Some(s);
+            } else if nested.path.is_ident("schema") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.schema = Some(s);
+            } else if nested.path.is_ident("schema_file") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.schema_file = Some(s);
+            } else if nested.path.is_ident("direction") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.direction = Some(s);
+            } else if nested.path.is_ident("schema_format") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.schema_format = Some(s.value());
+            } else if nested.path.is_ident("headers") {
+                // Type whose JSON schema documents the message headers
+                let value = nested.value()?;
+                let path: syn::Path = value.parse()?;
+                meta.headers = Some(path);
+            } else if nested.path.is_ident("correlation_id") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.correlation_id = Some(s.value());
+            } else if nested.path.is_ident("tags") {
+                // Parse array of strings: tags = ["chat", "rooms"]
+                let _ = nested.value()?; // Consume the equals sign
+                let content;
+                syn::bracketed!(content in nested.input);
+                let values: Punctuated<syn::LitStr, Token![,]> =
+                    content.parse_terminated(|stream| stream.parse(), Token![,])?;
+                meta.tags = values.iter().map(|lit| lit.value()).collect();
+            } else if nested.path.is_ident("external_docs") {
+                meta.external_docs = Some(ExternalDocsMeta::parse(&nested)?);
+            } else if nested.path.is_ident("nullable") {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.nullable = Some(s);
+            } else if nested.path.is_ident("instantiate") {
+                // Concrete type to document a generic type as, e.g. "Event<User>"
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                meta.instantiate = Some(s);
+            } else if nested.path.is_ident("max_schema_depth") {
+                let value = nested.value()?;
+                let n: syn::LitInt = value.parse()?;
+                meta.max_schema_depth = Some(n.base10_parse()?);
             } else if nested.path.is_ident("triggers_binary") {
                 // Flag attribute (no value)
                 meta.triggers_binary = true;
+            } else if nested.path.is_ident("no_payload") {
+                // Flag attribute (no value)
+                meta.no_payload = true;
+            } else if nested.path.is_ident("humanize_titles") {
+                // Flag attribute (no value)
+                meta.humanize_titles = true;
+            } else if nested.path.is_ident("deprecated") {
+                // Bare flag, or `deprecated = "reason"`
+                meta.deprecated = true;
+                if nested.input.peek(syn::Token![=]) {
+                    let value = nested.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    meta.deprecated_reason = Some(s.value());
+                }
             }
             Ok(())
         });
@@ -49,6 +209,81 @@ pub fn extract_asyncapi_meta(attrs: &[Attribute]) -> AsyncApiMeta {
     meta
 }
 
+/// Whether `ty` is an instantiation of the type named `name`, e.g. `Event<User>`
+/// or `crate::Event<User>` for `Event`
+pub fn instantiates(ty: &syn::Type, name: &syn::Ident) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == *name),
+        _ => false,
+    }
+}
+
+/// Split a CamelCase identifier into words, e.g. `UserJoin` into `User Join`
+///
+/// Acronyms stay together (`HTTPRequest` becomes `HTTP Request`), and so do digits
+/// and the letters before them (`V2Update` becomes `V2 Update`).
+pub fn humanize_ident(ident: &str) -> String {
+    let chars: Vec<char> = ident.trim_start_matches("r#").chars().collect();
+    let mut title = String::with_capacity(chars.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !title.is_empty() && !title.ends_with(' ') {
+                title.push(' ');
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1).copied();
+        let starts_word = c.is_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+        if starts_word && !title.ends_with(' ') {
+            title.push(' ');
+        }
+        title.push(c);
+    }
+    title.trim_end().to_string()
+}
+
+/// Extract a description from rustdoc comments
+///
+/// Lines are joined with `\n` so Markdown (lists, code blocks, paragraphs) keeps its
+/// shape. Only the single space rustdoc adds after `///` is removed, and leading or
+/// trailing blank lines are dropped.
+pub fn extract_doc_description(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+
+    let description = lines.join("\n");
+    let description = description.trim_matches('\n');
+    if description.trim().is_empty() {
+        None
+    } else {
+        Some(description.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,4 +348,162 @@ mod tests {
         assert!(meta.triggers_binary);
         assert_eq!(meta.content_type, None);
     }
+
+    #[test]
+    fn test_extract_binary_payload() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(triggers_binary, binary_payload = "none")]
+        }];
+
+        let meta = extract_asyncapi_meta(&attrs);
+        assert!(meta.triggers_binary);
+        assert_eq!(
+            meta.binary_payload.map(|s| s.value()),
+            Some("none".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_headers_and_correlation_id() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(headers = RequestHeaders, correlation_id = "$message.header#/requestId")]
+        }];
+
+        let meta = extract_asyncapi_meta(&attrs);
+        let headers = meta.headers.expect("headers should be parsed");
+        assert!(headers.is_ident("RequestHeaders"));
+        assert_eq!(
+            meta.correlation_id,
+            Some("$message.header#/requestId".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_external_docs() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(external_docs(url = "https://example.com/chat", description = "Chat guide"))]
+        }];
+        let docs = extract_asyncapi_meta(&attrs).external_docs.unwrap();
+        assert_eq!(docs.url.as_deref(), Some("https://example.com/chat"));
+        assert_eq!(docs.description.as_deref(), Some("Chat guide"));
+        assert!(external_docs_tokens(Some(&docs)).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(external_docs = "https://example.com/chat")]
+        }];
+        let docs = extract_asyncapi_meta(&attrs).external_docs.unwrap();
+        assert_eq!(docs.url.as_deref(), Some("https://example.com/chat"));
+        assert_eq!(docs.description, None);
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(external_docs(description = "No link"))]
+        }];
+        let docs = extract_asyncapi_meta(&attrs).external_docs.unwrap();
+        let err = external_docs_tokens(Some(&docs)).unwrap_err();
+        assert!(err.to_string().contains("requires a url"));
+    }
+
+    #[test]
+    fn test_extract_max_schema_depth() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(max_schema_depth = 32)]
+        }];
+
+        let meta = extract_asyncapi_meta(&attrs);
+        assert_eq!(meta.max_schema_depth, Some(32));
+        assert_eq!(extract_asyncapi_meta(&[]).max_schema_depth, None);
+    }
+
+    #[test]
+    fn test_extract_nullable() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(nullable = "any_of")]
+        }];
+
+        let meta = extract_asyncapi_meta(&attrs);
+        assert_eq!(meta.nullable.map(|s| s.value()), Some("any_of".to_string()));
+    }
+
+    #[test]
+    fn test_extract_no_payload() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(summary = "Audit event", no_payload)]
+        }];
+
+        let meta = extract_asyncapi_meta(&attrs);
+        assert!(meta.no_payload);
+        assert!(!extract_asyncapi_meta(&[]).no_payload);
+    }
+
+    #[test]
+    fn test_humanize_ident() {
+        assert_eq!(humanize_ident("UserJoin"), "User Join");
+        assert_eq!(humanize_ident("Ping"), "Ping");
+        assert_eq!(humanize_ident("HTTPRequest"), "HTTP Request");
+        assert_eq!(humanize_ident("V2Update"), "V2 Update");
+        assert_eq!(humanize_ident("user_left_room"), "user left room");
+        assert_eq!(humanize_ident("r#Type"), "Type");
+    }
+
+    #[test]
+    fn test_extract_deprecated() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(deprecated, summary = "Old chat")]
+        }];
+        let meta = extract_asyncapi_meta(&attrs);
+        assert!(meta.deprecated);
+        assert_eq!(meta.deprecated_reason, None);
+        assert_eq!(meta.summary, Some("Old chat".to_string()));
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(deprecated = "use chat.v2 instead")]
+        }];
+        let meta = extract_asyncapi_meta(&attrs);
+        assert!(meta.deprecated);
+        assert_eq!(
+            meta.deprecated_reason,
+            Some("use chat.v2 instead".to_string())
+        );
+        assert!(!extract_asyncapi_meta(&[]).deprecated);
+    }
+
+    #[test]
+    fn test_extract_name_override() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(name = "ChatMessageV2", summary = "Chat")]
+        }];
+
+        let meta = extract_asyncapi_meta(&attrs);
+        assert_eq!(meta.name, Some("ChatMessageV2".to_string()));
+        assert_eq!(meta.summary, Some("Chat".to_string()));
+    }
+
+    #[test]
+    fn test_extract_multi_line_doc_description() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[doc = " Sent when a user joins a room."] },
+            parse_quote! { #[doc = ""] },
+            parse_quote! { #[doc = " - Includes the **room** name"] },
+            parse_quote! { #[asyncapi(summary = "Join")] },
+        ];
+
+        assert_eq!(
+            extract_doc_description(&attrs),
+            Some("Sent when a user joins a room.\n\n- Includes the **room** name".to_string())
+        );
+        assert_eq!(extract_doc_description(&[]), None);
+    }
+
+    #[test]
+    fn test_instantiates() {
+        let name: syn::Ident = parse_quote!(Event);
+        assert!(instantiates(&parse_quote!(Event<User>), &name));
+        assert!(instantiates(&parse_quote!(crate::Event<User>), &name));
+        assert!(!instantiates(&parse_quote!(Other<User>), &name));
+        assert!(!instantiates(&parse_quote!(&Event<User>), &name));
+        assert!(!instantiates(
+            &parse_quote!(<Event<User> as Trait>::Out),
+            &name
+        ));
+    }
 }
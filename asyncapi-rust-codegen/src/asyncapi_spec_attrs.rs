@@ -2,18 +2,64 @@
 
 use syn::{Attribute, Path};
 
+use crate::asyncapi_attrs::ExternalDocsMeta;
+
 /// AsyncAPI spec metadata extracted from attributes
 #[derive(Debug, Default, Clone)]
 pub struct AsyncApiSpecMeta {
-    pub title: Option<String>,
-    pub version: Option<String>,
+    /// Any `&str` expression, e.g. a literal or a shared `const`
+    pub title: Option<syn::Expr>,
+    pub version: Option<syn::Expr>,
     pub description: Option<String>,
+    /// Path of a Markdown file used as the description, relative to `CARGO_MANIFEST_DIR`
+    pub description_file: Option<syn::LitStr>,
+    pub summary: Option<String>,
+    pub strict: bool,
+    pub ref_style: Option<String>,
+    pub ref_base: Option<String>,
+    /// Inline component messages into channels, without their `name`
+    pub omit_channel_message_names: bool,
+    pub default_content_type: Option<String>,
+    /// Function returning the `ContentTypeDefaults` to apply to the generated spec
+    pub content_type_defaults: Option<Path>,
+    pub contact: Option<ContactMeta>,
+    pub license: Option<LicenseMeta>,
+    pub tags: Vec<TagMeta>,
     pub servers: Vec<ServerMeta>,
     pub channels: Vec<ChannelMeta>,
     pub operations: Vec<OperationMeta>,
+    pub replies: Vec<ReplyMeta>,
     pub message_types: Vec<Path>,
 }
 
+/// API contact metadata from `contact(...)`
+#[derive(Debug, Clone, Default)]
+pub struct ContactMeta {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub email: Option<String>,
+}
+
+/// API license metadata from `license(...)`
+#[derive(Debug, Clone)]
+pub struct LicenseMeta {
+    /// Required; checked by the derive so the error can point at `path`
+    pub name: Option<String>,
+    pub url: Option<String>,
+    /// The `license` key itself, for error spans
+    pub path: Path,
+}
+
+/// API tag metadata
+#[derive(Debug, Clone)]
+pub struct TagMeta {
+    pub name: String,
+    pub description: Option<String>,
+    pub external_docs: Option<ExternalDocsMeta>,
+    /// `true` for `tag(...)` attributes, `false` for tags from doc comments
+    pub explicit: bool,
+}
+
 /// Server metadata
 #[derive(Debug, Clone)]
 pub struct ServerMeta {
@@ -40,9 +86,17 @@ pub struct ServerVariableMeta {
 pub struct ChannelMeta {
     pub name: String,
     pub address: Option<String>,
+    pub title: Option<String>,
     #[allow(dead_code)] // Reserved for future use
     pub description: Option<String>,
     pub parameters: Vec<ParameterMeta>,
+    /// Name of reusable bindings in `components.channelBindings`
+    pub bindings_ref: Option<String>,
+    /// `Some(true)` for `deprecated` / `deprecated = true`
+    pub deprecated: Option<bool>,
+    /// Content type of messages on the channel that don't declare one
+    pub default_content_type: Option<String>,
+    pub external_docs: Option<ExternalDocsMeta>,
 }
 
 /// Channel parameter metadata
@@ -52,6 +106,8 @@ pub struct ParameterMeta {
     pub description: Option<String>,
     pub schema_type: Option<String>,
     pub format: Option<String>,
+    /// Typed example values, emitted as the parameter schema's `examples`
+    pub examples: Vec<syn::Expr>,
 }
 
 /// Operation metadata
@@ -60,8 +116,51 @@ pub struct OperationMeta {
     pub name: String,
     pub action: String, // "send" or "receive"
     pub channel: String,
-    #[allow(dead_code)] // Reserved for future use
+    pub title: Option<String>,
+    pub summary: Option<String>,
     pub description: Option<String>,
+    /// Tag names, from `tags = ["...", ...]`
+    pub tags: Vec<String>,
+    /// Security scheme names, from `security = ["...", ...]`
+    pub security: Vec<String>,
+    pub deprecated: bool,
+    pub deprecated_reason: Option<String>,
+    pub messages: Vec<Path>,
+    /// Type whose messages matching `action` are used, from `messages_from = Type`
+    pub messages_from: Option<Path>,
+    pub ws_subprotocol: Option<String>,
+    /// Sort hint; lower values come first, unordered operations go last
+    pub order: Option<i64>,
+    /// Channel replies arrive on, from `reply_channel = "..."` or `reply(channel = "...")`
+    pub reply_channel: Option<String>,
+    /// Reply message types, from `reply(messages = [...])`
+    pub reply_messages: Vec<Path>,
+    /// Name of a `#[asyncapi_reply(...)]`, from `reply(ref = "...")`
+    pub reply_ref: Option<String>,
+    pub external_docs: Option<ExternalDocsMeta>,
+}
+
+/// Operation metadata from `#[asyncapi_operation_fn(...)]` on a function
+#[cfg(feature = "operation-fn")]
+#[derive(Debug, Clone, Default)]
+pub struct OperationFnMeta {
+    /// `AsyncApi` type the operation belongs to
+    pub api: Option<Path>,
+    pub name: Option<String>,
+    pub action: Option<syn::LitStr>,
+    pub channel: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub messages: Vec<Path>,
+    /// Sort hint, like `#[asyncapi_operation(order = ...)]`
+    pub order: Option<i64>,
+}
+
+/// Reusable reply metadata, emitted under `components.replies`
+#[derive(Debug, Clone)]
+pub struct ReplyMeta {
+    pub name: String,
+    pub channel: Option<String>,
     pub messages: Vec<Path>,
 }
 
@@ -74,20 +173,119 @@ pub fn extract_asyncapi_spec_meta(attrs: &[Attribute]) -> AsyncApiSpecMeta {
             // Parse main asyncapi attributes
             let _ = attr.parse_nested_meta(|nested| {
                 if nested.path.is_ident("title") {
+                    meta.title = Some(nested.value()?.parse()?);
+                } else if nested.path.is_ident("version") {
+                    meta.version = Some(nested.value()?.parse()?);
+                } else if nested.path.is_ident("description") {
                     let value = nested.value()?;
                     let s: syn::LitStr = value.parse()?;
-                    meta.title = Some(s.value());
-                } else if nested.path.is_ident("version") {
+                    meta.description = Some(s.value());
+                } else if nested.path.is_ident("description_file") {
+                    let value = nested.value()?;
+                    meta.description_file = Some(value.parse()?);
+                } else if nested.path.is_ident("summary") {
                     let value = nested.value()?;
                     let s: syn::LitStr = value.parse()?;
-                    meta.version = Some(s.value());
-                } else if nested.path.is_ident("description") {
+                    meta.summary = Some(s.value());
+                } else if nested.path.is_ident("strict") {
+                    // Flag attribute (no value)
+                    meta.strict = true;
+                } else if nested.path.is_ident("omit_channel_message_names") {
+                    meta.omit_channel_message_names = true;
+                } else if nested.path.is_ident("ref_style") {
                     let value = nested.value()?;
                     let s: syn::LitStr = value.parse()?;
-                    meta.description = Some(s.value());
+                    meta.ref_style = Some(s.value());
+                } else if nested.path.is_ident("ref_base") {
+                    let value = nested.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    meta.ref_base = Some(s.value());
+                } else if nested.path.is_ident("default_content_type") {
+                    let value = nested.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    meta.default_content_type = Some(s.value());
+                } else if nested.path.is_ident("content_type_defaults") {
+                    meta.content_type_defaults = Some(nested.value()?.parse()?);
+                } else if nested.path.is_ident("contact") {
+                    let mut contact = ContactMeta::default();
+                    nested.parse_nested_meta(|inner| {
+                        let field = if inner.path.is_ident("name") {
+                            &mut contact.name
+                        } else if inner.path.is_ident("url") {
+                            &mut contact.url
+                        } else if inner.path.is_ident("email") {
+                            &mut contact.email
+                        } else {
+                            return Ok(());
+                        };
+                        let s: syn::LitStr = inner.value()?.parse()?;
+                        *field = Some(s.value());
+                        Ok(())
+                    })?;
+                    meta.contact = Some(contact);
+                } else if nested.path.is_ident("license") {
+                    let mut license = LicenseMeta {
+                        name: None,
+                        url: None,
+                        path: nested.path.clone(),
+                    };
+                    nested.parse_nested_meta(|inner| {
+                        if inner.path.is_ident("name") {
+                            let s: syn::LitStr = inner.value()?.parse()?;
+                            license.name = Some(s.value());
+                        } else if inner.path.is_ident("url") {
+                            let s: syn::LitStr = inner.value()?.parse()?;
+                            license.url = Some(s.value());
+                        }
+                        Ok(())
+                    })?;
+                    meta.license = Some(license);
+                } else if nested.path.is_ident("tag") {
+                    // Parse nested tag(...) attribute
+                    let mut name = None;
+                    let mut description = None;
+                    let mut external_docs = None;
+                    nested.parse_nested_meta(|inner| {
+                        if inner.path.is_ident("name") {
+                            let value = inner.value()?;
+                            let s: syn::LitStr = value.parse()?;
+                            name = Some(s.value());
+                        } else if inner.path.is_ident("description") {
+                            let value = inner.value()?;
+                            let s: syn::LitStr = value.parse()?;
+                            description = Some(s.value());
+                        } else if inner.path.is_ident("external_docs") {
+                            external_docs = Some(ExternalDocsMeta::parse(&inner)?);
+                        }
+                        Ok(())
+                    })?;
+                    if let Some(name) = name {
+                        add_tag(
+                            &mut meta.tags,
+                            TagMeta {
+                                name,
+                                description,
+                                external_docs,
+                                explicit: true,
+                            },
+                        );
+                    }
                 }
                 Ok(())
             });
+        } else if attr.path().is_ident("doc") {
+            // Doc comment lines like `Tags: chat, realtime` also contribute tags
+            for name in extract_doc_tags(attr) {
+                add_tag(
+                    &mut meta.tags,
+                    TagMeta {
+                        name,
+                        description: None,
+                        external_docs: None,
+                        explicit: false,
+                    },
+                );
+            }
         } else if attr.path().is_ident("asyncapi_server") {
             // Parse server attributes
             if let Some(server) = extract_server(attr) {
@@ -103,6 +301,11 @@ pub fn extract_asyncapi_spec_meta(attrs: &[Attribute]) -> AsyncApiSpecMeta {
             if let Some(operation) = extract_operation(attr) {
                 meta.operations.push(operation);
             }
+        } else if attr.path().is_ident("asyncapi_reply") {
+            // Parse reusable reply attributes
+            if let Some(reply) = extract_reply(attr) {
+                meta.replies.push(reply);
+            }
         } else if attr.path().is_ident("asyncapi_messages") {
             // Parse message type references
             if let Ok(types) = extract_message_types(attr) {
@@ -114,6 +317,114 @@ pub fn extract_asyncapi_spec_meta(attrs: &[Attribute]) -> AsyncApiSpecMeta {
     meta
 }
 
+/// Check that every server uses a protocol recognized by AsyncAPI
+///
+/// Returns an error message naming the first server with an unknown protocol.
+pub fn validate_server_protocols(meta: &AsyncApiSpecMeta) -> Result<(), String> {
+    for server in &meta.servers {
+        if !asyncapi_rust_models::is_known_protocol(&server.protocol) {
+            return Err(format!(
+                "Server '{}' uses unknown protocol '{}'; expected one of: {}",
+                server.name,
+                server.protocol,
+                asyncapi_rust_models::KNOWN_PROTOCOLS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every server host is a bare authority such as `api.example.com:443`
+///
+/// Returns an error message naming the first server with a URL-like host.
+pub fn validate_server_hosts(meta: &AsyncApiSpecMeta) -> Result<(), String> {
+    for server in &meta.servers {
+        if let Err(reason) = asyncapi_rust_models::check_server_host(&server.host, &server.protocol)
+        {
+            return Err(format!("Server '{}': {}", server.name, reason));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every `{placeholder}` in a server pathname has a `variable(...)`
+///
+/// Returns an error message naming the first server and missing variable.
+pub fn validate_server_variables(meta: &AsyncApiSpecMeta) -> Result<(), String> {
+    for server in &meta.servers {
+        let Some(pathname) = &server.pathname else {
+            continue;
+        };
+        for placeholder in address_placeholders(pathname) {
+            if !server.variables.iter().any(|var| var.name == placeholder) {
+                return Err(format!(
+                    "Server '{}' pathname '{}' references variable '{}', which is not defined; add variable(name = \"{}\", ...)",
+                    server.name, pathname, placeholder, placeholder
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that every operation `reply_channel` names a declared channel
+pub fn validate_reply_channels(meta: &AsyncApiSpecMeta) -> Result<(), String> {
+    for operation in &meta.operations {
+        let Some(reply_channel) = &operation.reply_channel else {
+            continue;
+        };
+        if !meta
+            .channels
+            .iter()
+            .any(|channel| &channel.name == reply_channel)
+        {
+            return Err(format!(
+                "Operation '{}' has reply_channel '{}', which is not a declared channel",
+                operation.name, reply_channel
+            ));
+        }
+    }
+    for reply in &meta.replies {
+        let Some(reply_channel) = &reply.channel else {
+            continue;
+        };
+        if !meta
+            .channels
+            .iter()
+            .any(|channel| &channel.name == reply_channel)
+        {
+            return Err(format!(
+                "Reply '{}' has channel '{}', which is not a declared channel",
+                reply.name, reply_channel
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every `reply(ref = "...")` names a declared `#[asyncapi_reply(...)]`
+/// and isn't mixed with an inline reply
+pub fn validate_reply_refs(meta: &AsyncApiSpecMeta) -> Result<(), String> {
+    for operation in &meta.operations {
+        let Some(reply_ref) = &operation.reply_ref else {
+            continue;
+        };
+        if operation.reply_channel.is_some() || !operation.reply_messages.is_empty() {
+            return Err(format!(
+                "Operation '{}' combines reply(ref = \"{}\") with an inline reply channel or messages",
+                operation.name, reply_ref
+            ));
+        }
+        if !meta.replies.iter().any(|reply| &reply.name == reply_ref) {
+            return Err(format!(
+                "Operation '{}' references reply '{}', which is not declared with #[asyncapi_reply(...)]",
+                operation.name, reply_ref
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Extract message type paths from `#[asyncapi_messages(...)]` attribute
 fn extract_message_types(attr: &Attribute) -> syn::Result<Vec<Path>> {
     use syn::Token;
@@ -124,6 +435,42 @@ fn extract_message_types(attr: &Attribute) -> syn::Result<Vec<Path>> {
     Ok(types.into_iter().collect())
 }
 
+/// Add a tag, deduplicating by name in declaration order
+///
+/// An explicit `tag(...)` replaces a doc-comment tag of the same name in place;
+/// otherwise the first tag with a given name is kept.
+fn add_tag(tags: &mut Vec<TagMeta>, tag: TagMeta) {
+    match tags.iter_mut().find(|existing| existing.name == tag.name) {
+        Some(existing) if tag.explicit && !existing.explicit => *existing = tag,
+        Some(_) => {}
+        None => tags.push(tag),
+    }
+}
+
+/// Extract tag names from a `Tags: a, b` doc comment line
+fn extract_doc_tags(attr: &Attribute) -> Vec<String> {
+    let syn::Meta::NameValue(name_value) = &attr.meta else {
+        return Vec::new();
+    };
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(doc),
+        ..
+    }) = &name_value.value
+    else {
+        return Vec::new();
+    };
+
+    match doc.value().trim().strip_prefix("Tags:") {
+        Some(names) => names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
 /// Extract server metadata from `#[asyncapi_server(...)]` attribute
 fn extract_server(attr: &Attribute) -> Option<ServerMeta> {
     let mut name = None;
@@ -228,8 +575,13 @@ fn extract_server_variable(nested: &syn::meta::ParseNestedMeta) -> Option<Server
 fn extract_channel(attr: &Attribute) -> Option<ChannelMeta> {
     let mut name = None;
     let mut address = None;
+    let mut title = None;
     let mut description = None;
     let mut parameters = Vec::new();
+    let mut bindings_ref = None;
+    let mut deprecated = None;
+    let mut default_content_type = None;
+    let mut external_docs = None;
 
     let _ = attr.parse_nested_meta(|nested| {
         if nested.path.is_ident("name") {
@@ -240,10 +592,33 @@ fn extract_channel(attr: &Attribute) -> Option<ChannelMeta> {
             let value = nested.value()?;
             let s: syn::LitStr = value.parse()?;
             address = Some(s.value());
+        } else if nested.path.is_ident("title") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            title = Some(s.value());
         } else if nested.path.is_ident("description") {
             let value = nested.value()?;
             let s: syn::LitStr = value.parse()?;
             description = Some(s.value());
+        } else if nested.path.is_ident("bindings_ref") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            bindings_ref = Some(s.value());
+        } else if nested.path.is_ident("deprecated") {
+            // Bare flag, or `deprecated = true|false`
+            if nested.input.peek(syn::Token![=]) {
+                let value = nested.value()?;
+                let b: syn::LitBool = value.parse()?;
+                deprecated = Some(b.value);
+            } else {
+                deprecated = Some(true);
+            }
+        } else if nested.path.is_ident("default_content_type") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            default_content_type = Some(s.value());
+        } else if nested.path.is_ident("external_docs") {
+            external_docs = Some(ExternalDocsMeta::parse(&nested)?);
         } else if nested.path.is_ident("parameter") {
             // Parse nested parameter(...) attribute
             if let Some(param) = extract_channel_parameter(&nested) {
@@ -253,21 +628,74 @@ fn extract_channel(attr: &Attribute) -> Option<ChannelMeta> {
         Ok(())
     });
 
+    // Infer string parameters for address placeholders that weren't declared
+    if let Some(address) = &address {
+        for placeholder in address_placeholders(address) {
+            if !parameters.iter().any(|p| p.name == placeholder) {
+                parameters.push(ParameterMeta {
+                    name: placeholder,
+                    description: None,
+                    schema_type: Some("string".to_string()),
+                    format: None,
+                    examples: Vec::new(),
+                });
+            }
+        }
+    }
+
     // Require name
     Some(ChannelMeta {
         name: name?,
         address,
+        title,
         description,
         parameters,
+        bindings_ref,
+        deprecated,
+        default_content_type,
+        external_docs,
     })
 }
 
+/// Extract `{placeholder}` names from a channel address, in order
+fn address_placeholders(address: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut rest = address;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start + 1..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start + 1..start + 1 + len];
+        if !placeholder.is_empty() && !placeholders.iter().any(|p| p == placeholder) {
+            placeholders.push(placeholder.to_string());
+        }
+        rest = &rest[start + 2 + len..];
+    }
+    placeholders
+}
+
+/// Get the declared parameters of a channel that don't appear in its address
+pub fn unused_channel_parameters(channel: &ChannelMeta) -> Vec<&str> {
+    let placeholders = channel
+        .address
+        .as_deref()
+        .map(address_placeholders)
+        .unwrap_or_default();
+    channel
+        .parameters
+        .iter()
+        .filter(|param| !placeholders.contains(&param.name))
+        .map(|param| param.name.as_str())
+        .collect()
+}
+
 /// Extract channel parameter from nested meta (called from within parse_nested_meta)
 fn extract_channel_parameter(nested: &syn::meta::ParseNestedMeta) -> Option<ParameterMeta> {
     let mut name = None;
     let mut description = None;
     let mut schema_type = None;
     let mut format = None;
+    let mut examples = Vec::new();
 
     let _ = nested.parse_nested_meta(|inner| {
         if inner.path.is_ident("name") {
@@ -286,6 +714,14 @@ fn extract_channel_parameter(nested: &syn::meta::ParseNestedMeta) -> Option<Para
             let value = inner.value()?;
             let s: syn::LitStr = value.parse()?;
             format = Some(s.value());
+        } else if inner.path.is_ident("examples") {
+            // Parse array of values of any JSON type: examples = [42, 7]
+            let _ = inner.value()?; // Consume the equals sign
+            let content;
+            syn::bracketed!(content in inner.input);
+            let values: syn::punctuated::Punctuated<syn::Expr, syn::Token![,]> =
+                content.parse_terminated(|stream| stream.parse(), syn::Token![,])?;
+            examples = values.into_iter().collect();
         }
         Ok(())
     });
@@ -295,9 +731,57 @@ fn extract_channel_parameter(nested: &syn::meta::ParseNestedMeta) -> Option<Para
         description,
         schema_type,
         format,
+        examples,
     })
 }
 
+/// Extract operation metadata from an `#[asyncapi_operation_fn(...)]` attribute
+#[cfg(feature = "operation-fn")]
+pub fn extract_operation_fn(attr: &Attribute) -> OperationFnMeta {
+    use syn::Token;
+    use syn::punctuated::Punctuated;
+
+    let mut meta = OperationFnMeta::default();
+    let _ = attr.parse_nested_meta(|nested| {
+        if nested.path.is_ident("api") {
+            let value = nested.value()?;
+            meta.api = Some(value.parse()?);
+        } else if nested.path.is_ident("name") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            meta.name = Some(s.value());
+        } else if nested.path.is_ident("action") {
+            let value = nested.value()?;
+            meta.action = Some(value.parse()?);
+        } else if nested.path.is_ident("channel") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            meta.channel = Some(s.value());
+        } else if nested.path.is_ident("summary") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            meta.summary = Some(s.value());
+        } else if nested.path.is_ident("description") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            meta.description = Some(s.value());
+        } else if nested.path.is_ident("messages") {
+            let _ = nested.value()?; // Consume the equals sign
+            let content;
+            syn::bracketed!(content in nested.input);
+            let types: Punctuated<Path, Token![,]> =
+                content.parse_terminated(|stream| stream.parse(), Token![,])?;
+            meta.messages = types.into_iter().collect();
+        } else if nested.path.is_ident("order") {
+            let value = nested.value()?;
+            let n: syn::LitInt = value.parse()?;
+            meta.order = Some(n.base10_parse()?);
+        }
+        Ok(())
+    });
+    meta
+}
+
 /// Extract operation metadata from `#[asyncapi_operation(...)]` attribute
 fn extract_operation(attr: &Attribute) -> Option<OperationMeta> {
     use syn::Token;
@@ -306,8 +790,21 @@ fn extract_operation(attr: &Attribute) -> Option<OperationMeta> {
     let mut name = None;
     let mut action = None;
     let mut channel = None;
+    let mut title = None;
+    let mut summary = None;
     let mut description = None;
+    let mut tags = Vec::new();
+    let mut security = Vec::new();
+    let mut deprecated = false;
+    let mut deprecated_reason = None;
     let mut messages = Vec::new();
+    let mut messages_from = None;
+    let mut ws_subprotocol = None;
+    let mut order = None;
+    let mut reply_channel = None;
+    let mut reply_messages = Vec::new();
+    let mut reply_ref = None;
+    let mut external_docs = None;
 
     let _ = attr.parse_nested_meta(|nested| {
         if nested.path.is_ident("name") {
@@ -322,10 +819,39 @@ fn extract_operation(attr: &Attribute) -> Option<OperationMeta> {
             let value = nested.value()?;
             let s: syn::LitStr = value.parse()?;
             channel = Some(s.value());
+        } else if nested.path.is_ident("title") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            title = Some(s.value());
+        } else if nested.path.is_ident("summary") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            summary = Some(s.value());
         } else if nested.path.is_ident("description") {
             let value = nested.value()?;
             let s: syn::LitStr = value.parse()?;
             description = Some(s.value());
+        } else if nested.path.is_ident("tags") || nested.path.is_ident("security") {
+            // Parse array of strings: tags = ["admin", "chat"]
+            let _ = nested.value()?; // Consume the equals sign
+            let content;
+            syn::bracketed!(content in nested.input);
+            let values: Punctuated<syn::LitStr, Token![,]> =
+                content.parse_terminated(|stream| stream.parse(), Token![,])?;
+            let values = values.iter().map(|lit| lit.value()).collect();
+            if nested.path.is_ident("tags") {
+                tags = values;
+            } else {
+                security = values;
+            }
+        } else if nested.path.is_ident("deprecated") {
+            // Bare flag, or `deprecated = "reason"`
+            deprecated = true;
+            if nested.input.peek(Token![=]) {
+                let value = nested.value()?;
+                let s: syn::LitStr = value.parse()?;
+                deprecated_reason = Some(s.value());
+            }
         } else if nested.path.is_ident("messages") {
             // Parse array of type paths: messages = [Type1, Type2, ...]
             let _ = nested.value()?; // Parse the equals sign and prepare for value parsing
@@ -334,6 +860,49 @@ fn extract_operation(attr: &Attribute) -> Option<OperationMeta> {
             let types: Punctuated<Path, Token![,]> =
                 content.parse_terminated(|stream| stream.parse(), Token![,])?;
             messages = types.into_iter().collect();
+        } else if nested.path.is_ident("messages_from") {
+            messages_from = Some(nested.value()?.parse()?);
+        } else if nested.path.is_ident("order") {
+            let value = nested.value()?;
+            let n: syn::LitInt = value.parse()?;
+            order = Some(n.base10_parse()?);
+        } else if nested.path.is_ident("reply_channel") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            reply_channel = Some(s.value());
+        } else if nested.path.is_ident("reply") {
+            // Parse nested reply(channel = "...", messages = [...]) or reply(ref = "...")
+            nested.parse_nested_meta(|inner| {
+                if inner.path.is_ident("ref") {
+                    let value = inner.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    reply_ref = Some(s.value());
+                } else if inner.path.is_ident("channel") {
+                    let value = inner.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    reply_channel = Some(s.value());
+                } else if inner.path.is_ident("messages") {
+                    let _ = inner.value()?; // Consume the equals sign
+                    let content;
+                    syn::bracketed!(content in inner.input);
+                    let types: Punctuated<Path, Token![,]> =
+                        content.parse_terminated(|stream| stream.parse(), Token![,])?;
+                    reply_messages = types.into_iter().collect();
+                }
+                Ok(())
+            })?;
+        } else if nested.path.is_ident("ws") {
+            // Parse nested ws(...) binding attribute
+            nested.parse_nested_meta(|inner| {
+                if inner.path.is_ident("subprotocol") {
+                    let value = inner.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    ws_subprotocol = Some(s.value());
+                }
+                Ok(())
+            })?;
+        } else if nested.path.is_ident("external_docs") {
+            external_docs = Some(ExternalDocsMeta::parse(&nested)?);
         }
         Ok(())
     });
@@ -343,17 +912,89 @@ fn extract_operation(attr: &Attribute) -> Option<OperationMeta> {
         name: name?,
         action: action?,
         channel: channel?,
+        title,
+        summary,
         description,
+        tags,
+        security,
+        deprecated,
+        deprecated_reason,
+        messages,
+        messages_from,
+        ws_subprotocol,
+        order,
+        reply_channel,
+        reply_messages,
+        reply_ref,
+        external_docs,
+    })
+}
+
+/// Extract reusable reply metadata from `#[asyncapi_reply(...)]` attribute
+fn extract_reply(attr: &Attribute) -> Option<ReplyMeta> {
+    use syn::Token;
+    use syn::punctuated::Punctuated;
+
+    let mut name = None;
+    let mut channel = None;
+    let mut messages = Vec::new();
+
+    let _ = attr.parse_nested_meta(|nested| {
+        if nested.path.is_ident("name") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            name = Some(s.value());
+        } else if nested.path.is_ident("channel") {
+            let value = nested.value()?;
+            let s: syn::LitStr = value.parse()?;
+            channel = Some(s.value());
+        } else if nested.path.is_ident("messages") {
+            let _ = nested.value()?; // Consume the equals sign
+            let content;
+            syn::bracketed!(content in nested.input);
+            let types: Punctuated<Path, Token![,]> =
+                content.parse_terminated(|stream| stream.parse(), Token![,])?;
+            messages = types.into_iter().collect();
+        }
+        Ok(())
+    });
+
+    // Require name
+    Some(ReplyMeta {
+        name: name?,
+        channel,
         messages,
     })
 }
 
+/// Operations sorted by their `order` hint, then by declaration order
+///
+/// Operations without an `order` keep their relative position after all
+/// ordered ones.
+pub fn ordered_operations(operations: &[OperationMeta]) -> Vec<&OperationMeta> {
+    let mut operations: Vec<&OperationMeta> = operations.iter().collect();
+    // sort_by_key is stable, so ties keep declaration order
+    operations.sort_by_key(|operation| (operation.order.is_none(), operation.order));
+    operations
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use quote::quote;
     use syn::parse_quote;
 
+    /// Value of a string literal expression
+    fn lit_str(expr: &Option<syn::Expr>) -> Option<String> {
+        match expr {
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            })) => Some(s.value()),
+            _ => None,
+        }
+    }
+
     #[test]
     fn test_extract_title_and_version() {
         let attrs: Vec<Attribute> = vec![parse_quote! {
@@ -361,11 +1002,27 @@ mod tests {
         }];
 
         let meta = extract_asyncapi_spec_meta(&attrs);
-        assert_eq!(meta.title, Some("Chat API".to_string()));
-        assert_eq!(meta.version, Some("1.0.0".to_string()));
+        assert_eq!(lit_str(&meta.title), Some("Chat API".to_string()));
+        assert_eq!(lit_str(&meta.version), Some("1.0.0".to_string()));
         assert_eq!(meta.description, None);
     }
 
+    #[test]
+    fn test_extract_title_and_version_from_consts() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = TITLE, version = crate::meta::VERSION)]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let title = meta.title.unwrap();
+        let version = meta.version.unwrap();
+        assert_eq!(quote::quote!(#title).to_string(), "TITLE");
+        assert_eq!(
+            quote::quote!(#version).to_string(),
+            "crate :: meta :: VERSION"
+        );
+    }
+
     #[test]
     fn test_extract_with_description() {
         let attrs: Vec<Attribute> = vec![parse_quote! {
@@ -377,8 +1034,8 @@ mod tests {
         }];
 
         let meta = extract_asyncapi_spec_meta(&attrs);
-        assert_eq!(meta.title, Some("My API".to_string()));
-        assert_eq!(meta.version, Some("2.0.0".to_string()));
+        assert_eq!(lit_str(&meta.title), Some("My API".to_string()));
+        assert_eq!(lit_str(&meta.version), Some("2.0.0".to_string()));
         assert_eq!(meta.description, Some("A great API".to_string()));
     }
 
@@ -389,8 +1046,8 @@ mod tests {
         }];
 
         let meta = extract_asyncapi_spec_meta(&attrs);
-        assert_eq!(meta.title, None);
-        assert_eq!(meta.version, None);
+        assert!(meta.title.is_none());
+        assert!(meta.version.is_none());
         assert_eq!(meta.description, None);
     }
 
@@ -438,6 +1095,17 @@ mod tests {
         assert_eq!(meta.channels.len(), 1);
         assert_eq!(meta.channels[0].name, "chat");
         assert_eq!(meta.channels[0].address, Some("/ws/chat".to_string()));
+        assert_eq!(meta.channels[0].title, None);
+    }
+
+    #[test]
+    fn test_extract_channel_with_title() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(name = "chat", address = "/ws/chat", title = "Chat Room")]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.channels[0].title, Some("Chat Room".to_string()));
     }
 
     #[test]
@@ -464,7 +1132,7 @@ mod tests {
         ];
 
         let meta = extract_asyncapi_spec_meta(&attrs);
-        assert_eq!(meta.title, Some("Chat API".to_string()));
+        assert_eq!(lit_str(&meta.title), Some("Chat API".to_string()));
         assert_eq!(meta.servers.len(), 1);
         assert_eq!(meta.channels.len(), 1);
         assert_eq!(meta.operations.len(), 2);
@@ -621,6 +1289,67 @@ mod tests {
         assert_eq!(param1.format, Some("int64".to_string()));
     }
 
+    #[test]
+    fn test_extract_channel_parameter_examples() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(
+                name = "rooms",
+                address = "/rooms/{roomId}",
+                parameter(name = "roomId", schema_type = "integer", examples = [42, -1])
+            )]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let examples = &meta.channels[0].parameters[0].examples;
+        assert_eq!(examples.len(), 2);
+        assert_eq!(quote!(#(#examples),*).to_string(), "42 , - 1");
+    }
+
+    #[test]
+    fn test_infer_channel_parameters_from_address() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(
+                name = "room",
+                address = "/ws/{userId}/{room}",
+                parameter(name = "userId", schema_type = "integer")
+            )]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let parameters = &meta.channels[0].parameters;
+        assert_eq!(parameters.len(), 2);
+        // Explicit parameters take precedence over inferred ones
+        assert_eq!(parameters[0].name, "userId");
+        assert_eq!(parameters[0].schema_type, Some("integer".to_string()));
+        assert_eq!(parameters[1].name, "room");
+        assert_eq!(parameters[1].schema_type, Some("string".to_string()));
+        assert!(unused_channel_parameters(&meta.channels[0]).is_empty());
+    }
+
+    #[test]
+    fn test_unused_channel_parameters() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(
+                name = "room",
+                address = "/ws/{room}",
+                parameter(name = "userId", schema_type = "integer")
+            )]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(unused_channel_parameters(&meta.channels[0]), vec!["userId"]);
+    }
+
+    #[test]
+    fn test_address_placeholders() {
+        assert_eq!(
+            address_placeholders("/ws/{userId}/{room}/{userId}"),
+            vec!["userId".to_string(), "room".to_string()]
+        );
+        assert!(address_placeholders("/ws/chat").is_empty());
+        assert!(address_placeholders("/ws/{unterminated").is_empty());
+    }
+
     #[test]
     fn test_extract_operation_with_messages() {
         let attrs: Vec<Attribute> = vec![parse_quote! {
@@ -669,4 +1398,262 @@ mod tests {
         );
         assert_eq!(quote!(#path1).to_string(), "crate :: SystemMessage");
     }
+
+    #[test]
+    fn test_extract_strict() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0", strict)]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert!(meta.strict);
+        assert_eq!(lit_str(&meta.version), Some("1.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_extract_omit_channel_message_names() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0", omit_channel_message_names)]
+        }];
+        assert!(extract_asyncapi_spec_meta(&attrs).omit_channel_message_names);
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0")]
+        }];
+        assert!(!extract_asyncapi_spec_meta(&attrs).omit_channel_message_names);
+    }
+
+    #[test]
+    fn test_extract_ref_style() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0", ref_style = "components")]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.ref_style, Some("components".to_string()));
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0")]
+        }];
+        assert_eq!(extract_asyncapi_spec_meta(&attrs).ref_style, None);
+    }
+
+    #[test]
+    fn test_extract_ref_base() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0", ref_base = "./messages.json")]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.ref_base, Some("./messages.json".to_string()));
+    }
+
+    #[test]
+    fn test_extract_content_type_defaults() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(title = "API", version = "1.0.0", default_content_type = "text/plain", content_type_defaults = defaults::broker)]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.default_content_type, Some("text/plain".to_string()));
+        let path = meta.content_type_defaults.unwrap();
+        assert_eq!(quote::quote!(#path).to_string(), "defaults :: broker");
+    }
+
+    #[test]
+    fn test_extract_tags_dedupes_with_explicit_winning() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[doc = " Chat API"] },
+            parse_quote! { #[doc = " Tags: chat, realtime, chat"] },
+            parse_quote! {
+                #[asyncapi(
+                    title = "API",
+                    version = "1.0.0",
+                    tag(name = "admin", description = "Admin operations"),
+                    tag(name = "realtime", description = "Realtime messaging")
+                )]
+            },
+            parse_quote! { #[asyncapi(tag(name = "admin", description = "Duplicate"))] },
+        ];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let names: Vec<&str> = meta.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["chat", "realtime", "admin"]);
+        assert_eq!(meta.tags[0].description, None);
+        assert_eq!(
+            meta.tags[1].description,
+            Some("Realtime messaging".to_string())
+        );
+        assert!(meta.tags[1].explicit);
+        assert_eq!(
+            meta.tags[2].description,
+            Some("Admin operations".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_server_variables() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_server(
+                name = "prod",
+                host = "api.example.com",
+                protocol = "wss",
+                pathname = "/api/{version}/ws/{userId}",
+                variable(name = "version")
+            )]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let err = validate_server_variables(&meta).unwrap_err();
+        assert!(err.contains("'prod'"));
+        assert!(err.contains("'userId'"));
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_server(
+                name = "prod",
+                host = "api.example.com",
+                protocol = "wss",
+                pathname = "/api/{version}",
+                variable(name = "version")
+            )]
+        }];
+        assert!(validate_server_variables(&extract_asyncapi_spec_meta(&attrs)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_server_protocols() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_server(name = "prod", host = "api.example.com", protocol = "wss")] },
+            parse_quote! { #[asyncapi_server(name = "events", host = "kafka.example.com:9092", protocol = "kafka")] },
+        ];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert!(validate_server_protocols(&meta).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_server(name = "prod", host = "api.example.com", protocol = "websocket")]
+        }];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let err = validate_server_protocols(&meta).unwrap_err();
+        assert!(err.contains("Server 'prod' uses unknown protocol 'websocket'"));
+    }
+
+    #[test]
+    fn test_validate_server_hosts() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_server(name = "prod", host = "api.example.com:443", protocol = "wss")] },
+            parse_quote! { #[asyncapi_server(name = "local", host = "localhost", protocol = "ws")] },
+        ];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert!(validate_server_hosts(&meta).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_server(name = "prod", host = "https://api.example.com", protocol = "wss")]
+        }];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let err = validate_server_hosts(&meta).unwrap_err();
+        assert!(err.starts_with("Server 'prod': host 'https://api.example.com'"));
+    }
+
+    #[test]
+    fn test_extract_operation_with_ws_binding() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_operation(name = "sendMessage", action = "send", channel = "chat", ws(subprotocol = "json"))]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.operations.len(), 1);
+        assert_eq!(meta.operations[0].ws_subprotocol, Some("json".to_string()));
+    }
+
+    #[test]
+    fn test_ordered_operations() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_operation(name = "unordered", action = "send", channel = "chat")] },
+            parse_quote! { #[asyncapi_operation(name = "late", action = "send", channel = "chat", order = 20)] },
+            parse_quote! { #[asyncapi_operation(name = "early", action = "receive", channel = "chat", order = 10)] },
+            parse_quote! { #[asyncapi_operation(name = "alsoLate", action = "receive", channel = "chat", order = 20)] },
+        ];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.operations[2].order, Some(10));
+        let names: Vec<&str> = ordered_operations(&meta.operations)
+            .iter()
+            .map(|operation| operation.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["early", "late", "alsoLate", "unordered"]);
+    }
+
+    #[test]
+    fn test_extract_operation_reply_group() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_operation(name = "ask", action = "send", channel = "requests", messages = [Question], reply(channel = "responses", messages = [Answer, ErrorReply]))]
+        }];
+
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let operation = &meta.operations[0];
+        assert_eq!(operation.reply_channel, Some("responses".to_string()));
+        let reply_messages: Vec<String> = operation
+            .reply_messages
+            .iter()
+            .map(|path| quote!(#path).to_string())
+            .collect();
+        assert_eq!(reply_messages, vec!["Answer", "ErrorReply"]);
+        assert_eq!(operation.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_reply_channels() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_channel(name = "requests", address = "/ws/requests")] },
+            parse_quote! { #[asyncapi_channel(name = "responses", address = "/ws/responses")] },
+            parse_quote! { #[asyncapi_operation(name = "ask", action = "send", channel = "requests", reply_channel = "responses")] },
+        ];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(
+            meta.operations[0].reply_channel,
+            Some("responses".to_string())
+        );
+        assert!(validate_reply_channels(&meta).is_ok());
+
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_channel(name = "requests", address = "/ws/requests")] },
+            parse_quote! { #[asyncapi_operation(name = "ask", action = "send", channel = "requests", reply_channel = "answers")] },
+        ];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let err = validate_reply_channels(&meta).unwrap_err();
+        assert!(err.contains("Operation 'ask' has reply_channel 'answers'"));
+    }
+
+    #[test]
+    fn test_validate_reply_refs() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_channel(name = "answers", address = "/ws/answers")] },
+            parse_quote! { #[asyncapi_reply(name = "answer", channel = "answers", messages = [Answer])] },
+            parse_quote! { #[asyncapi_operation(name = "ask", action = "send", channel = "answers", reply(ref = "answer"))] },
+        ];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        assert_eq!(meta.replies[0].name, "answer");
+        assert_eq!(meta.replies[0].messages.len(), 1);
+        assert_eq!(meta.operations[0].reply_ref, Some("answer".to_string()));
+        assert!(validate_reply_refs(&meta).is_ok());
+        assert!(validate_reply_channels(&meta).is_ok());
+
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_reply(name = "answer", channel = "responses")] },
+            parse_quote! { #[asyncapi_operation(name = "ask", action = "send", channel = "answers", reply(ref = "anwser"))] },
+            parse_quote! { #[asyncapi_operation(name = "mixed", action = "send", channel = "answers", reply(ref = "answer", messages = [Answer]))] },
+        ];
+        let meta = extract_asyncapi_spec_meta(&attrs);
+        let err = validate_reply_refs(&meta).unwrap_err();
+        assert!(err.contains("Operation 'ask' references reply 'anwser'"));
+        let err = validate_reply_channels(&meta).unwrap_err();
+        assert!(err.contains("Reply 'answer' has channel 'responses'"));
+
+        let meta = AsyncApiSpecMeta {
+            operations: meta.operations[1..].to_vec(),
+            ..meta
+        };
+        let err = validate_reply_refs(&meta).unwrap_err();
+        assert!(err.contains("Operation 'mixed' combines reply(ref = \"answer\")"));
+    }
 }
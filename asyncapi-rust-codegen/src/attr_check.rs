@@ -0,0 +1,433 @@
+//! Compile errors for misused attribute keys
+//!
+//! The extractors skip anything they can't parse, so a key written in the wrong
+//! shape (e.g. `#[asyncapi(summary)]` instead of `summary = "..."`) used to be
+//! silently ignored. The tables below describe the shape of every key, and
+//! [`check_attrs`] reports misuse before extraction runs. Unknown keys are left
+//! alone.
+
+use quote::ToTokens;
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Token};
+
+/// Shape of an attribute key
+pub enum KeyKind {
+    /// `key = <value>`, with an example value for the error message
+    Value(&'static str),
+    /// Bare `key`
+    Flag,
+    /// Bare `key` or `key = <value>`, with an example value for the error message
+    FlagOrValue(&'static str),
+    /// `key = ["...", ...]`, an array of string literals
+    Strings,
+    /// `key(...)` with its own nested keys
+    List(&'static [(&'static str, KeyKind)]),
+    /// `key = <value>` or `key(...)`, with an example value for the error message
+    ValueOrList(&'static str, &'static [(&'static str, KeyKind)]),
+}
+
+use KeyKind::{Flag, FlagOrValue, List, Strings, Value, ValueOrList};
+
+const STR: &str = "\"...\"";
+
+/// `external_docs = "url"` or `external_docs(url = "...", description = "...")`
+const EXTERNAL_DOCS: KeyKind = ValueOrList(
+    "\"https://...\"",
+    &[("url", Value(STR)), ("description", Value(STR))],
+);
+
+/// Keys of `#[asyncapi(...)]` on `ToAsyncApiMessage` types and variants
+pub const MESSAGE_KEYS: &[(&str, KeyKind)] = &[
+    ("name", Value(STR)),
+    ("summary", Value(STR)),
+    ("description", Value(STR)),
+    ("title", Value(STR)),
+    ("content_type", Value(STR)),
+    ("binary_payload", Value(STR)),
+    ("schema", Value(STR)),
+    ("schema_file", Value(STR)),
+    ("schema_format", Value(STR)),
+    ("headers", Value("HeaderType")),
+    ("correlation_id", Value(STR)),
+    ("nullable", Value(STR)),
+    ("max_schema_depth", Value("64")),
+    ("instantiate", Value("\"Type<Concrete>\"")),
+    ("triggers_binary", Flag),
+    ("no_payload", Flag),
+    ("humanize_titles", Flag),
+    ("deprecated", FlagOrValue(STR)),
+    ("direction", Value(STR)),
+    ("tags", Strings),
+    ("external_docs", EXTERNAL_DOCS),
+];
+
+/// Keys of `#[asyncapi(...)]` on `AsyncApi` types
+pub const SPEC_KEYS: &[(&str, KeyKind)] = &[
+    ("title", Value(STR)),
+    ("version", Value(STR)),
+    ("description", Value(STR)),
+    ("description_file", Value(STR)),
+    ("summary", Value(STR)),
+    ("strict", Flag),
+    ("ref_style", Value(STR)),
+    ("ref_base", Value(STR)),
+    ("omit_channel_message_names", Flag),
+    ("default_content_type", Value(STR)),
+    ("content_type_defaults", Value("path::to::fn")),
+    (
+        "contact",
+        List(&[
+            ("name", Value(STR)),
+            ("url", Value(STR)),
+            ("email", Value(STR)),
+        ]),
+    ),
+    (
+        "license",
+        List(&[("name", Value(STR)), ("url", Value(STR))]),
+    ),
+    (
+        "tag",
+        List(&[
+            ("name", Value(STR)),
+            ("description", Value(STR)),
+            ("external_docs", EXTERNAL_DOCS),
+        ]),
+    ),
+];
+
+/// Keys of `#[asyncapi_server(...)]`
+pub const SERVER_KEYS: &[(&str, KeyKind)] = &[
+    ("name", Value(STR)),
+    ("host", Value(STR)),
+    ("protocol", Value(STR)),
+    ("pathname", Value(STR)),
+    ("description", Value(STR)),
+    (
+        "variable",
+        List(&[
+            ("name", Value(STR)),
+            ("description", Value(STR)),
+            ("default", Value(STR)),
+            ("enum_values", Strings),
+            ("examples", Strings),
+        ]),
+    ),
+];
+
+/// Keys of `#[asyncapi_channel(...)]`
+pub const CHANNEL_KEYS: &[(&str, KeyKind)] = &[
+    ("name", Value(STR)),
+    ("address", Value(STR)),
+    ("title", Value(STR)),
+    ("description", Value(STR)),
+    ("bindings_ref", Value(STR)),
+    ("deprecated", FlagOrValue("true")),
+    ("default_content_type", Value(STR)),
+    ("external_docs", EXTERNAL_DOCS),
+    (
+        "parameter",
+        List(&[
+            ("name", Value(STR)),
+            ("description", Value(STR)),
+            ("schema_type", Value(STR)),
+            ("format", Value(STR)),
+            ("examples", Value("[...]")),
+        ]),
+    ),
+];
+
+/// Keys of `#[asyncapi_operation(...)]`
+pub const OPERATION_KEYS: &[(&str, KeyKind)] = &[
+    ("name", Value(STR)),
+    ("action", Value(STR)),
+    ("channel", Value(STR)),
+    ("title", Value(STR)),
+    ("summary", Value(STR)),
+    ("description", Value(STR)),
+    ("tags", Strings),
+    ("security", Strings),
+    ("deprecated", FlagOrValue(STR)),
+    ("messages", Value("[Type, ...]")),
+    ("messages_from", Value("Type")),
+    ("order", Value("10")),
+    ("reply_channel", Value(STR)),
+    (
+        "reply",
+        List(&[
+            ("ref", Value(STR)),
+            ("channel", Value(STR)),
+            ("messages", Value("[Type, ...]")),
+        ]),
+    ),
+    ("ws", List(&[("subprotocol", Value(STR))])),
+    ("external_docs", EXTERNAL_DOCS),
+];
+
+/// Keys of `#[asyncapi_reply(...)]`
+pub const REPLY_KEYS: &[(&str, KeyKind)] = &[
+    ("name", Value(STR)),
+    ("channel", Value(STR)),
+    ("messages", Value("[Type, ...]")),
+];
+
+/// Keys of `#[asyncapi_operation_fn(...)]`
+#[cfg(feature = "operation-fn")]
+pub const OPERATION_FN_KEYS: &[(&str, KeyKind)] = &[
+    ("api", Value("ApiType")),
+    ("name", Value(STR)),
+    ("action", Value(STR)),
+    ("channel", Value(STR)),
+    ("summary", Value(STR)),
+    ("description", Value(STR)),
+    ("messages", Value("[Type, ...]")),
+    ("order", Value("10")),
+];
+
+/// Check every `#[<name>(...)]` attribute in `attrs` against `keys`
+///
+/// All misused keys are reported together as one combined error.
+pub fn check_attrs(attrs: &[Attribute], name: &str, keys: &[(&str, KeyKind)]) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(name)) {
+        let _ = attr.parse_nested_meta(|nested| check_key(&nested, keys, &mut errors));
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+fn check_key(
+    nested: &ParseNestedMeta,
+    keys: &[(&str, KeyKind)],
+    errors: &mut Option<syn::Error>,
+) -> syn::Result<()> {
+    let has_value = nested.input.peek(Token![=]);
+    let has_list = nested.input.peek(syn::token::Paren);
+    let key = keys
+        .iter()
+        .find(|(key, _)| nested.path.is_ident(key))
+        .map(|(key, kind)| (*key, kind));
+
+    let message = match key {
+        Some((key, Value(example))) if !has_value => {
+            Some(format!("expected `{} = {}`", key, example))
+        }
+        Some((key, Flag)) if has_value || has_list => Some(format!(
+            "`{}` is a flag and takes no value; write `{}` on its own",
+            key, key
+        )),
+        Some((key, FlagOrValue(example))) if has_list => {
+            Some(format!("expected `{}` or `{} = {}`", key, key, example))
+        }
+        Some((key, Strings)) if !has_value => Some(format!("expected `{} = [\"...\", ...]`", key)),
+        Some((key, Strings)) => {
+            check_strings(key, &nested.value()?.parse()?, errors);
+            return Ok(());
+        }
+        Some((key, List(_))) if !has_list => Some(format!("expected `{}(...)`", key)),
+        Some((_, List(inner) | ValueOrList(_, inner))) if has_list => {
+            nested.parse_nested_meta(|inner_nested| check_key(&inner_nested, inner, errors))?;
+            None
+        }
+        Some((key, ValueOrList(example, _))) if !has_value => Some(format!(
+            "expected `{} = {}` or `{}(...)`",
+            key, example, key
+        )),
+        _ => None,
+    };
+    if let Some(message) = message {
+        push_error(errors, syn::Error::new_spanned(&nested.path, message));
+    }
+
+    // Skip whatever follows the key so later keys are still checked
+    if nested.input.peek(Token![=]) {
+        nested.value()?.parse::<syn::Expr>()?;
+    } else if nested.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in nested.input);
+        content.parse::<proc_macro2::TokenStream>()?;
+    }
+    Ok(())
+}
+
+/// Report every element of a `key = [...]` array that isn't a string literal
+fn check_strings(key: &str, value: &syn::Expr, errors: &mut Option<syn::Error>) {
+    let syn::Expr::Array(array) = value else {
+        let message = format!("expected `{} = [\"...\", ...]`", key);
+        push_error(errors, syn::Error::new_spanned(value, message));
+        return;
+    };
+    for element in &array.elems {
+        let literal = match element {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(_),
+                ..
+            }) => continue,
+            // Suggest the quoted form of numbers and booleans, e.g. `"8080"` for `8080`
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => Some(lit.to_token_stream()),
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) if matches!(**expr, syn::Expr::Lit(_)) => Some(element.to_token_stream()),
+            _ => None,
+        };
+        let message = match literal {
+            Some(literal) => format!(
+                "`{}` values must be string literals; write `\"{}\"`",
+                key,
+                literal.to_string().replace(' ', "")
+            ),
+            None => format!("`{}` values must be string literals", key),
+        };
+        push_error(errors, syn::Error::new_spanned(element, message));
+    }
+}
+
+fn push_error(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_value_key_without_value() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(summary, description = "Fine", title)]
+        }];
+
+        let err = check_attrs(&attrs, "asyncapi", MESSAGE_KEYS).unwrap_err();
+        let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "expected `summary = \"...\"`".to_string(),
+                "expected `title = \"...\"`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flag_key_with_value() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(triggers_binary = true)]
+        }];
+
+        let err = check_attrs(&attrs, "asyncapi", MESSAGE_KEYS).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`triggers_binary` is a flag and takes no value; write `triggers_binary` on its own"
+        );
+    }
+
+    #[test]
+    fn test_flag_or_value_key() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(deprecated, deprecated = "use v2")]
+        }];
+        assert!(check_attrs(&attrs, "asyncapi", MESSAGE_KEYS).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi(deprecated(reason = "use v2"))]
+        }];
+        let err = check_attrs(&attrs, "asyncapi", MESSAGE_KEYS).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected `deprecated` or `deprecated = \"...\"`"
+        );
+    }
+
+    #[test]
+    fn test_value_or_list_key() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(external_docs = "https://example.com", external_docs(url = "https://example.com"))]
+        }];
+        assert!(check_attrs(&attrs, "asyncapi_channel", CHANNEL_KEYS).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(external_docs, external_docs(url))]
+        }];
+        let err = check_attrs(&attrs, "asyncapi_channel", CHANNEL_KEYS).unwrap_err();
+        let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "expected `external_docs = \"https://...\"` or `external_docs(...)`".to_string(),
+                "expected `url = \"...\"`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_list_keys() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi_operation(name = "send", ws(subprotocol), order)] },
+            parse_quote! { #[asyncapi_operation(name = "receive", ws)] },
+        ];
+
+        let err = check_attrs(&attrs, "asyncapi_operation", OPERATION_KEYS).unwrap_err();
+        let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "expected `subprotocol = \"...\"`".to_string(),
+                "expected `order = 10`".to_string(),
+                "expected `ws(...)`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_and_unknown_keys_pass() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[asyncapi(title = "API", version = "1.0.0", strict, tag(name = "chat"))] },
+            parse_quote! { #[asyncapi(custom = "ignored", other(nested))] },
+        ];
+        assert!(check_attrs(&attrs, "asyncapi", SPEC_KEYS).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_channel(name = "rooms", address = "/rooms/{id}", parameter(name = "id", examples = [1, 2]))]
+        }];
+        assert!(check_attrs(&attrs, "asyncapi_channel", CHANNEL_KEYS).is_ok());
+    }
+
+    #[test]
+    fn test_string_array_keys() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_server(
+                name = "prod",
+                variable(name = "port", enum_values = ["8080"], examples = ["8080", 8443, -1, true, PORT])
+            )]
+        }];
+
+        let err = check_attrs(&attrs, "asyncapi_server", SERVER_KEYS).unwrap_err();
+        let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "`examples` values must be string literals; write `\"8443\"`".to_string(),
+                "`examples` values must be string literals; write `\"-1\"`".to_string(),
+                "`examples` values must be string literals; write `\"true\"`".to_string(),
+                "`examples` values must be string literals".to_string(),
+            ]
+        );
+
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[asyncapi_operation(tags = "admin", security)]
+        }];
+        let err = check_attrs(&attrs, "asyncapi_operation", OPERATION_KEYS).unwrap_err();
+        let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "expected `tags = [\"...\", ...]`".to_string(),
+                "expected `security = [\"...\", ...]`".to_string(),
+            ]
+        );
+    }
+}
@@ -93,32 +93,119 @@
 //!
 //! ## Supported Attributes
 //!
+//! Keys are checked at compile time: a value key written as a bare flag (`#[asyncapi(summary)]`)
+//! or a flag given a value (`#[asyncapi(strict = true)]`) is a compile error naming the
+//! expected form. Unrecognized keys are ignored.
+//!
 //! ### `#[asyncapi(...)]` on message types
 //!
 //! Helper attributes for documenting messages (used with `ToAsyncApiMessage`):
 //!
+//! - `name = "..."` - Message name and component key, overriding the serde name (the wire
+//!   discriminator is unchanged)
 //! - `summary = "..."` - Short summary of the message
-//! - `description = "..."` - Detailed description
+//! - `description = "..."` - Detailed description (defaults to the type's or variant's rustdoc,
+//!   with line breaks kept so Markdown renders as written)
 //! - `title = "..."` - Human-readable title (defaults to message name)
+//! - `humanize_titles` - Container-level flag defaulting titles to the humanized Rust identifier
+//!   instead, e.g. `User Join` for a `UserJoin` variant. Messages renamed with `#[serde(rename)]`
+//!   or `name = "..."` keep their name as the title
 //! - `content_type = "..."` - Content type (defaults to "application/json")
 //! - `triggers_binary` - Flag for binary messages (sets content_type to "application/octet-stream")
+//! - `no_payload` - Flag for metadata-only messages: the payload schema is left out (and not
+//!   generated), and the message is marked `x-no-payload: true` so
+//!   `AsyncApiSpec::validate_message_payloads()` doesn't report it. On an enum it applies to
+//!   every variant
+//! - `deprecated` or `deprecated = "..."` - Mark the message deprecated, optionally with migration
+//!   guidance. AsyncAPI has no `deprecated` field for messages, so this emits `x-deprecated: true`
+//!   and `x-deprecated-reason` extensions, plus `deprecated: true` on the payload schema. On an
+//!   enum it applies to every variant
+//! - `direction = "send"|"receive"` - Operation action the message belongs to, for operations
+//!   using `messages_from`; messages without one are used in both directions
+//! - `tags = ["...", ...]` - Message tags; on an enum, a variant's own tags replace the
+//!   container's. In `#[derive(AsyncApi)]` specs, a tag declared with `tag(...)` lends its
+//!   description and `externalDocs` to the copy in `components.messages`
+//! - `external_docs(url = "...", description = "...")` - The message's `externalDocs`; `url` is
+//!   required, and `external_docs = "..."` is short for a URL alone. On an enum, the container's
+//!   applies to variants without their own
+//! - `headers = Type` - Type whose JSON schema documents the message headers
+//! - `correlation_id = "..."` - Correlation ID location (e.g. `"$message.header#/requestId"`);
+//!   header locations are checked against `headers`, and `AsyncApiSpec::validate()` reports a
+//!   missing header as `ValidationIssue::MissingCorrelationHeader`
+//! - `nullable = "type_array"|"any_of"` - Container-level form for `Option<T>` fields in payload
+//!   schemas: `"type": ["T", "null"]` (default) or `"anyOf": [T, {"type": "null"}]`
+//! - `max_schema_depth = N` - Container-level limit on payload schema nesting (defaults to
+//!   `Schema::DEFAULT_MAX_DEPTH`); deeper schemas panic with a clear error instead of overflowing
+//! - `binary_payload = "binary"|"schema"|"none"` - Payload for binary messages:
+//!   `Schema::binary()`, i.e. `{ "type": "string", "format": "binary" }` (default), the schemars
+//!   schema, or omitted
+//! - `schema = "..."` / `schema_file = "..."` - Payload schema document used instead of schemars,
+//!   inline or read from a path relative to `CARGO_MANIFEST_DIR`. It's emitted as a Multi Format
+//!   Schema Object (`{ "schemaFormat": ..., "schema": ... }`), with the `schemaFormat` derived from
+//!   the content type: `application/x-protobuf` gives Protocol Buffers and `application/avro`
+//!   gives Avro (see `asyncapi_rust::schema_format_for`). JSON documents such as Avro schemas are
+//!   embedded as JSON, anything else (e.g. a `.proto` file) as a string
+//! - `schema_format = "..."` - `schemaFormat` of the schema document, for content types without a
+//!   known format
+//! - `instantiate = "Type<Concrete>"` - Container-level concrete instantiation of a generic
+//!   struct or enum (required for generic types). The methods are generated for that
+//!   instantiation, e.g. `Event::<User>::asyncapi_messages()`, with the payload taken from
+//!   `schema_for!(Event<User>)`. List it as `#[asyncapi_messages(Event::<User>)]`
+//!
+//! On an enum, `content_type`, `triggers_binary` and `binary_payload` in the container-level
+//! `#[asyncapi(...)]` apply to every variant that doesn't set its own `content_type` or
+//! `triggers_binary` (or `binary_payload`). The same goes for `schema`, `schema_file` and
+//! `schema_format`.
 //!
 //! ### `#[asyncapi(...)]` on API specs
 //!
 //! Required attributes for complete specifications (used with `AsyncApi`):
 //!
-//! - `title = "..."` - API title (required)
-//! - `version = "..."` - API version (required)
-//! - `description = "..."` - API description (optional)
+//! - `title = "..."` - API title (required); any `&str` expression, e.g. a shared `const TITLE: &str`
+//! - `version = "..."` - API version (required); also accepts a `&str` expression
+//! - `description = "..."` - API description (optional); Markdown is passed through as-is
+//! - `description_file = "..."` - Markdown file embedded as the API description, with a path
+//!   relative to `CARGO_MANIFEST_DIR` like `schema_file` (optional; can't be combined with
+//!   `description`)
+//! - `summary = "..."` - One-line API summary, emitted as the `x-summary` Info extension since
+//!   AsyncAPI Info has no `summary` field (optional)
+//! - `strict` - Turn best-effort checks into compile errors (e.g. unknown server protocols,
+//!   server hosts with a URL scheme or path, channel parameters missing from the address)
+//! - `ref_style = "channel"|"components"` - Target of operation message refs (defaults to `"channel"`)
+//! - `ref_base = "..."` - File or URL prefixed to component message refs, for multi-file documents
+//! - `omit_channel_message_names` - Inline messages into channels without their `name`, which
+//!   the channel's `messages` key already gives; `components.messages` keep the name and
+//!   operation refs are unchanged (optional; has no effect on refs under `ref_base`)
+//! - `default_content_type = "..."` - Document-level `defaultContentType` (optional); messages
+//!   that don't declare a content type are documented with it instead of `application/json`
+//! - `content_type_defaults = path::to::fn` - Function returning an
+//!   `asyncapi_rust::ContentTypeDefaults`; without `default_content_type`, the spec's
+//!   `defaultContentType` is picked from its server protocols (optional)
+//! - `tag(name = "...", description = "...", external_docs = "https://...")` - API tag in
+//!   `info.tags`, repeatable (optional); `external_docs` takes the same forms as on messages
+//! - `contact(name = "...", url = "...", email = "...")` - API contact, all keys optional (optional)
+//! - `license(name = "...", url = "...")` - API license; `name` is required (optional)
+//!
+//! Keys may be split across several `#[asyncapi(...)]` attributes on the same type, e.g. one for
+//! title and version and another for the descriptive fields; a key given twice keeps the last value.
+//!
+//! A `Tags: name1, name2` line in the type's doc comment also adds tags. Tags are deduplicated
+//! by name in declaration order, and an explicit `tag(...)` overrides a doc-comment tag.
 //!
 //! ### `#[asyncapi_server(...)]`
 //!
 //! Define server connection information:
 //!
 //! - `name = "..."` - Server identifier (required)
-//! - `host = "..."` - Server host/URL (required)
-//! - `protocol = "..."` - Protocol (e.g., "wss", "ws", "grpc") (required)
+//! - `host = "..."` - Server host as a bare authority, e.g. `api.example.com:443` (required);
+//!   `AsyncApiSpec::validate()` warns about hosts with a scheme or path
+//! - `protocol = "..."` - Protocol (e.g., "wss", "ws", "kafka") (required)
+//! - `pathname = "..."` - Path relative to the host, may contain `{variable}` placeholders (optional)
 //! - `description = "..."` - Server description (optional)
+//! - `variable(name = "...", description = "...", default = "...", enum_values = [...], examples = [...])` -
+//!   Server variable (optional, repeatable). Every `{placeholder}` in `pathname` must have one;
+//!   a missing definition is a compile error. AsyncAPI server variable values are strings, so
+//!   `enum_values` and `examples` take string literals (`examples = ["8443"]`, not `[8443]`).
 //!
 //! ### `#[asyncapi_channel(...)]`
 //!
@@ -126,6 +213,27 @@
 //!
 //! - `name = "..."` - Channel identifier (required)
 //! - `address = "..."` - Channel path/address (optional)
+//! - `title = "..."` - Human-readable channel title (optional)
+//! - `parameter(name = "...", description = "...", schema_type = "...", format = "...", examples = [...])` -
+//!   Address parameter (optional, repeatable). `examples` keep their JSON type, so `examples = [42]`
+//!   produces numbers in the parameter schema. The AsyncAPI 3.0 Parameter Object has no `schema`,
+//!   so `validate_schema()` reports parameters declared with a type, format or examples.
+//! - `bindings_ref = "..."` - Name of reusable bindings, emitted as a
+//!   `#/components/channelBindings/{name}` reference (optional). The bindings themselves are
+//!   added to the spec with `AsyncApiSpec::with_channel_bindings`; `validate()` reports
+//!   references to undeclared bindings.
+//! - `deprecated` or `deprecated = true` - Mark a channel scheduled for removal, emitted as the
+//!   `x-deprecated` extension since AsyncAPI channels have no `deprecated` field (optional)
+//! - `default_content_type = "..."` - Content type of messages on this channel that don't set
+//!   `content_type` or `triggers_binary`, emitted as the `x-default-content-type` extension
+//!   (optional). Such messages otherwise take the document's `defaultContentType`, and only
+//!   fall back to `application/json` without either
+//! - `external_docs(url = "...", description = "...")` or `external_docs = "..."` - The channel's
+//!   `externalDocs` (optional; `url` is required)
+//!
+//! Placeholders in the address (e.g. `{userId}` in `/ws/{userId}`) that have no `parameter(...)`
+//! entry are added as string parameters. A declared parameter missing from the address is reported
+//! by `AsyncApiSpec::validate()`, or is a compile error under `strict`.
 //!
 //! ### `#[asyncapi_operation(...)]`
 //!
@@ -134,12 +242,77 @@
 //! - `name = "..."` - Operation identifier (required)
 //! - `action = "send"|"receive"` - Operation type (required)
 //! - `channel = "..."` - Channel reference (required)
+//! - `title = "..."` - Human-readable operation title (optional)
+//! - `summary = "..."` / `description = "..."` - Operation summary and description (optional)
+//! - `tags = ["...", ...]` - Operation tags (optional); a tag declared with `tag(...)` on the
+//!   API lends its description
+//! - `security = ["...", ...]` - Security schemes required by the operation, emitted as
+//!   `#/components/securitySchemes/{name}` references (optional)
+//! - `deprecated` or `deprecated = "..."` - Mark the operation deprecated, emitted as
+//!   `x-deprecated: true` and `x-deprecated-reason` extensions like on messages (optional)
 //! - `messages = [Type1, Type2, ...]` - Message types available for this operation (optional)
+//! - `messages_from = Type` - Message type whose messages with a matching `direction` (or none)
+//!   are available for this operation, e.g. the send half of a bidirectional protocol enum; the
+//!   channel only gets those messages too (optional)
+//! - `ws(subprotocol = "...")` - WebSocket subprotocol, emitted as an operation binding (optional)
+//! - `order = N` - Sort hint for the generated operations map (optional)
+//! - `external_docs(url = "...", description = "...")` or `external_docs = "..."` - The
+//!   operation's `externalDocs` (optional; `url` is required)
+//! - `reply_channel = "..."` - Channel replies arrive on, emitted as an operation `reply`
+//!   (optional; must name a declared channel)
+//! - `reply(channel = "...", messages = [Type1, ...])` - Full reply definition (optional); reply
+//!   message types are registered in `components.messages` and added to the reply channel
+//! - `reply(ref = "...")` - Reference to a reusable `#[asyncapi_reply(...)]`, emitted as
+//!   `#/components/replies/{name}` (optional; can't be combined with an inline reply)
+//!
+//! ### `#[asyncapi_reply(...)]`
+//!
+//! Define a reusable reply, emitted under `components.replies` for operations to share:
+//!
+//! - `name = "..."` - Reply identifier (required)
+//! - `channel = "..."` - Channel the reply is sent on (optional; must name a declared channel)
+//! - `messages = [Type1, ...]` - Reply message types (optional); registered in
+//!   `components.messages` and added to the reply channel like inline reply messages
+//!
+//! ### `#[asyncapi_operation_fn(...)]` on functions
+//!
+//! Requires the `operation-fn` feature. Declare an operation on a free function instead of the
+//! `AsyncApi` type. The function is unchanged; the operation is registered with `inventory` and
+//! added to the spec of `api` when `asyncapi_spec()` runs, following the API's `order`,
+//! `ref_style`, `ref_base` and content-type defaults:
+//!
+//! - `api = Type` - `AsyncApi` type the operation belongs to (required)
+//! - `action = "send"|"receive"` - Operation type (required)
+//! - `channel = "..."` - Channel name (required)
+//! - `name = "..."` - Operation identifier (defaults to the function name)
+//! - `summary = "..."` - Operation summary (optional)
+//! - `description = "..."` - Operation description (defaults to the function's rustdoc)
+//! - `messages = [Type1, ...]` - Message types, added to the channel and `components.messages`
+//! - `order = 10` - Sort hint, like `#[asyncapi_operation(order = ...)]`
+//!
+//! An undeclared channel or an operation name that is already taken is reported by
+//! `AsyncApiSpec::validate()`. Under the API's `strict`, an undeclared channel or a name taken
+//! by an `#[asyncapi_operation]` is a compile error.
+//!
+//! Operations are emitted in ascending `order`, with ties and operations without a hint
+//! keeping their declaration order (unordered operations come after ordered ones).
 //!
 //! When the `messages` parameter is specified on operations, those messages are automatically
 //! added to the channel that the operation references. Operation messages reference the channel's
 //! messages (e.g., `#/channels/{channel}/messages/{message}`), while channel messages reference
 //! the components section (e.g., `#/components/messages/{message}`), following AsyncAPI 3.0 spec.
+//! Tooling that expects operations to reference components directly can opt into
+//! `#[asyncapi(ref_style = "components")]`.
+//! For multi-file documents, `#[asyncapi(ref_base = "./messages.json")]` makes every component
+//! message ref external (`./messages.json#/components/messages/{message}`). The generated spec
+//! still contains `components.messages`, so it can be used to write that file, and
+//! `AsyncApiSpec::to_bundle()` points the refs back at it for a single-file document.
+//! Operation and reply message types are registered in `components.messages` alongside the types
+//! listed in `#[asyncapi_messages(...)]`; each type's schemas are generated once even if listed
+//! repeatedly.
+//!
+//! Besides `asyncapi_spec()`, the derive generates `asyncapi_all_message_names()`, which
+//! concatenates the `asyncapi_message_names()` of every type in `#[asyncapi_messages(...)]`.
 //!
 //! ## Integration with serde
 //!
@@ -147,15 +320,24 @@
 //!
 //! - `#[serde(rename = "...")]` - Use custom name in AsyncAPI spec
 //! - `#[serde(tag = "...")]` - Tagged enum with discriminator field
+//! - `#[serde(tag = "...", content = "...")]` - Adjacently tagged enum; each payload carries
+//!   the `$defs` its content refers to
+//! - `#[serde(transparent)]` - Newtype structs documented as their inner type
 //! - `#[serde(skip)]` - Exclude fields from schema
 //! - `#[serde(skip_serializing_if = "...")]` - Optional fields
 //!
+//! Payload schemas are whatever schemars emits for the Rust types, so fields using
+//! `#[serde(with = "...")]` must say how they look on the wire with `#[schemars(with = "...")]`
+//! (e.g. `#[schemars(with = "i64")]` next to `chrono::serde::ts_seconds`). schemars rejects
+//! `serde(with)` modules without it, and a mismatched type is documented as-is.
+//!
 //! ## Integration with schemars
 //!
 //! JSON schemas are generated automatically using schemars:
 //!
 //! - Requires `JsonSchema` derive on message types
 //! - Generates complete JSON Schema from Rust type definitions
+//! - Field doc comments become property `description`s in the payload schema
 //! - Supports nested types, generics, and references
 //! - Schemas include validation rules from type constraints
 //!
@@ -165,10 +347,20 @@
 //!
 //! **From `ToAsyncApiMessage`:**
 //! - `asyncapi_message_names() -> Vec<&'static str>` - Get all message names
+//! - `asyncapi_message_names_for(action) -> Vec<&'static str>` - Names of the messages whose
+//!   `direction` matches `"send"` or `"receive"`, plus those without one
 //! - `asyncapi_message_count() -> usize` - Number of messages
 //! - `asyncapi_tag_field() -> Option<&'static str>` - Serde tag field if present
+//! - `asyncapi_content_type_for(name) -> Option<&'static str>` - Content type of a message by
+//!   name, e.g. to pick a text or binary WebSocket frame at runtime. Channel and document
+//!   defaults aren't known to the type; `AsyncApiSpec::message_content_type(name)` applies them
+//! - `asyncapi_declared_content_type_for(name) -> Option<&'static str>` - Like
+//!   `asyncapi_content_type_for`, but `None` for messages that are JSON only by default
 //! - `asyncapi_messages() -> Vec<Message>` - Generate messages with schemas
 //!
+//! `ToAsyncApiMessage` also implements the `asyncapi_rust::ToAsyncApiMessage` trait so
+//! message types can be used generically (e.g. with `AsyncApiSpec::builder()`).
+//!
 //! **From `AsyncApi`:**
 //! - `asyncapi_spec() -> AsyncApiSpec` - Generate complete specification
 //!
@@ -187,11 +379,26 @@ use syn::{Data, DeriveInput, parse_macro_input};
 
 mod asyncapi_attrs;
 mod asyncapi_spec_attrs;
+mod attr_check;
 mod serde_attrs;
 
-use asyncapi_attrs::extract_asyncapi_meta;
-use asyncapi_spec_attrs::extract_asyncapi_spec_meta;
-use serde_attrs::{extract_serde_rename, extract_serde_tag};
+use asyncapi_attrs::{
+    ExternalDocsMeta, external_docs_tokens, extract_asyncapi_meta, extract_doc_description,
+    humanize_ident, instantiates,
+};
+#[cfg(feature = "operation-fn")]
+use asyncapi_spec_attrs::extract_operation_fn;
+use asyncapi_spec_attrs::{
+    extract_asyncapi_spec_meta, ordered_operations, unused_channel_parameters,
+    validate_reply_channels, validate_reply_refs, validate_server_hosts, validate_server_protocols,
+    validate_server_variables,
+};
+#[cfg(feature = "operation-fn")]
+use attr_check::OPERATION_FN_KEYS;
+use attr_check::{
+    CHANNEL_KEYS, MESSAGE_KEYS, OPERATION_KEYS, REPLY_KEYS, SERVER_KEYS, SPEC_KEYS, check_attrs,
+};
+use serde_attrs::{extract_serde_rename, extract_serde_tag, has_serde_transparent};
 
 /// Derive macro for generating AsyncAPI message metadata
 ///
@@ -214,58 +421,206 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
+    // Reject keys written in the wrong shape, e.g. `summary` without a value
+    let mut attr_sets = vec![&input.attrs];
+    if let Data::Enum(data_enum) = &input.data {
+        attr_sets.extend(data_enum.variants.iter().map(|variant| &variant.attrs));
+    }
+    for attrs in attr_sets {
+        if let Err(err) = check_attrs(attrs, "asyncapi", MESSAGE_KEYS) {
+            return err.to_compile_error().into();
+        }
+    }
+
     // Extract serde tag attribute from enum
     let tag_field = extract_serde_tag(&input.attrs);
 
+    // Generic types are documented through one concrete instantiation, which the
+    // generated impls are written for
+    let instantiate = extract_asyncapi_meta(&input.attrs).instantiate;
+    let target: syn::Type = match &instantiate {
+        Some(lit) => match lit.parse::<syn::Type>() {
+            Ok(ty) if instantiates(&ty, name) => ty,
+            _ => {
+                return syn::Error::new_spanned(
+                    lit,
+                    format!(
+                        "Invalid instantiate '{}', must name `{}` with concrete type arguments, e.g. \"{}<MyType>\"",
+                        lit.value(),
+                        name,
+                        name
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        None if !input.generics.params.is_empty() => {
+            return syn::Error::new_spanned(
+                &input.generics,
+                format!(
+                    "ToAsyncApiMessage on a generic type needs a concrete instantiation, e.g. #[asyncapi(instantiate = \"{}<MyType>\")]",
+                    name
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+        None => syn::parse_quote! { #name },
+    };
+
     // Struct to hold message metadata
     struct MessageMeta {
         name: String,
+        // serde's name for the variant, used to find its schema
+        wire_name: String,
         summary: Option<String>,
         description: Option<String>,
         title: Option<String>,
         content_type: Option<String>,
         triggers_binary: bool,
+        binary_payload: Option<syn::LitStr>,
+        schema: Option<syn::LitStr>,
+        schema_file: Option<syn::LitStr>,
+        schema_format: Option<String>,
+        no_payload: bool,
+        deprecated: bool,
+        deprecated_reason: Option<String>,
+        // `"send"`/`"receive"` for one-way messages, checked below
+        direction: Option<syn::LitStr>,
+        headers: Option<syn::Path>,
+        correlation_id: Option<String>,
+        tags: Vec<String>,
+        external_docs: Option<ExternalDocsMeta>,
     }
 
+    // Type whose JSON schema becomes the payload. `#[serde(transparent)]` newtypes
+    // serialize as their single field, so document the inner type directly.
+    let mut payload_type = match &instantiate {
+        Some(_) => quote! { #target },
+        None => quote! { Self },
+    };
+
     // Parse enum variants or struct
     let (messages, _is_enum) = match &input.data {
         Data::Enum(data_enum) => {
+            // Container-level content type settings are the default for every variant
+            let container_meta = extract_asyncapi_meta(&input.attrs);
             let mut message_metas = Vec::new();
 
             for variant in &data_enum.variants {
                 let variant_name = &variant.ident;
 
                 // Check for serde(rename) attribute on variant
-                let message_name = extract_serde_rename(&variant.attrs)
-                    .unwrap_or_else(|| variant_name.to_string());
+                let serde_rename = extract_serde_rename(&variant.attrs);
+                let renamed = serde_rename.is_some();
+                let message_name = serde_rename.unwrap_or_else(|| variant_name.to_string());
 
                 // Extract asyncapi metadata
                 let asyncapi_meta = extract_asyncapi_meta(&variant.attrs);
 
+                // Renamed variants keep their name as the default title
+                let title = asyncapi_meta.title.or_else(|| {
+                    (container_meta.humanize_titles && !renamed && asyncapi_meta.name.is_none())
+                        .then(|| humanize_ident(&variant_name.to_string()))
+                });
+
+                // A variant's own content_type or triggers_binary overrides the container's
+                let (content_type, triggers_binary) =
+                    if asyncapi_meta.content_type.is_some() || asyncapi_meta.triggers_binary {
+                        (asyncapi_meta.content_type, asyncapi_meta.triggers_binary)
+                    } else {
+                        (
+                            container_meta.content_type.clone(),
+                            container_meta.triggers_binary,
+                        )
+                    };
+
                 message_metas.push(MessageMeta {
-                    name: message_name,
+                    name: asyncapi_meta.name.unwrap_or_else(|| message_name.clone()),
+                    wire_name: message_name,
                     summary: asyncapi_meta.summary,
-                    description: asyncapi_meta.description,
-                    title: asyncapi_meta.title,
-                    content_type: asyncapi_meta.content_type,
-                    triggers_binary: asyncapi_meta.triggers_binary,
+                    description: asyncapi_meta
+                        .description
+                        .or_else(|| extract_doc_description(&variant.attrs)),
+                    title,
+                    content_type,
+                    triggers_binary,
+                    binary_payload: asyncapi_meta
+                        .binary_payload
+                        .or_else(|| container_meta.binary_payload.clone()),
+                    schema: asyncapi_meta
+                        .schema
+                        .or_else(|| container_meta.schema.clone()),
+                    schema_file: asyncapi_meta
+                        .schema_file
+                        .or_else(|| container_meta.schema_file.clone()),
+                    schema_format: asyncapi_meta
+                        .schema_format
+                        .or_else(|| container_meta.schema_format.clone()),
+                    no_payload: asyncapi_meta.no_payload || container_meta.no_payload,
+                    deprecated: asyncapi_meta.deprecated || container_meta.deprecated,
+                    deprecated_reason: asyncapi_meta
+                        .deprecated_reason
+                        .or_else(|| container_meta.deprecated_reason.clone()),
+                    direction: asyncapi_meta
+                        .direction
+                        .or_else(|| container_meta.direction.clone()),
+                    headers: asyncapi_meta.headers,
+                    correlation_id: asyncapi_meta.correlation_id,
+                    // A variant's own tags replace the container's
+                    tags: if asyncapi_meta.tags.is_empty() {
+                        container_meta.tags.clone()
+                    } else {
+                        asyncapi_meta.tags
+                    },
+                    external_docs: asyncapi_meta
+                        .external_docs
+                        .or_else(|| container_meta.external_docs.clone()),
                 });
             }
 
             (message_metas, true)
         }
-        Data::Struct(_) => {
+        Data::Struct(data_struct) => {
             // For structs, extract metadata from the struct itself
             let asyncapi_meta = extract_asyncapi_meta(&input.attrs);
 
+            if has_serde_transparent(&input.attrs) && data_struct.fields.len() == 1 {
+                if let Some(field) = data_struct.fields.iter().next() {
+                    let inner_type = &field.ty;
+                    payload_type = quote! { #inner_type };
+                }
+            }
+
+            let title = asyncapi_meta.title.or_else(|| {
+                (asyncapi_meta.humanize_titles && asyncapi_meta.name.is_none())
+                    .then(|| humanize_ident(&name.to_string()))
+            });
+
             (
                 vec![MessageMeta {
-                    name: name.to_string(),
+                    name: asyncapi_meta.name.unwrap_or_else(|| name.to_string()),
+                    wire_name: name.to_string(),
                     summary: asyncapi_meta.summary,
-                    description: asyncapi_meta.description,
-                    title: asyncapi_meta.title,
+                    description: asyncapi_meta
+                        .description
+                        .or_else(|| extract_doc_description(&input.attrs)),
+                    title,
                     content_type: asyncapi_meta.content_type,
                     triggers_binary: asyncapi_meta.triggers_binary,
+                    binary_payload: asyncapi_meta.binary_payload,
+                    schema: asyncapi_meta.schema,
+                    schema_file: asyncapi_meta.schema_file,
+                    schema_format: asyncapi_meta.schema_format,
+                    no_payload: asyncapi_meta.no_payload,
+                    deprecated: asyncapi_meta.deprecated,
+                    deprecated_reason: asyncapi_meta.deprecated_reason,
+                    direction: asyncapi_meta.direction,
+                    headers: asyncapi_meta.headers,
+                    correlation_id: asyncapi_meta.correlation_id,
+                    tags: asyncapi_meta.tags,
+                    external_docs: asyncapi_meta.external_docs,
                 }],
                 false,
             )
@@ -280,8 +635,28 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
     let message_count = messages.len();
     let message_literals = messages.iter().map(|m| m.name.as_str());
 
+    let mut message_directions = Vec::with_capacity(messages.len());
+    for m in &messages {
+        message_directions.push(match &m.direction {
+            None => quote! { None },
+            Some(lit) => match lit.value().as_str() {
+                direction @ ("send" | "receive") => quote! { Some(#direction) },
+                other => {
+                    return syn::Error::new_spanned(
+                        lit,
+                        format!("Invalid direction '{}', must be 'send' or 'receive'", other),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            },
+        });
+    }
+    let directed_message_names = messages.iter().map(|m| m.name.as_str());
+
     // Prepare metadata for message generation
     let message_names_for_gen = messages.iter().map(|m| m.name.as_str());
+    let message_wire_names = messages.iter().map(|m| m.wire_name.as_str());
     let message_titles = messages.iter().map(|m| {
         if let Some(ref title) = m.title {
             quote! { Some(#title.to_string()) }
@@ -304,15 +679,196 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
             quote! { None }
         }
     });
+    let effective_content_type = |m: &MessageMeta| match &m.content_type {
+        Some(ct) => ct.clone(),
+        None if m.triggers_binary => "application/octet-stream".to_string(),
+        None => "application/json".to_string(),
+    };
     let message_content_types = messages.iter().map(|m| {
-        if let Some(ref ct) = m.content_type {
-            quote! { Some(#ct.to_string()) }
-        } else if m.triggers_binary {
-            quote! { Some("application/octet-stream".to_string()) }
+        let ct = effective_content_type(m);
+        quote! { Some(#ct.to_string()) }
+    });
+    // One match arm per distinct name; the first message with a name wins
+    let mut content_type_arms = Vec::with_capacity(messages.len());
+    // Only messages with a `content_type` or `triggers_binary` declare their content type
+    let mut declared_content_type_arms = Vec::with_capacity(messages.len());
+    let mut seen_names = std::collections::HashSet::new();
+    for m in &messages {
+        if seen_names.insert(m.name.as_str()) {
+            let (msg_name, ct) = (&m.name, effective_content_type(m));
+            content_type_arms.push(quote! { #msg_name => Some(#ct), });
+            if m.content_type.is_some() || m.triggers_binary {
+                declared_content_type_arms.push(quote! { #msg_name => Some(#ct), });
+            }
+        }
+    }
+
+    let message_deprecations =
+        messages
+            .iter()
+            .map(|m| match (m.deprecated, &m.deprecated_reason) {
+                (false, _) => quote! { None },
+                (true, Some(reason)) => quote! { Some(Some(#reason)) },
+                (true, None) => quote! { Some(None) },
+            });
+    let message_headers = messages.iter().map(|m| {
+        if let Some(ref headers) = m.headers {
+            quote! { Some(asyncapi_rust::Schema::from_type::<#headers>()) }
+        } else {
+            quote! { None }
+        }
+    });
+    let message_correlation_ids = messages.iter().map(|m| {
+        if let Some(ref location) = m.correlation_id {
+            quote! {
+                Some(asyncapi_rust::CorrelationId {
+                    description: None,
+                    location: #location.to_string(),
+                })
+            }
         } else {
-            quote! { Some("application/json".to_string()) }
+            quote! { None }
+        }
+    });
+    let message_tags = messages.iter().map(|m| {
+        if m.tags.is_empty() {
+            quote! { None }
+        } else {
+            let tags = &m.tags;
+            quote! {
+                Some(vec![#(asyncapi_rust::Tag {
+                    name: #tags.to_string(),
+                    description: None,
+                    external_docs: None,
+                }),*])
+            }
         }
     });
+    let message_external_docs = messages.iter().map(|m| {
+        external_docs_tokens(m.external_docs.as_ref()).unwrap_or_else(|err| err.to_compile_error())
+    });
+
+    // Binary messages don't serialize through JSON, so the schemars schema (e.g. an
+    // integer array for `Vec<u8>`) is misleading. Pick the payload mode per message.
+    let mut message_payload_modes = Vec::with_capacity(messages.len());
+    // `(schemaFormat, document)` for messages whose schema is supplied as a document
+    let mut message_schema_documents = Vec::with_capacity(messages.len());
+    for m in &messages {
+        let document = match (&m.schema, &m.schema_file) {
+            (Some(_), Some(path)) => {
+                return syn::Error::new_spanned(
+                    path,
+                    "schema and schema_file can't be used together",
+                )
+                .to_compile_error()
+                .into();
+            }
+            (Some(schema), None) => Some((schema, quote! { #schema })),
+            (None, Some(path)) => {
+                if let Err(err) = check_manifest_file("schema_file", path) {
+                    return err.to_compile_error().into();
+                }
+                Some((
+                    path,
+                    quote! { include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path)) },
+                ))
+            }
+            (None, None) => None,
+        };
+        let document = match document {
+            Some(_) if m.no_payload => None,
+            Some((lit, document)) => {
+                let content_type = effective_content_type(m);
+                let schema_format = m
+                    .schema_format
+                    .as_deref()
+                    .or_else(|| asyncapi_rust_models::schema_format_for(&content_type));
+                let Some(schema_format) = schema_format else {
+                    return syn::Error::new_spanned(
+                        lit,
+                        format!(
+                            "No schemaFormat is known for content type '{}'; set schema_format = \"...\"",
+                            content_type
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                };
+                Some(quote! { (#schema_format, #document) })
+            }
+            None => None,
+        };
+        message_schema_documents.push(match &document {
+            Some(document) => quote! { Some(#document) },
+            None => quote! { None },
+        });
+
+        let is_binary =
+            m.triggers_binary || m.content_type.as_deref() == Some("application/octet-stream");
+        let mode = match &m.binary_payload {
+            _ if m.no_payload => "none".to_string(),
+            _ if document.is_some() => "document".to_string(),
+            Some(lit) => match lit.value().as_str() {
+                mode @ ("binary" | "schema" | "none") => mode.to_string(),
+                other => {
+                    return syn::Error::new_spanned(
+                        lit,
+                        format!(
+                            "Invalid binary_payload '{}', must be 'binary', 'schema', or 'none'",
+                            other
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            },
+            None if is_binary => "binary".to_string(),
+            None => "schema".to_string(),
+        };
+        message_payload_modes.push(mode);
+    }
+
+    // Metadata-only types never need their JSON schema generated
+    let schema_json = if message_payload_modes.iter().any(|mode| mode == "schema") {
+        quote! {
+            serde_json::to_value(schemars::schema_for!(#payload_type))
+                .expect("Failed to serialize schema")
+        }
+    } else {
+        quote! { serde_json::Value::Null }
+    };
+
+    // Schema conversion depth limit and nullable style, configurable on the container
+    let container_meta = extract_asyncapi_meta(&input.attrs);
+    let max_schema_depth = match container_meta.max_schema_depth {
+        Some(depth) => quote! { #depth },
+        None => quote! { asyncapi_rust::Schema::DEFAULT_MAX_DEPTH },
+    };
+    let nullable_style = match container_meta
+        .nullable
+        .as_ref()
+        .map(|lit| (lit, lit.value()))
+    {
+        None => quote! { asyncapi_rust::NullableStyle::TypeArray },
+        Some((_, style)) if style == "type_array" => {
+            quote! { asyncapi_rust::NullableStyle::TypeArray }
+        }
+        Some((_, style)) if style == "any_of" => quote! { asyncapi_rust::NullableStyle::AnyOf },
+        Some((lit, style)) => {
+            return syn::Error::new_spanned(
+                lit,
+                format!(
+                    "Invalid nullable '{}', must be 'type_array' or 'any_of'",
+                    style
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // Variants are matched by the `const` of their tag property
+    let variant_tag = tag_field.clone().unwrap_or_else(|| "type".to_string());
 
     let tag_info = if let Some(tag) = tag_field {
         quote! {
@@ -323,12 +879,26 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
     };
 
     let expanded = quote! {
-        impl #name {
+        impl #target {
             /// Get AsyncAPI message names for this type
             pub fn asyncapi_message_names() -> Vec<&'static str> {
                 vec![#(#message_literals),*]
             }
 
+            /// Get the names of messages used by operations with the given action
+            ///
+            /// `action` is `"send"` or `"receive"`. Messages without a
+            /// `direction` are used in both.
+            pub fn asyncapi_message_names_for(action: &str) -> Vec<&'static str> {
+                let directions: Vec<(&'static str, Option<&str>)> =
+                    vec![#((#directed_message_names, #message_directions)),*];
+                directions
+                    .into_iter()
+                    .filter(|(_, direction)| direction.is_none_or(|d| d == action))
+                    .map(|(name, _)| name)
+                    .collect()
+            }
+
             /// Get the number of messages in this type
             pub fn asyncapi_message_count() -> usize {
                 #message_count
@@ -339,6 +909,27 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
                 #tag_info
             }
 
+            /// Get the documented content type of the message with the given name
+            ///
+            /// Returns `None` for names that aren't messages of this type.
+            pub fn asyncapi_content_type_for(name: &str) -> Option<&'static str> {
+                match name {
+                    #(#content_type_arms)*
+                    _ => None,
+                }
+            }
+
+            /// Get the content type the message with the given name declares itself
+            ///
+            /// Returns `None` for messages that are `application/json` only by
+            /// default, and for names that aren't messages of this type.
+            pub fn asyncapi_declared_content_type_for(name: &str) -> Option<&'static str> {
+                match name {
+                    #(#declared_content_type_arms)*
+                    _ => None,
+                }
+            }
+
             /// Generate AsyncAPI Message objects with JSON schemas
             ///
             /// This method requires that the type implements `schemars::JsonSchema`.
@@ -346,13 +937,19 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
             where
                 Self: schemars::JsonSchema,
             {
-                use schemars::schema_for;
-
-                let schema = schema_for!(Self);
+                Self::asyncapi_messages_iter().collect()
+            }
 
+            /// Iterate over AsyncAPI Message objects without collecting them
+            ///
+            /// The JSON schema is generated once up front; each message is built as
+            /// the iterator advances.
+            pub fn asyncapi_messages_iter() -> impl Iterator<Item = asyncapi_rust::Message>
+            where
+                Self: schemars::JsonSchema,
+            {
                 // Convert schemars RootSchema to JSON
-                let schema_json = serde_json::to_value(&schema)
-                    .expect("Failed to serialize schema");
+                let schema_json = #schema_json;
 
                 // For enums, extract individual variant schemas from oneOf
                 let variant_schemas = if let Some(one_of_array) = schema_json.get("oneOf") {
@@ -361,20 +958,25 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
                         let mut variant_map = std::collections::HashMap::with_capacity(variants.len());
 
                         for variant in variants {
-                            // Extract the const value from the type field
+                            // Extract the const value from the tag field
                             if let Some(properties) = variant.get("properties") {
-                                if let Some(type_prop) = properties.get("type") {
+                                if let Some(type_prop) = properties.get(#variant_tag) {
                                     if let Some(const_val) = type_prop.get("const") {
                                         if let Some(variant_name) = const_val.as_str() {
-                                            // Convert this variant to a Schema
-                                            // Note: clone is necessary here because we need ownership
-                                            // of the JSON value to deserialize it
-                                            let variant_schema: asyncapi_rust::Schema =
-                                                serde_json::from_value(variant.clone())
-                                                    .unwrap_or_else(|e| panic!(
-                                                        "Failed to deserialize schema for variant '{}': {}",
-                                                        variant_name, e
-                                                    ));
+                                            // Convert this variant to a Schema, bringing along
+                                            // the root `$defs` it references (e.g. a shared
+                                            // struct in an adjacently tagged `content` field)
+                                            let mut variant = variant.clone();
+                                            asyncapi_rust::lift_defs(&mut variant, &schema_json);
+                                            let variant_schema =
+                                                asyncapi_rust::Schema::from_json_with_max_depth(
+                                                    variant,
+                                                    #max_schema_depth,
+                                                )
+                                                .unwrap_or_else(|e| panic!(
+                                                    "Failed to deserialize schema for variant '{}': {}",
+                                                    variant_name, e
+                                                ));
                                             variant_map.insert(variant_name.to_string(), variant_schema);
                                         }
                                     }
@@ -391,38 +993,138 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
                 };
 
                 // Create messages with metadata
-                let message_names = vec![#(#message_names_for_gen),*];
-                let message_titles = vec![#(#message_titles),*];
-                let message_summaries = vec![#(#message_summaries),*];
-                let message_descriptions = vec![#(#message_descriptions),*];
-                let message_content_types = vec![#(#message_content_types),*];
-
-                let mut messages = Vec::new();
-                for i in 0..message_names.len() {
+                let message_names: Vec<&str> = vec![#(#message_names_for_gen),*];
+                let message_wire_names: Vec<&str> = vec![#(#message_wire_names),*];
+                let message_titles: Vec<Option<String>> = vec![#(#message_titles),*];
+                let message_summaries: Vec<Option<String>> = vec![#(#message_summaries),*];
+                let message_descriptions: Vec<Option<String>> = vec![#(#message_descriptions),*];
+                let message_content_types: Vec<Option<String>> = vec![#(#message_content_types),*];
+                let message_payload_modes: Vec<&str> = vec![#(#message_payload_modes),*];
+                let message_schema_documents: Vec<Option<(&str, &str)>> =
+                    vec![#(#message_schema_documents),*];
+                // `Some(reason)` for deprecated messages
+                let message_deprecations: Vec<Option<Option<&str>>> =
+                    vec![#(#message_deprecations),*];
+                let message_headers: Vec<Option<asyncapi_rust::Schema>> = vec![#(#message_headers),*];
+                let message_correlation_ids: Vec<Option<asyncapi_rust::CorrelationId>> =
+                    vec![#(#message_correlation_ids),*];
+                let message_tags: Vec<Option<Vec<asyncapi_rust::Tag>>> =
+                    vec![#(#message_tags),*];
+                let message_external_docs: Vec<Option<asyncapi_rust::ExternalDocumentation>> =
+                    vec![#(#message_external_docs),*];
+
+                (0..message_names.len()).map(move |i| {
                     let msg_name = message_names[i];
 
                     // For enums, try to find the specific variant schema
-                    let msg_payload = if let Some(ref variant_schemas) = variant_schemas {
+                    let msg_payload = if message_payload_modes[i] == "none" {
+                        None
+                    } else if let Some((schema_format, document)) = message_schema_documents[i] {
+                        // JSON documents (e.g. Avro schemas) are embedded as JSON, others
+                        // (e.g. `.proto` files) as a string
+                        let document = serde_json::from_str(document)
+                            .unwrap_or_else(|_| serde_json::Value::from(document));
+                        Some(asyncapi_rust::Schema::multi_format(schema_format, document))
+                    } else if message_payload_modes[i] == "binary" {
+                        Some(asyncapi_rust::Schema::binary())
+                    } else if let Some(ref variant_schemas) = variant_schemas {
                         // Try to get the specific variant schema for this message
-                        variant_schemas.get(msg_name).cloned()
+                        variant_schemas.get(message_wire_names[i]).cloned()
                     } else {
                         // For structs, deserialize and use the full schema
-                        let payload_schema: asyncapi_rust::Schema = serde_json::from_value(schema_json.clone())
-                            .expect("Failed to deserialize schema");
+                        let payload_schema = asyncapi_rust::Schema::from_json_with_max_depth(
+                            schema_json.clone(),
+                            #max_schema_depth,
+                        )
+                        .unwrap_or_else(|e| panic!("Failed to deserialize schema: {}", e));
                         Some(payload_schema)
                     };
 
-                    messages.push(asyncapi_rust::Message {
+                    // Canonicalize nullable representations in generated schemas
+                    let msg_payload = msg_payload.map(|mut payload| {
+                        if message_payload_modes[i] == "schema" {
+                            payload.normalize_nullable(#nullable_style);
+                        }
+                        payload
+                    });
+
+                    // AsyncAPI messages have no `deprecated` field, so mark the message
+                    // with extensions and the payload with JSON Schema's `deprecated`
+                    let mut extensions = std::collections::HashMap::new();
+                    let mut msg_payload = msg_payload;
+                    // Tell `validate_message_payloads()` the missing payload is intended
+                    if message_payload_modes[i] == "none" {
+                        extensions.insert(
+                            asyncapi_rust::NO_PAYLOAD_EXTENSION.to_string(),
+                            serde_json::Value::Bool(true),
+                        );
+                    }
+                    if let Some(reason) = message_deprecations[i] {
+                        extensions.insert("x-deprecated".to_string(), serde_json::Value::Bool(true));
+                        if let Some(reason) = reason {
+                            extensions.insert(
+                                "x-deprecated-reason".to_string(),
+                                serde_json::Value::from(reason),
+                            );
+                        }
+                        // A schema document isn't JSON Schema, so leave it untouched
+                        if let Some(asyncapi_rust::Schema::Object(payload)) = msg_payload
+                            .as_mut()
+                            .filter(|_| message_payload_modes[i] != "document")
+                        {
+                            payload
+                                .additional
+                                .insert("deprecated".to_string(), serde_json::Value::Bool(true));
+                        }
+                    }
+
+                    asyncapi_rust::Message {
                         name: Some(msg_name.to_string()),
                         title: message_titles[i].clone(),
                         summary: message_summaries[i].clone(),
                         description: message_descriptions[i].clone(),
                         content_type: message_content_types[i].clone(),
+                        headers: message_headers[i].clone(),
+                        correlation_id: message_correlation_ids[i].clone(),
                         payload: msg_payload,
-                    });
-                }
+                        tags: message_tags[i].clone(),
+                        external_docs: message_external_docs[i].clone(),
+                        extensions,
+                    }
+                })
+            }
+        }
+
+        impl asyncapi_rust::ToAsyncApiMessage for #target {
+            fn asyncapi_message_names() -> Vec<&'static str> {
+                // Inherent methods take precedence over trait methods here
+                <#target>::asyncapi_message_names()
+            }
+
+            fn asyncapi_message_names_for(action: &str) -> Vec<&'static str> {
+                <#target>::asyncapi_message_names_for(action)
+            }
+
+            fn asyncapi_content_type_for(name: &str) -> Option<&'static str> {
+                <#target>::asyncapi_content_type_for(name)
+            }
 
-                messages
+            fn asyncapi_declared_content_type_for(name: &str) -> Option<&'static str> {
+                <#target>::asyncapi_declared_content_type_for(name)
+            }
+
+            fn asyncapi_messages() -> Vec<asyncapi_rust::Message>
+            where
+                Self: schemars::JsonSchema,
+            {
+                <#target>::asyncapi_messages()
+            }
+
+            fn asyncapi_messages_iter() -> impl Iterator<Item = asyncapi_rust::Message>
+            where
+                Self: schemars::JsonSchema,
+            {
+                <#target>::asyncapi_messages_iter()
             }
         }
     };
@@ -445,6 +1147,9 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
 /// )]
 /// struct ChatApi;
 /// ```
+///
+/// Only the attributes are read, so the type can also be a struct with fields or an
+/// enum, e.g. the application's config type.
 #[proc_macro_derive(
     AsyncApi,
     attributes(
@@ -452,6 +1157,7 @@ pub fn derive_to_asyncapi_message(input: TokenStream) -> TokenStream {
         asyncapi_server,
         asyncapi_channel,
         asyncapi_operation,
+        asyncapi_reply,
         asyncapi_messages
     )
 )]
@@ -459,9 +1165,100 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
+    // Reject keys written in the wrong shape, e.g. `strict = true`
+    for (attr_name, keys) in [
+        ("asyncapi", SPEC_KEYS),
+        ("asyncapi_server", SERVER_KEYS),
+        ("asyncapi_channel", CHANNEL_KEYS),
+        ("asyncapi_operation", OPERATION_KEYS),
+        ("asyncapi_reply", REPLY_KEYS),
+    ] {
+        if let Err(err) = check_attrs(&input.attrs, attr_name, keys) {
+            return err.to_compile_error().into();
+        }
+    }
+
     // Extract asyncapi spec metadata
     let spec_meta = extract_asyncapi_spec_meta(&input.attrs);
 
+    // Under `strict`, reject servers whose protocol AsyncAPI doesn't recognize
+    // and hosts that look like URLs rather than `host:port`
+    if spec_meta.strict {
+        for check in [validate_server_protocols, validate_server_hosts] {
+            if let Err(message) = check(&spec_meta) {
+                return syn::Error::new_spanned(name, message)
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
+
+    // Server pathname placeholders must have a matching variable definition
+    if let Err(message) = validate_server_variables(&spec_meta) {
+        return syn::Error::new_spanned(name, message)
+            .to_compile_error()
+            .into();
+    }
+
+    // Reply channels must be declared with `#[asyncapi_channel(...)]`, and reply
+    // refs with `#[asyncapi_reply(...)]`
+    for check in [validate_reply_channels, validate_reply_refs] {
+        if let Err(message) = check(&spec_meta) {
+            return syn::Error::new_spanned(name, message)
+                .to_compile_error()
+                .into();
+        }
+    }
+
+    // Declared channel parameters missing from the address are an error under
+    // `strict`, otherwise reported by `AsyncApiSpec::validate()`
+    if spec_meta.strict {
+        for channel in &spec_meta.channels {
+            if let Some(param) = unused_channel_parameters(channel).first() {
+                let message = format!(
+                    "Channel '{}' declares parameter '{}', which does not appear in its address",
+                    channel.name, param
+                );
+                return syn::Error::new_spanned(name, message)
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
+
+    // Operation message refs point at channel messages unless told otherwise
+    let components_ref_style = match spec_meta.ref_style.as_deref() {
+        None | Some("channel") => false,
+        Some("components") => true,
+        Some(other) => {
+            return syn::Error::new_spanned(
+                name,
+                format!(
+                    "Invalid ref_style '{}', must be 'channel' or 'components'",
+                    other
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // Component message refs, optionally into an external file
+    let components_ref_prefix = match spec_meta.ref_base.as_deref() {
+        Some(base) if base.contains('#') => {
+            return syn::Error::new_spanned(
+                name,
+                format!(
+                    "Invalid ref_base '{}', must be a file path or URL without a '#' fragment",
+                    base
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+        base => format!("{}#/components/messages/", base.unwrap_or_default()),
+    };
+
     // Validate required fields
     let title = match spec_meta.title {
         Some(t) => t,
@@ -487,12 +1284,103 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
         }
     };
 
-    let description = if let Some(desc) = spec_meta.description {
-        quote! { Some(#desc.to_string()) }
+    let description = match (spec_meta.description, &spec_meta.description_file) {
+        (Some(_), Some(path)) => {
+            return syn::Error::new_spanned(
+                path,
+                "description and description_file can't be used together",
+            )
+            .to_compile_error()
+            .into();
+        }
+        (Some(desc), None) => quote! { Some(#desc.to_string()) },
+        (None, Some(path)) => {
+            if let Err(err) = check_manifest_file("description_file", path) {
+                return err.to_compile_error().into();
+            }
+            quote! {
+                Some(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path)).to_string())
+            }
+        }
+        (None, None) => quote! { None },
+    };
+
+    let summary = if let Some(summary) = spec_meta.summary {
+        quote! { Some(#summary.to_string()) }
     } else {
         quote! { None }
     };
 
+    let optional_string = |value: &Option<String>| match value {
+        Some(value) => quote! { Some(#value.to_string()) },
+        None => quote! { None },
+    };
+
+    let contact_code = match &spec_meta.contact {
+        Some(contact) => {
+            let name = optional_string(&contact.name);
+            let url = optional_string(&contact.url);
+            let email = optional_string(&contact.email);
+            quote! {
+                Some(asyncapi_rust::Contact {
+                    name: #name,
+                    url: #url,
+                    email: #email,
+                })
+            }
+        }
+        None => quote! { None },
+    };
+
+    let license_code = match &spec_meta.license {
+        Some(license) => {
+            let Some(license_name) = &license.name else {
+                return syn::Error::new_spanned(
+                    &license.path,
+                    "license requires a name: #[asyncapi(license(name = \"...\"))]",
+                )
+                .to_compile_error()
+                .into();
+            };
+            let url = optional_string(&license.url);
+            quote! {
+                Some(asyncapi_rust::License {
+                    name: #license_name.to_string(),
+                    url: #url,
+                })
+            }
+        }
+        None => quote! { None },
+    };
+
+    // A missing `url` becomes a compile_error! in place of the field value
+    let external_docs_code = |docs: &Option<ExternalDocsMeta>| {
+        external_docs_tokens(docs.as_ref()).unwrap_or_else(|err| err.to_compile_error())
+    };
+
+    // Generate info tags (already deduplicated in declaration order)
+    let tags_code = if spec_meta.tags.is_empty() {
+        quote! { None }
+    } else {
+        let tag_entries = spec_meta.tags.iter().map(|tag| {
+            let tag_name = &tag.name;
+            let tag_desc = if let Some(d) = &tag.description {
+                quote! { Some(#d.to_string()) }
+            } else {
+                quote! { None }
+            };
+            let tag_docs = external_docs_code(&tag.external_docs);
+            quote! {
+                asyncapi_rust::Tag {
+                    name: #tag_name.to_string(),
+                    description: #tag_desc,
+                    external_docs: #tag_docs,
+                }
+            }
+        });
+        quote! { Some(vec![#(#tag_entries),*]) }
+    };
+
     // Generate servers
     let servers_code = if spec_meta.servers.is_empty() {
         quote! { None }
@@ -572,6 +1460,7 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
                         pathname: #pathname,
                         description: #desc,
                         variables: #variables,
+                        security: None,
                     }
                 );
             }
@@ -586,6 +1475,45 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
         }
     };
 
+    // Message types of all operations that reference a channel, including reply
+    // messages of operations and component replies on it. `messages_from` types only
+    // contribute the messages matching the operation's action.
+    let channel_message_types = |channel_name: &str| -> Vec<(&syn::Path, Option<&str>)> {
+        spec_meta
+            .operations
+            .iter()
+            .filter(|op| op.channel == channel_name)
+            .flat_map(|op| {
+                op.messages.iter().map(|type_name| (type_name, None)).chain(
+                    op.messages_from
+                        .iter()
+                        .map(|type_name| (type_name, Some(op.action.as_str()))),
+                )
+            })
+            .chain(
+                spec_meta
+                    .operations
+                    .iter()
+                    .filter(|op| op.reply_channel.as_deref() == Some(channel_name))
+                    .flat_map(|op| &op.reply_messages)
+                    .map(|type_name| (type_name, None)),
+            )
+            .chain(
+                spec_meta
+                    .replies
+                    .iter()
+                    .filter(|reply| reply.channel.as_deref() == Some(channel_name))
+                    .flat_map(|reply| &reply.messages)
+                    .map(|type_name| (type_name, None)),
+            )
+            .collect()
+    };
+    // Names of a channel message type's messages, as a runtime expression
+    let channel_message_names = |type_name: &syn::Path, action: Option<&str>| match action {
+        Some(action) => quote! { #type_name::asyncapi_message_names_for(#action) },
+        None => quote! { #type_name::asyncapi_message_names() },
+    };
+
     // Generate channels
     let channels_code = if spec_meta.channels.is_empty() {
         quote! { None }
@@ -597,6 +1525,11 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
             } else {
                 quote! { None }
             };
+            let title = if let Some(t) = &channel.title {
+                quote! { Some(#t.to_string()) }
+            } else {
+                quote! { None }
+            };
 
             // Generate channel parameters
             let parameters = if channel.parameters.is_empty() {
@@ -610,8 +1543,13 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
                         quote! { None }
                     };
 
-                    // Build schema from schema_type and format
-                    let schema = if let Some(schema_type) = &param.schema_type {
+                    // Build schema from schema_type, format and examples
+                    let schema = if param.schema_type.is_some() || !param.examples.is_empty() {
+                        let schema_type = if let Some(t) = &param.schema_type {
+                            quote! { Some(serde_json::json!(#t)) }
+                        } else {
+                            quote! { None }
+                        };
                         let format_field = if let Some(fmt) = &param.format {
                             quote! {
                                 additional.insert("format".to_string(), serde_json::json!(#fmt));
@@ -619,13 +1557,25 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
                         } else {
                             quote! {}
                         };
+                        // Schema examples keep their JSON type (unlike server variable examples)
+                        let examples_field = if param.examples.is_empty() {
+                            quote! {}
+                        } else {
+                            let examples = &param.examples;
+                            quote! {
+                                let examples: Vec<serde_json::Value> =
+                                    vec![#(serde_json::json!(#examples)),*];
+                                additional.insert("examples".to_string(), serde_json::Value::Array(examples));
+                            }
+                        };
 
                         quote! {
                             {
                                 let mut additional = std::collections::HashMap::new();
                                 #format_field
+                                #examples_field
                                 Some(asyncapi_rust::Schema::Object(Box::new(asyncapi_rust::SchemaObject {
-                                    schema_type: Some(serde_json::json!(#schema_type)),
+                                    schema_type: #schema_type,
                                     properties: None,
                                     required: None,
                                     description: None,
@@ -637,6 +1587,8 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
                                     one_of: None,
                                     any_of: None,
                                     all_of: None,
+                                    read_only: None,
+                                    write_only: None,
                                     additional,
                                 })))
                             }
@@ -650,6 +1602,10 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
                             #param_name.to_string(),
                             asyncapi_rust::Parameter {
                                 description: #param_desc,
+                                enum_values: None,
+                                default: None,
+                                examples: None,
+                                location: None,
                                 schema: #schema,
                             }
                         );
@@ -666,27 +1622,23 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
             };
 
             // Collect messages from all operations that reference this channel
-            let channel_name_str = name.as_str();
-            let operations_for_channel: Vec<_> = spec_meta.operations.iter()
-                .filter(|op| op.channel == channel_name_str)
-                .collect();
+            let channel_message_types = channel_message_types(name);
 
-            let messages_field = if operations_for_channel.is_empty() ||
-                                   operations_for_channel.iter().all(|op| op.messages.is_empty()) {
+            let messages_field = if channel_message_types.is_empty() {
                 quote! { None }
             } else {
-                let message_calls: Vec<_> = operations_for_channel.iter()
-                    .flat_map(|op| &op.messages)
+                let message_calls: Vec<_> = channel_message_types.into_iter()
                     .collect::<std::collections::HashSet<_>>() // Deduplicate
                     .into_iter()
-                    .map(|type_name| {
+                    .map(|(type_name, action)| {
+                        let names = channel_message_names(type_name, action);
                         quote! {
                             // Call asyncapi_message_names() for this type and add references
-                            for msg_name in #type_name::asyncapi_message_names() {
+                            for msg_name in #names {
                                 channel_messages.insert(
                                     msg_name.to_string(),
                                     asyncapi_rust::MessageRef::Reference {
-                                        reference: format!("#/components/messages/{}", msg_name),
+                                        reference: format!("{}{}", #components_ref_prefix, msg_name),
                                     }
                                 );
                             }
@@ -703,13 +1655,40 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
                 }
             };
 
+            let bindings = if let Some(bindings_ref) = &channel.bindings_ref {
+                let reference = format!("#/components/channelBindings/{}", bindings_ref);
+                quote! {
+                    Some(asyncapi_rust::BindingsRef::Reference {
+                        reference: #reference.to_string(),
+                    })
+                }
+            } else {
+                quote! { None }
+            };
+
+            let deprecated = match channel.deprecated {
+                Some(deprecated) => quote! { Some(#deprecated) },
+                None => quote! { None },
+            };
+            let external_docs = external_docs_code(&channel.external_docs);
+            let default_content_type = match &channel.default_content_type {
+                Some(content_type) => quote! { Some(#content_type.to_string()) },
+                None => quote! { None },
+            };
+
             quote! {
                 channels.insert(
                     #name.to_string(),
                     asyncapi_rust::Channel {
                         address: #address,
+                        title: #title,
                         messages: #messages_field,
                         parameters: #parameters,
+                        tags: None,
+                        external_docs: #external_docs,
+                        bindings: #bindings,
+                        deprecated: #deprecated,
+                        default_content_type: #default_content_type,
                     }
                 );
             }
@@ -724,85 +1703,294 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
         }
     };
 
+    // An `OperationReply` expression; messages reference the reply channel's messages
+    // (or components, without a channel or under `ref_style = "components"`)
+    let reply_code = |reply_channel: Option<&String>, reply_messages: &[syn::Path]| {
+        let reply_channel_field = match reply_channel {
+            Some(reply_channel) => quote! {
+                Some(asyncapi_rust::ChannelRef {
+                    reference: format!("#/channels/{}", #reply_channel),
+                })
+            },
+            None => quote! { None },
+        };
+        let reference = match reply_channel {
+            Some(reply_channel) if !components_ref_style => quote! {
+                format!("#/channels/{}/messages/{}", #reply_channel, msg_name)
+            },
+            _ => quote! { format!("{}{}", #components_ref_prefix, msg_name) },
+        };
+        let reply_messages_field = if reply_messages.is_empty() {
+            quote! { None }
+        } else {
+            let message_calls = reply_messages.iter().map(|type_name| {
+                quote! {
+                    for msg_name in #type_name::asyncapi_message_names() {
+                        message_refs.push(asyncapi_rust::MessageRef::Reference {
+                            reference: #reference,
+                        });
+                    }
+                }
+            });
+            quote! {
+                {
+                    let mut message_refs = Vec::new();
+                    #(#message_calls)*
+                    Some(message_refs)
+                }
+            }
+        };
+        quote! {
+            asyncapi_rust::OperationReply {
+                address: None,
+                channel: #reply_channel_field,
+                messages: #reply_messages_field,
+            }
+        }
+    };
+
     // Generate operations
     let operations_code = if spec_meta.operations.is_empty() {
         quote! { None }
     } else {
-        let operation_entries = spec_meta.operations.iter().map(|operation| {
-            let name = &operation.name;
-            let channel_ref = &operation.channel;
-            let action = &operation.action;
-
-            // Convert action string to OperationAction enum
-            let action_enum = if action == "send" {
-                quote! { asyncapi_rust::OperationAction::Send }
-            } else if action == "receive" {
-                quote! { asyncapi_rust::OperationAction::Receive }
-            } else {
-                return syn::Error::new_spanned(
-                    name,
-                    format!("Invalid action '{}', must be 'send' or 'receive'", action),
-                )
-                .to_compile_error();
-            };
+        let operation_entries =
+            ordered_operations(&spec_meta.operations)
+                .into_iter()
+                .map(|operation| {
+                    let name = &operation.name;
+                    let channel_ref = &operation.channel;
+                    let action = &operation.action;
+
+                    // Convert action string to OperationAction enum
+                    let action_enum = if action == "send" {
+                        quote! { asyncapi_rust::OperationAction::Send }
+                    } else if action == "receive" {
+                        quote! { asyncapi_rust::OperationAction::Receive }
+                    } else {
+                        return syn::Error::new_spanned(
+                            name,
+                            format!("Invalid action '{}', must be 'send' or 'receive'", action),
+                        )
+                        .to_compile_error();
+                    };
 
-            // Generate messages references if any messages are specified
-            let messages_field = if operation.messages.is_empty() {
-                quote! { None }
-            } else {
-                let message_calls = operation.messages.iter().map(|type_name| {
-                    quote! {
-                        // Call asyncapi_message_names() for this type and add references to channel messages
-                        for msg_name in #type_name::asyncapi_message_names() {
-                            message_refs.push(asyncapi_rust::MessageRef::Reference {
-                                reference: format!("#/channels/{}/messages/{}", #channel_ref, msg_name),
+                    // Generate messages references if any messages are specified
+                    let messages_field = if operation.messages.is_empty()
+                        && operation.messages_from.is_none()
+                    {
+                        quote! { None }
+                    } else {
+                        let reference = if components_ref_style {
+                            quote! { format!("{}{}", #components_ref_prefix, msg_name) }
+                        } else {
+                            quote! { format!("#/channels/{}/messages/{}", #channel_ref, msg_name) }
+                        };
+                        let message_calls = operation
+                            .messages
+                            .iter()
+                            .map(|type_name| quote! { #type_name::asyncapi_message_names() })
+                            .chain(operation.messages_from.iter().map(|type_name| {
+                                quote! { #type_name::asyncapi_message_names_for(#action) }
+                            }))
+                            .map(|names| {
+                                quote! {
+                                    // Add references to this type's messages
+                                    for msg_name in #names {
+                                        message_refs.push(asyncapi_rust::MessageRef::Reference {
+                                            reference: #reference,
+                                        });
+                                    }
+                                }
                             });
+
+                        quote! {
+                            {
+                                let mut message_refs = Vec::new();
+                                #(#message_calls)*
+                                Some(message_refs)
+                            }
                         }
-                    }
-                });
+                    };
 
-                quote! {
+                    let title_field = optional_string(&operation.title);
+                    let summary_field = optional_string(&operation.summary);
+                    let description_field = optional_string(&operation.description);
+
+                    // Operation tags reuse the description and docs of a matching spec-level tag
+                    let external_docs_field = external_docs_code(&operation.external_docs);
+                    let tags_field = if operation.tags.is_empty() {
+                        quote! { None }
+                    } else {
+                        let tags = operation.tags.iter().map(|tag_name| {
+                            let spec_tag = spec_meta.tags.iter().find(|tag| &tag.name == tag_name);
+                            let description =
+                                optional_string(&spec_tag.and_then(|tag| tag.description.clone()));
+                            let external_docs = external_docs_code(
+                                &spec_tag.and_then(|tag| tag.external_docs.clone()),
+                            );
+                            quote! {
+                                asyncapi_rust::Tag {
+                                    name: #tag_name.to_string(),
+                                    description: #description,
+                                    external_docs: #external_docs,
+                                }
+                            }
+                        });
+                        quote! { Some(vec![#(#tags),*]) }
+                    };
+
+                    let security_field = if operation.security.is_empty() {
+                        quote! { None }
+                    } else {
+                        let references = operation
+                            .security
+                            .iter()
+                            .map(|scheme| format!("#/components/securitySchemes/{}", scheme));
+                        quote! {
+                            Some(vec![#(asyncapi_rust::SecuritySchemeRef::Reference {
+                                reference: #references.to_string(),
+                            }),*])
+                        }
+                    };
+
+                    // Like messages, operations have no `deprecated` field in AsyncAPI 3.0
+                    let deprecation = match (operation.deprecated, &operation.deprecated_reason) {
+                        (false, _) => quote! {},
+                        (true, reason) => {
+                            let reason = reason.iter();
+                            quote! {
+                                extensions.insert(
+                                    "x-deprecated".to_string(),
+                                    serde_json::Value::Bool(true),
+                                );
+                                #(extensions.insert(
+                                    "x-deprecated-reason".to_string(),
+                                    serde_json::Value::from(#reason),
+                                );)*
+                            }
+                        }
+                    };
+                    let extensions_field = if operation.deprecated {
+                        quote! {
+                            {
+                                let mut extensions = std::collections::HashMap::new();
+                                #deprecation
+                                extensions
+                            }
+                        }
+                    } else {
+                        quote! { std::collections::HashMap::new() }
+                    };
+
+                    let bindings_field = if let Some(subprotocol) = &operation.ws_subprotocol {
+                        quote! {
+                            Some(asyncapi_rust::OperationBindings {
+                                ws: Some(asyncapi_rust::WebSocketOperationBinding {
+                                    subprotocol: Some(#subprotocol.to_string()),
+                                }),
+                                additional: std::collections::HashMap::new(),
+                            })
+                        }
+                    } else {
+                        quote! { None }
+                    };
+
+                    let reply_field = if let Some(reply_ref) = &operation.reply_ref {
+                        let reference = format!("#/components/replies/{}", reply_ref);
+                        quote! {
+                            Some(asyncapi_rust::OperationReplyRef::Reference {
+                                reference: #reference.to_string(),
+                            })
+                        }
+                    } else if operation.reply_channel.is_none()
+                        && operation.reply_messages.is_empty()
                     {
-                        let mut message_refs = Vec::new();
-                        #(#message_calls)*
-                        Some(message_refs)
-                    }
-                }
-            };
+                        quote! { None }
+                    } else {
+                        let reply =
+                            reply_code(operation.reply_channel.as_ref(), &operation.reply_messages);
+                        quote! {
+                            Some(asyncapi_rust::OperationReplyRef::Inline(#reply))
+                        }
+                    };
 
-            quote! {
-                operations.insert(
-                    #name.to_string(),
-                    asyncapi_rust::Operation {
-                        action: #action_enum,
-                        channel: asyncapi_rust::ChannelRef {
-                            reference: format!("#/channels/{}", #channel_ref),
-                        },
-                        messages: #messages_field,
+                    quote! {
+                        operations.insert(
+                            #name.to_string(),
+                            asyncapi_rust::Operation {
+                                action: #action_enum,
+                                channel: asyncapi_rust::ChannelRef {
+                                    reference: format!("#/channels/{}", #channel_ref),
+                                },
+                                title: #title_field,
+                                summary: #summary_field,
+                                description: #description_field,
+                                messages: #messages_field,
+                                reply: #reply_field,
+                                tags: #tags_field,
+                                external_docs: #external_docs_field,
+                                security: #security_field,
+                                bindings: #bindings_field,
+                                extensions: #extensions_field,
+                            }
+                        );
                     }
-                );
-            }
-        });
+                });
 
         quote! {
             {
-                let mut operations = std::collections::HashMap::new();
+                let mut operations = asyncapi_rust::IndexMap::new();
                 #(#operation_entries)*
                 Some(operations)
             }
         }
     };
 
-    // Generate components with messages
-    let components_code = if spec_meta.message_types.is_empty() {
+    // Generate components with messages from `#[asyncapi_messages(...)]` and from
+    // operations (channels reference them under `#/components/messages/...`).
+    // Each type is listed once so its schemas are only generated once.
+    let mut seen_message_types = std::collections::HashSet::new();
+    let component_message_types: Vec<_> = spec_meta
+        .message_types
+        .iter()
+        .chain(spec_meta.operations.iter().flat_map(|op| {
+            op.messages
+                .iter()
+                .chain(&op.messages_from)
+                .chain(&op.reply_messages)
+        }))
+        .chain(spec_meta.replies.iter().flat_map(|reply| &reply.messages))
+        .filter(|type_name| seen_message_types.insert(quote!(#type_name).to_string()))
+        .collect();
+
+    let replies_field = if spec_meta.replies.is_empty() {
         quote! { None }
     } else {
-        let message_calls = spec_meta.message_types.iter().map(|type_name| {
+        let reply_entries = spec_meta.replies.iter().map(|reply| {
+            let name = &reply.name;
+            let reply = reply_code(reply.channel.as_ref(), &reply.messages);
+            quote! {
+                replies.insert(#name.to_string(), #reply);
+            }
+        });
+        quote! {
+            {
+                let mut replies = std::collections::HashMap::new();
+                #(#reply_entries)*
+                Some(replies)
+            }
+        }
+    };
+
+    let components_code = if component_message_types.is_empty() && spec_meta.replies.is_empty() {
+        quote! { None }
+    } else {
+        let message_calls = component_message_types.iter().map(|type_name| {
             quote! {
                 // Call asyncapi_messages() for this type and add to messages map
                 for msg in #type_name::asyncapi_messages() {
                     if let Some(ref name) = msg.name {
-                        messages.insert(name.clone(), msg.clone());
+                        messages.entry(name.clone()).or_insert(msg);
                     }
                 }
             }
@@ -812,33 +2000,216 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
             {
                 let mut messages = std::collections::HashMap::new();
                 #(#message_calls)*
-                Some(asyncapi_rust::Components {
+                let components = asyncapi_rust::Components {
                     messages: if messages.is_empty() { None } else { Some(messages) },
                     schemas: None,
-                })
+                    security_schemes: None,
+                    replies: #replies_field,
+                    reply_addresses: None,
+                    channel_bindings: None,
+                };
+                // Strict validators reject `"components": {}`, so omit it entirely
+                if components.is_empty() { None } else { Some(components) }
+            }
+        }
+    };
+
+    // Message names of every type in `#[asyncapi_messages(...)]`, in order
+    let message_type_names = spec_meta.message_types.iter().map(|type_name| {
+        quote! {
+            names.extend(#type_name::asyncapi_message_names());
+        }
+    });
+    let all_message_names_body = if spec_meta.message_types.is_empty() {
+        quote! { Vec::new() }
+    } else {
+        quote! {
+            let mut names = Vec::new();
+            #(#message_type_names)*
+            names
+        }
+    };
+
+    let default_content_type = match &spec_meta.default_content_type {
+        Some(content_type) => quote! { Some(#content_type.to_string()) },
+        None => quote! { None },
+    };
+    let apply_content_type_defaults = match &spec_meta.content_type_defaults {
+        Some(defaults) => quote! { spec.apply_content_type_defaults(&#defaults()); },
+        None => quote! {},
+    };
+
+    // Messages that don't declare a content type take their channel's default, then
+    // the document's, instead of `application/json`. The first channel declaring a
+    // default wins for messages on several channels; `validate()` reports those.
+    let channel_content_type_defaults: Vec<_> = spec_meta
+        .channels
+        .iter()
+        .filter_map(|channel| {
+            let content_type = channel.default_content_type.as_ref()?;
+            let calls = channel_message_types(&channel.name)
+                .into_iter()
+                .map(|(type_name, action)| {
+                    let names = channel_message_names(type_name, action);
+                    quote! {
+                        for msg_name in #names {
+                            if #type_name::asyncapi_declared_content_type_for(msg_name).is_none() {
+                                content_types.entry(msg_name).or_insert_with(|| #content_type.to_string());
+                            }
+                        }
+                    }
+                });
+            Some(quote! { #(#calls)* })
+        })
+        .collect();
+    let has_document_default =
+        spec_meta.default_content_type.is_some() || spec_meta.content_type_defaults.is_some();
+    let apply_message_content_types = if component_message_types.is_empty()
+        || (channel_content_type_defaults.is_empty() && !has_document_default)
+    {
+        quote! {}
+    } else {
+        let document_content_type_defaults = component_message_types.iter().map(|type_name| {
+            quote! {
+                for msg_name in #type_name::asyncapi_message_names() {
+                    if #type_name::asyncapi_declared_content_type_for(msg_name).is_none() {
+                        content_types
+                            .entry(msg_name)
+                            .or_insert_with(|| document_default.clone());
+                    }
+                }
+            }
+        });
+        quote! {
+            // Precedence: message, then channel, then document, then application/json
+            let mut content_types = std::collections::HashMap::new();
+            #(#channel_content_type_defaults)*
+            if let Some(document_default) = spec.default_content_type.clone() {
+                #(#document_content_type_defaults)*
+            }
+            if let Some(messages) = spec.components.as_mut().and_then(|c| c.messages.as_mut()) {
+                for (name, content_type) in content_types {
+                    if let Some(message) = messages.get_mut(name) {
+                        message.content_type = Some(content_type);
+                    }
+                }
+            }
+        }
+    };
+
+    // Component message tags reuse the description and docs of a matching spec-level tag
+    let apply_message_tag_details = if component_message_types.is_empty()
+        || spec_meta.tags.is_empty()
+    {
+        quote! {}
+    } else {
+        quote! {
+            let declared = spec.info.tags.clone().unwrap_or_default();
+            let message_tags = spec
+                .components
+                .iter_mut()
+                .flat_map(|c| c.messages.iter_mut().flat_map(|m| m.values_mut()))
+                .flat_map(|message| message.tags.iter_mut().flatten());
+            for tag in message_tags {
+                if let Some(spec_tag) = declared.iter().find(|d| d.name == tag.name) {
+                    tag.description = tag.description.take().or_else(|| spec_tag.description.clone());
+                    tag.external_docs =
+                        tag.external_docs.take().or_else(|| spec_tag.external_docs.clone());
+                }
             }
         }
     };
 
+    // Runs last, so channels get the component messages in their final form
+    let apply_omit_channel_message_names = if spec_meta.omit_channel_message_names {
+        quote! {
+            spec.omit_channel_message_names();
+        }
+    } else {
+        quote! {}
+    };
+
+    // Operations declared on functions with `#[asyncapi_operation_fn]` follow the
+    // derive's settings; the attribute macro checks them against these under `strict`
+    #[cfg(feature = "operation-fn")]
+    let (operation_fn_options, apply_operation_fns) = {
+        let strict = spec_meta.strict;
+        let channel_names = spec_meta.channels.iter().map(|channel| &channel.name);
+        let operation_orders =
+            ordered_operations(&spec_meta.operations)
+                .into_iter()
+                .map(|operation| {
+                    let name = &operation.name;
+                    let order = match operation.order {
+                        Some(order) => quote! { Some(#order) },
+                        None => quote! { None },
+                    };
+                    quote! { (#name, #order) }
+                });
+        (
+            quote! {
+                /// Settings that operations declared with `#[asyncapi_operation_fn]` follow
+                #[doc(hidden)]
+                pub const ASYNCAPI_OPERATION_FN_OPTIONS: asyncapi_rust::operation_fn::ApiOptions =
+                    asyncapi_rust::operation_fn::ApiOptions {
+                        strict: #strict,
+                        components_ref_style: #components_ref_style,
+                        components_ref_prefix: #components_ref_prefix,
+                        channels: &[#(#channel_names),*],
+                        operations: &[#(#operation_orders),*],
+                    };
+            },
+            quote! {
+                asyncapi_rust::operation_fn::apply::<Self>(
+                    &mut spec,
+                    &Self::ASYNCAPI_OPERATION_FN_OPTIONS,
+                );
+            },
+        )
+    };
+    #[cfg(not(feature = "operation-fn"))]
+    let (operation_fn_options, apply_operation_fns) = (quote! {}, quote! {});
+
     let expanded = quote! {
         impl #name {
+            #operation_fn_options
+
+            /// Get the message names of all types listed in `#[asyncapi_messages(...)]`
+            ///
+            /// Names are concatenated in the order the types are listed.
+            pub fn asyncapi_all_message_names() -> Vec<&'static str> {
+                #all_message_names_body
+            }
+
             /// Generate the AsyncAPI specification
             ///
             /// Returns an AsyncApiSpec with Info, Servers, Channels, and Operations
             /// sections populated from attributes.
             pub fn asyncapi_spec() -> asyncapi_rust::AsyncApiSpec {
-                asyncapi_rust::AsyncApiSpec {
+                let mut spec = asyncapi_rust::AsyncApiSpec {
                     asyncapi: "3.0.0".to_string(),
                     info: asyncapi_rust::Info {
-                        title: #title.to_string(),
-                        version: #version.to_string(),
+                        title: (#title).to_string(),
+                        version: (#version).to_string(),
                         description: #description,
+                        summary: #summary,
+                        contact: #contact_code,
+                        license: #license_code,
+                        tags: #tags_code,
                     },
                     servers: #servers_code,
+                    default_content_type: #default_content_type,
                     channels: #channels_code,
                     operations: #operations_code,
                     components: #components_code,
-                }
+                };
+                #apply_content_type_defaults
+                // Operations declared on functions with #[asyncapi_operation_fn]
+                #apply_operation_fns
+                #apply_message_content_types
+                #apply_message_tag_details
+                #apply_omit_channel_message_names
+                spec
             }
         }
     };
@@ -846,6 +2217,149 @@ pub fn derive_asyncapi(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Attribute macro declaring an AsyncAPI operation on a function
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use asyncapi_rust::asyncapi_operation_fn;
+///
+/// /// Broadcast a message to everyone in the room
+/// #[asyncapi_operation_fn(api = ChatApi, action = "send", channel = "chat", messages = [ChatMessage])]
+/// fn broadcast(text: &str) -> ChatMessage {
+///     ChatMessage::Chat { text: text.to_string() }
+/// }
+/// ```
+///
+/// The function is left unchanged. The operation is registered with `inventory` and
+/// added to the spec when `ChatApi::asyncapi_spec()` runs; see
+/// `asyncapi_rust::operation_fn`. Only free functions can be annotated.
+#[cfg(feature = "operation-fn")]
+#[proc_macro_attribute]
+pub fn asyncapi_operation_fn(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let attr: syn::Attribute = syn::parse_quote! { #[asyncapi_operation_fn(#args)] };
+    let item_fn = parse_macro_input!(item as syn::ItemFn);
+    let fn_name = &item_fn.sig.ident;
+
+    if let Err(err) = check_attrs(
+        std::slice::from_ref(&attr),
+        "asyncapi_operation_fn",
+        OPERATION_FN_KEYS,
+    ) {
+        return err.to_compile_error().into();
+    }
+    let meta = extract_operation_fn(&attr);
+
+    let (Some(api), Some(action), Some(channel)) = (&meta.api, &meta.action, &meta.channel) else {
+        return syn::Error::new_spanned(
+            fn_name,
+            "#[asyncapi_operation_fn] requires `api = ApiType`, `action = \"send\"|\"receive\"` and `channel = \"...\"`",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let action_enum = match action.value().as_str() {
+        "send" => quote! { asyncapi_rust::OperationAction::Send },
+        "receive" => quote! { asyncapi_rust::OperationAction::Receive },
+        other => {
+            return syn::Error::new_spanned(
+                action,
+                format!("Invalid action '{}', must be 'send' or 'receive'", other),
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let name = meta.name.unwrap_or_else(|| fn_name.to_string());
+    let optional_str = |value: Option<String>| match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    };
+    let summary = optional_str(meta.summary);
+    let description = optional_str(
+        meta.description
+            .or_else(|| extract_doc_description(&item_fn.attrs)),
+    );
+    let message_types = &meta.messages;
+    let order = match meta.order {
+        Some(order) => quote! { Some(#order) },
+        None => quote! { None },
+    };
+
+    // Under the API's `strict`, an unknown channel or a name taken by an
+    // `#[asyncapi_operation]` is a compile error; otherwise `validate()` reports it
+    let unknown_channel = format!(
+        "Operation '{}' uses channel '{}', which is not a declared channel",
+        name, channel
+    );
+    let taken_name = format!(
+        "Operation '{}' is already declared with #[asyncapi_operation]",
+        name
+    );
+
+    let expanded = quote! {
+        #item_fn
+
+        const _: () = {
+            let options = &<#api>::ASYNCAPI_OPERATION_FN_OPTIONS;
+            assert!(!options.strict || options.has_channel(#channel), #unknown_channel);
+            assert!(!options.strict || !options.has_operation(#name), #taken_name);
+        };
+
+        asyncapi_rust::operation_fn::inventory::submit! {
+            asyncapi_rust::operation_fn::OperationFn {
+                api: ::core::any::TypeId::of::<#api>,
+                name: #name,
+                action: #action_enum,
+                channel: #channel,
+                summary: #summary,
+                description: #description,
+                messages: {
+                    fn messages() -> Vec<asyncapi_rust::Message> {
+                        let messages: Vec<Vec<asyncapi_rust::Message>> = vec![
+                            #(<#message_types as asyncapi_rust::ToAsyncApiMessage>::asyncapi_messages()),*
+                        ];
+                        messages.into_iter().flatten().collect()
+                    }
+                    messages
+                },
+                declared_content_type: {
+                    fn declared_content_type(name: &str) -> bool {
+                        let declared: &[Option<&str>] = &[
+                            #(<#message_types as asyncapi_rust::ToAsyncApiMessage>::asyncapi_declared_content_type_for(name)),*
+                        ];
+                        declared.iter().any(Option::is_some)
+                    }
+                    declared_content_type
+                },
+                order: #order,
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Check that a `*_file` attribute names a file under `CARGO_MANIFEST_DIR`
+///
+/// include_str! fails too, but without saying which attribute is at fault.
+fn check_manifest_file(attr: &str, path: &syn::LitStr) -> syn::Result<()> {
+    let missing = std::env::var_os("CARGO_MANIFEST_DIR")
+        .is_some_and(|dir| !std::path::Path::new(&dir).join(path.value()).is_file());
+    if missing {
+        return Err(syn::Error::new_spanned(
+            path,
+            format!(
+                "{attr} '{}' not found; the path is relative to CARGO_MANIFEST_DIR",
+                path.value()
+            ),
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
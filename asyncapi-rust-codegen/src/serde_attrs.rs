@@ -52,6 +52,33 @@ pub fn extract_serde_tag(attrs: &[Attribute]) -> Option<String> {
     None
 }
 
+/// Check whether `#[serde(transparent)]` is present
+pub fn has_serde_transparent(attrs: &[Attribute]) -> bool {
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+
+        let mut transparent = false;
+
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("transparent") {
+                transparent = true;
+            } else if meta.input.peek(syn::Token![=]) {
+                // Skip over values of other keys so parsing can continue
+                let value = meta.value()?;
+                let _: syn::Expr = value.parse()?;
+            }
+            Ok(())
+        });
+
+        if transparent {
+            return true;
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +122,22 @@ mod tests {
 
         assert_eq!(extract_serde_tag(&attrs), None);
     }
+
+    #[test]
+    fn test_has_serde_transparent() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[serde(transparent)]
+        }];
+
+        assert!(has_serde_transparent(&attrs));
+    }
+
+    #[test]
+    fn test_has_serde_transparent_none() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[serde(rename = "foo")]
+        }];
+
+        assert!(!has_serde_transparent(&attrs));
+    }
 }
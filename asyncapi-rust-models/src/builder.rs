@@ -0,0 +1,580 @@
+//! Runtime builder for AsyncAPI specifications
+//!
+//! The `#[derive(AsyncApi)]` macro covers APIs whose shape is known at compile time.
+//! Servers that register channels dynamically (e.g. plugin systems) can use
+//! [`SpecBuilder`] instead, which produces the same structure at runtime.
+
+use crate::prelude::*;
+use crate::{
+    AsyncApiSpec, Channel, ChannelRef, Components, IndexMap, Map, Message, MessageRef, Operation,
+    OperationAction, Parameter, Server, ToAsyncApiMessage,
+};
+#[cfg(feature = "std")]
+use schemars::JsonSchema;
+
+/// Builder for constructing an [`AsyncApiSpec`] at runtime
+///
+/// Created with [`AsyncApiSpec::builder()`].
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::{AsyncApiSpec, OperationAction};
+///
+/// let spec = AsyncApiSpec::builder()
+///     .title("Plugin API")
+///     .version("1.0.0")
+///     .channel("chat", |c| c.address("/ws/chat"))
+///     .operation("sendMessage", OperationAction::Send, "chat", |o| o)
+///     .build();
+///
+/// assert!(spec.channels.unwrap().contains_key("chat"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SpecBuilder {
+    spec: AsyncApiSpec,
+    messages: Map<String, Message>,
+    omit_channel_message_names: bool,
+}
+
+impl SpecBuilder {
+    /// Set the API title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.spec.info.title = title.into();
+        self
+    }
+
+    /// Set the API version
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.spec.info.version = version.into();
+        self
+    }
+
+    /// Set the API description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.spec.info.description = Some(description.into());
+        self
+    }
+
+    /// Inline channel messages without their `name`, which `components.messages` keep
+    ///
+    /// See [`AsyncApiSpec::omit_channel_message_names`].
+    pub fn omit_channel_message_names(mut self) -> Self {
+        self.omit_channel_message_names = true;
+        self
+    }
+
+    /// Add a server
+    pub fn server(mut self, name: impl Into<String>, server: Server) -> Self {
+        self.spec
+            .servers
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), server);
+        self
+    }
+
+    /// Add a channel configured by the given closure
+    ///
+    /// Messages registered on the channel are added to `components.messages`,
+    /// and the channel references them from there.
+    pub fn channel<F>(mut self, name: impl Into<String>, configure: F) -> Self
+    where
+        F: FnOnce(ChannelBuilder) -> ChannelBuilder,
+    {
+        let built = configure(ChannelBuilder::default());
+        for message in built.messages {
+            if let Some(ref msg_name) = message.name {
+                self.messages.insert(msg_name.clone(), message);
+            }
+        }
+        self.spec
+            .channels
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), built.channel);
+        self
+    }
+
+    /// Add an operation on `channel` configured by the given closure
+    ///
+    /// Operation messages reference the channel's messages
+    /// (`#/channels/{channel}/messages/{message}`), matching the derive output.
+    pub fn operation<F>(
+        mut self,
+        name: impl Into<String>,
+        action: OperationAction,
+        channel: impl Into<String>,
+        configure: F,
+    ) -> Self
+    where
+        F: FnOnce(OperationBuilder) -> OperationBuilder,
+    {
+        let channel = channel.into();
+        let built = configure(OperationBuilder::default());
+
+        let messages = if built.message_names.is_empty() {
+            None
+        } else {
+            Some(
+                built
+                    .message_names
+                    .iter()
+                    .map(|msg_name| MessageRef::Reference {
+                        reference: format!("#/channels/{}/messages/{}", channel, msg_name),
+                    })
+                    .collect(),
+            )
+        };
+
+        self.spec
+            .operations
+            .get_or_insert_with(IndexMap::default)
+            .insert(
+                name.into(),
+                Operation {
+                    action,
+                    channel: ChannelRef {
+                        reference: format!("#/channels/{}", channel),
+                    },
+                    title: None,
+                    summary: None,
+                    description: None,
+                    messages,
+                    reply: None,
+                    tags: None,
+                    external_docs: None,
+                    security: None,
+                    bindings: None,
+                    extensions: Map::new(),
+                },
+            );
+        self
+    }
+
+    /// Finish building and return the specification
+    pub fn build(mut self) -> AsyncApiSpec {
+        let components = Components {
+            messages: if self.messages.is_empty() {
+                None
+            } else {
+                Some(self.messages)
+            },
+            schemas: None,
+            security_schemes: None,
+            replies: None,
+            reply_addresses: None,
+            channel_bindings: None,
+        };
+        if !components.is_empty() {
+            self.spec.components = Some(components);
+        }
+        if self.omit_channel_message_names {
+            self.spec.omit_channel_message_names();
+        }
+        self.spec
+    }
+}
+
+/// Builder for a single [`Channel`], used by [`SpecBuilder::channel`]
+#[derive(Debug, Clone, Default)]
+pub struct ChannelBuilder {
+    channel: Channel,
+    messages: Vec<Message>,
+}
+
+impl ChannelBuilder {
+    /// Set the channel address
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.channel.address = Some(address.into());
+        self
+    }
+
+    /// Add a channel parameter
+    pub fn parameter(mut self, name: impl Into<String>, parameter: Parameter) -> Self {
+        self.channel
+            .parameters
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), parameter);
+        self
+    }
+
+    /// Register every message of `T` on this channel
+    #[cfg(feature = "std")]
+    pub fn message<T>(mut self) -> Self
+    where
+        T: ToAsyncApiMessage + JsonSchema,
+    {
+        let channel_messages = self.channel.messages_mut();
+        for message in T::asyncapi_messages() {
+            if let Some(ref msg_name) = message.name {
+                channel_messages.insert(
+                    msg_name.clone(),
+                    MessageRef::Reference {
+                        reference: format!("#/components/messages/{}", msg_name),
+                    },
+                );
+            }
+            self.messages.push(message);
+        }
+        self
+    }
+}
+
+/// Builder for a single [`Operation`], used by [`SpecBuilder::operation`]
+#[derive(Debug, Clone, Default)]
+pub struct OperationBuilder {
+    message_names: Vec<String>,
+}
+
+impl OperationBuilder {
+    /// Reference every message of `T` from this operation
+    ///
+    /// The messages should also be registered on the operation's channel with
+    /// [`ChannelBuilder::message`] so the references resolve.
+    pub fn message<T>(mut self) -> Self
+    where
+        T: ToAsyncApiMessage,
+    {
+        self.message_names.extend(
+            T::asyncapi_message_names()
+                .into_iter()
+                .map(|msg_name| msg_name.to_string()),
+        );
+        self
+    }
+}
+
+/// Build an [`AsyncApiSpec`] inline
+///
+/// A terser alternative to [`SpecBuilder`] for tests and scripts. Every key is
+/// optional and expands to the matching builder call:
+///
+/// - `title`, `version`, `description` - Info fields
+/// - `servers: { "name": { host: ..., protocol: ..., pathname: ..., description: ... } }`
+/// - `channels: { "name": { address: ..., messages: [Type, ...] } }`
+/// - `operations: { "name": { action: send|receive, channel: "...", messages: [Type, ...] } }`
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::spec;
+///
+/// let spec = spec! {
+///     title: "Chat API",
+///     version: "1.0.0",
+///     servers: {
+///         "production": { host: "chat.example.com:443", protocol: "wss" },
+///     },
+///     channels: {
+///         "chat": { address: "/ws/chat" },
+///     },
+///     operations: {
+///         "sendMessage": { action: send, channel: "chat" },
+///     },
+/// };
+///
+/// assert_eq!(spec.info.title, "Chat API");
+/// assert_eq!(spec.servers.unwrap()["production"].protocol, "wss");
+/// assert!(spec.operations.unwrap().contains_key("sendMessage"));
+/// ```
+#[macro_export]
+macro_rules! spec {
+    (@spec $builder:expr ;) => {
+        $builder.build()
+    };
+    (@spec $builder:expr ; title: $title:expr $(, $($rest:tt)*)?) => {
+        $crate::spec!(@spec $builder.title($title) ; $($($rest)*)?)
+    };
+    (@spec $builder:expr ; version: $version:expr $(, $($rest:tt)*)?) => {
+        $crate::spec!(@spec $builder.version($version) ; $($($rest)*)?)
+    };
+    (@spec $builder:expr ; description: $description:expr $(, $($rest:tt)*)?) => {
+        $crate::spec!(@spec $builder.description($description) ; $($($rest)*)?)
+    };
+    (@spec $builder:expr ;
+        servers: { $($name:tt : { $($server:tt)* }),* $(,)? } $(, $($rest:tt)*)?
+    ) => {
+        $crate::spec!(@spec $builder $(.server($name, $crate::spec!(@server $($server)*)))* ;
+            $($($rest)*)?)
+    };
+    (@spec $builder:expr ;
+        channels: { $($name:tt : { $($channel:tt)* }),* $(,)? } $(, $($rest:tt)*)?
+    ) => {
+        $crate::spec!(@spec $builder
+            $(.channel($name, |channel| $crate::spec!(@channel channel ; $($channel)*)))* ;
+            $($($rest)*)?)
+    };
+    (@spec $builder:expr ;
+        operations: { $($name:tt : {
+            action: $action:ident,
+            channel: $channel:expr
+            $(, messages: [$($message:ty),* $(,)?])?
+            $(,)?
+        }),* $(,)? } $(, $($rest:tt)*)?
+    ) => {
+        $crate::spec!(@spec $builder
+            $(.operation(
+                $name,
+                $crate::spec!(@action $action),
+                $channel,
+                |operation| operation $($(.message::<$message>())*)?,
+            ))* ;
+            $($($rest)*)?)
+    };
+    (@spec $builder:expr ; $key:ident : $($rest:tt)*) => {
+        compile_error!(concat!("unknown spec! key `", stringify!($key), "`"))
+    };
+
+    (@server $($fields:tt)*) => {{
+        let mut server = $crate::Server {
+            host: ::core::default::Default::default(),
+            protocol: ::core::default::Default::default(),
+            pathname: None,
+            description: None,
+            variables: None,
+            security: None,
+        };
+        $crate::spec!(@server_field server ; $($fields)*);
+        server
+    }};
+    (@server_field $server:ident ;) => {};
+    (@server_field $server:ident ; host: $host:expr $(, $($rest:tt)*)?) => {
+        $server.host = ::core::convert::Into::into($host);
+        $crate::spec!(@server_field $server ; $($($rest)*)?);
+    };
+    (@server_field $server:ident ; protocol: $protocol:expr $(, $($rest:tt)*)?) => {
+        $server.protocol = ::core::convert::Into::into($protocol);
+        $crate::spec!(@server_field $server ; $($($rest)*)?);
+    };
+    (@server_field $server:ident ; pathname: $pathname:expr $(, $($rest:tt)*)?) => {
+        $server.pathname = Some(::core::convert::Into::into($pathname));
+        $crate::spec!(@server_field $server ; $($($rest)*)?);
+    };
+    (@server_field $server:ident ; description: $description:expr $(, $($rest:tt)*)?) => {
+        $server.description = Some(::core::convert::Into::into($description));
+        $crate::spec!(@server_field $server ; $($($rest)*)?);
+    };
+    (@server_field $server:ident ; $key:ident : $($rest:tt)*) => {
+        compile_error!(concat!("unknown spec! server key `", stringify!($key), "`"))
+    };
+
+    (@channel $channel:expr ;) => {
+        $channel
+    };
+    (@channel $channel:expr ; address: $address:expr $(, $($rest:tt)*)?) => {
+        $crate::spec!(@channel $channel.address($address) ; $($($rest)*)?)
+    };
+    (@channel $channel:expr ; messages: [$($message:ty),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::spec!(@channel $channel $(.message::<$message>())* ; $($($rest)*)?)
+    };
+    (@channel $channel:expr ; $key:ident : $($rest:tt)*) => {
+        compile_error!(concat!("unknown spec! channel key `", stringify!($key), "`"))
+    };
+
+    (@action send) => {
+        $crate::OperationAction::Send
+    };
+    (@action receive) => {
+        $crate::OperationAction::Receive
+    };
+    (@action $action:ident) => {
+        compile_error!(concat!(
+            "unknown operation action `",
+            stringify!($action),
+            "`, expected `send` or `receive`"
+        ))
+    };
+
+    ($($body:tt)*) => {
+        $crate::spec!(@spec $crate::AsyncApiSpec::builder() ; $($body)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Schema;
+    use schemars::JsonSchema;
+    use std::collections::HashMap;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Ping {
+        id: u64,
+    }
+
+    impl ToAsyncApiMessage for Ping {
+        fn asyncapi_message_names() -> Vec<&'static str> {
+            vec!["Ping"]
+        }
+
+        fn asyncapi_messages() -> Vec<Message>
+        where
+            Self: JsonSchema,
+        {
+            vec![Message {
+                name: Some("Ping".to_string()),
+                title: None,
+                summary: None,
+                description: None,
+                content_type: Some("application/json".to_string()),
+                headers: None,
+                correlation_id: None,
+                payload: Some(Schema::from_type::<Self>()),
+                tags: None,
+                external_docs: None,
+                extensions: HashMap::new(),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_builder_dynamic_channels() {
+        let mut spec_builder = AsyncApiSpec::builder().title("Plugins").version("2.0.0");
+        for plugin in ["alpha", "beta"] {
+            spec_builder = spec_builder
+                .channel(plugin, |c| {
+                    c.address(format!("/ws/{}", plugin)).message::<Ping>()
+                })
+                .operation(
+                    format!("{}Send", plugin),
+                    OperationAction::Send,
+                    plugin,
+                    |o| o.message::<Ping>(),
+                );
+        }
+        let spec = spec_builder.build();
+
+        assert_eq!(spec.info.title, "Plugins");
+        assert_eq!(spec.info.version, "2.0.0");
+
+        let channels = spec.channels.expect("Should have channels");
+        assert_eq!(channels.len(), 2);
+        let alpha = channels.get("alpha").unwrap();
+        assert_eq!(alpha.address, Some("/ws/alpha".to_string()));
+        match alpha.messages.as_ref().unwrap().get("Ping").unwrap() {
+            MessageRef::Reference { reference } => {
+                assert_eq!(reference, "#/components/messages/Ping");
+            }
+            _ => panic!("Expected message reference"),
+        }
+
+        let operations = spec.operations.expect("Should have operations");
+        let beta_send = operations.get("betaSend").unwrap();
+        assert_eq!(beta_send.channel.reference, "#/channels/beta");
+        match &beta_send.messages.as_ref().unwrap()[0] {
+            MessageRef::Reference { reference } => {
+                assert_eq!(reference, "#/channels/beta/messages/Ping");
+            }
+            _ => panic!("Expected message reference"),
+        }
+
+        let components = spec.components.expect("Should have components");
+        let ping = components.messages.unwrap().remove("Ping").unwrap();
+        assert!(ping.payload.is_some());
+    }
+
+    #[test]
+    fn test_builder_omit_channel_message_names() {
+        let build = |builder: SpecBuilder| {
+            let spec = builder
+                .channel("chat", |c| c.message::<Ping>())
+                .operation("ping", OperationAction::Send, "chat", |o| {
+                    o.message::<Ping>()
+                })
+                .build();
+            serde_json::to_value(spec).unwrap()
+        };
+        let with_refs = build(AsyncApiSpec::builder());
+        let inlined = build(AsyncApiSpec::builder().omit_channel_message_names());
+
+        assert_eq!(
+            with_refs["channels"]["chat"]["messages"]["Ping"]["$ref"],
+            "#/components/messages/Ping"
+        );
+        let mut expected = with_refs["components"]["messages"]["Ping"].clone();
+        expected.as_object_mut().unwrap().remove("name");
+        assert_eq!(inlined["channels"]["chat"]["messages"]["Ping"], expected);
+        // Components and operation refs are the same either way
+        assert_eq!(inlined["components"], with_refs["components"]);
+        assert_eq!(inlined["operations"], with_refs["operations"]);
+    }
+
+    #[test]
+    fn test_builder_without_messages_has_no_components() {
+        let spec = AsyncApiSpec::builder()
+            .channel("events", |c| c.address("/events"))
+            .build();
+
+        assert!(spec.components.is_none());
+        assert_eq!(spec.channels.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_spec_macro_matches_builder() {
+        let from_macro = spec! {
+            title: "Plugins",
+            version: "2.0.0",
+            description: "Inline spec",
+            channels: {
+                "alpha": { address: "/ws/alpha", messages: [Ping] },
+                "beta": { address: format!("/ws/{}", "beta") },
+            },
+            operations: {
+                "alphaSend": { action: send, channel: "alpha", messages: [Ping] },
+                "betaReceive": { action: receive, channel: "beta" },
+            },
+        };
+        let from_builder = AsyncApiSpec::builder()
+            .title("Plugins")
+            .version("2.0.0")
+            .description("Inline spec")
+            .channel("alpha", |c| c.address("/ws/alpha").message::<Ping>())
+            .channel("beta", |c| c.address("/ws/beta"))
+            .operation("alphaSend", OperationAction::Send, "alpha", |o| {
+                o.message::<Ping>()
+            })
+            .operation("betaReceive", OperationAction::Receive, "beta", |o| o)
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(&from_macro).unwrap(),
+            serde_json::to_value(&from_builder).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_spec_macro_servers() {
+        let spec = spec! {
+            title: "Servers",
+            version: "1.0.0",
+            servers: {
+                "production": {
+                    host: "chat.example.com:443",
+                    protocol: "wss",
+                    pathname: "/v1",
+                    description: String::from("Production"),
+                },
+                "local": { protocol: "ws", host: "localhost:8080" },
+            }
+        };
+
+        let servers = spec.servers.unwrap();
+        let production = &servers["production"];
+        assert_eq!(production.host, "chat.example.com:443");
+        assert_eq!(production.pathname.as_deref(), Some("/v1"));
+        assert_eq!(production.description.as_deref(), Some("Production"));
+        let local = &servers["local"];
+        assert_eq!(local.protocol, "ws");
+        assert!(local.pathname.is_none());
+        assert!(spec.channels.is_none());
+    }
+
+    #[test]
+    fn test_spec_macro_empty() {
+        let spec = spec! {};
+        assert_eq!(
+            serde_json::to_value(&spec).unwrap(),
+            serde_json::to_value(AsyncApiSpec::builder().build()).unwrap()
+        );
+    }
+}
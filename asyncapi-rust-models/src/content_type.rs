@@ -0,0 +1,452 @@
+//! Per-protocol default content types
+//!
+//! AsyncAPI lets a document declare a `defaultContentType` for messages that don't
+//! set their own. What makes a sensible default depends on the transport: JSON over
+//! WebSocket, but perhaps raw bytes over MQTT or Avro over Kafka.
+//! [`ContentTypeDefaults`] maps server protocols to content types and picks one for
+//! a spec from its servers. Channels can override the document's default for the
+//! messages on them, see [`AsyncApiSpec::content_type_of`].
+//!
+//! Non-JSON content types are usually described by a schema in their own language
+//! rather than JSON Schema. [`schema_format_for`] maps such content types to the
+//! AsyncAPI `schemaFormat`, and [`Schema::multi_format`] builds the payload that
+//! carries the schema document.
+
+use crate::prelude::*;
+use crate::{AsyncApiSpec, Channel, Map, Message, Schema, SchemaObject};
+
+/// `schemaFormat` for Avro schemas in JSON
+pub const AVRO_SCHEMA_FORMAT: &str = "application/vnd.apache.avro;version=1.9.0";
+
+/// `schemaFormat` for Protocol Buffers (proto3) schemas
+pub const PROTOBUF_SCHEMA_FORMAT: &str = "application/vnd.google.protobuf;version=3";
+
+/// Get the `schemaFormat` for payloads of the given content type
+///
+/// Only content types whose schemas aren't JSON Schema are mapped; parameters such
+/// as `;charset=utf-8` are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::{AVRO_SCHEMA_FORMAT, PROTOBUF_SCHEMA_FORMAT, schema_format_for};
+///
+/// assert_eq!(schema_format_for("application/x-protobuf"), Some(PROTOBUF_SCHEMA_FORMAT));
+/// assert_eq!(schema_format_for("application/avro"), Some(AVRO_SCHEMA_FORMAT));
+/// assert_eq!(schema_format_for("application/json"), None);
+/// ```
+pub fn schema_format_for(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    match media_type.to_ascii_lowercase().as_str() {
+        "application/x-protobuf" | "application/protobuf" | "application/vnd.google.protobuf" => {
+            Some(PROTOBUF_SCHEMA_FORMAT)
+        }
+        "application/avro"
+        | "avro/binary"
+        | "application/vnd.apache.avro"
+        | "application/vnd.apache.avro+binary"
+        | "application/vnd.apache.avro+json" => Some(AVRO_SCHEMA_FORMAT),
+        _ => None,
+    }
+}
+
+/// Returns `true` if a Multi Format Schema Object's `schemaFormat` is JSON Schema
+///
+/// Covers the AsyncAPI Schema Object (`application/vnd.aai.asyncapi...`) and plain
+/// JSON Schema (`application/schema+json...`), in JSON or YAML; parameters such as
+/// `;version=draft-07` are ignored.
+#[cfg(feature = "validate-schema")]
+pub(crate) fn is_json_schema_format(schema_format: &str) -> bool {
+    let media_type = schema_format.split(';').next().unwrap_or_default().trim();
+    matches!(
+        media_type.to_ascii_lowercase().as_str(),
+        "application/vnd.aai.asyncapi"
+            | "application/vnd.aai.asyncapi+json"
+            | "application/vnd.aai.asyncapi+yaml"
+            | "application/schema+json"
+            | "application/schema+yaml"
+    )
+}
+
+/// Table of default content types by server protocol
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::ContentTypeDefaults;
+///
+/// let defaults = ContentTypeDefaults::default()
+///     .protocol("mqtt", "application/octet-stream")
+///     .protocol("kafka", "application/vnd.apache.avro+json");
+///
+/// assert_eq!(defaults.for_protocol("mqtt"), Some("application/octet-stream"));
+/// assert_eq!(defaults.for_protocol("wss"), Some("application/json"));
+/// assert_eq!(defaults.select(["mqtt", "mqtt"]), Some("application/octet-stream"));
+/// // Servers that disagree leave the choice to each message
+/// assert_eq!(defaults.select(["mqtt", "wss"]), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentTypeDefaults {
+    protocols: Map<String, String>,
+}
+
+impl Default for ContentTypeDefaults {
+    /// JSON for the WebSocket and HTTP protocols, which is what the derive macros
+    /// document messages as unless told otherwise
+    fn default() -> Self {
+        let protocols = ["ws", "wss", "http", "https"]
+            .into_iter()
+            .map(|protocol| (protocol.to_string(), "application/json".to_string()))
+            .collect();
+        Self { protocols }
+    }
+}
+
+impl ContentTypeDefaults {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self {
+            protocols: Map::new(),
+        }
+    }
+
+    /// Set the default content type for `protocol`, replacing any previous entry
+    pub fn protocol(
+        mut self,
+        protocol: impl Into<String>,
+        content_type: impl Into<String>,
+    ) -> Self {
+        self.protocols.insert(protocol.into(), content_type.into());
+        self
+    }
+
+    /// Get the default content type for `protocol`, if the table has one
+    pub fn for_protocol(&self, protocol: &str) -> Option<&str> {
+        self.protocols.get(protocol).map(String::as_str)
+    }
+
+    /// Pick the default content type for servers using the given protocols
+    ///
+    /// Returns `None` if there are no protocols, any protocol has no entry, or the
+    /// protocols map to different content types.
+    pub fn select<'a>(&self, protocols: impl IntoIterator<Item = &'a str>) -> Option<&str> {
+        let mut selected = None;
+        for protocol in protocols {
+            let content_type = self.for_protocol(protocol)?;
+            match selected {
+                None => selected = Some(content_type),
+                Some(existing) if existing != content_type => return None,
+                Some(_) => {}
+            }
+        }
+        selected
+    }
+}
+
+impl AsyncApiSpec {
+    /// Set `defaultContentType` from the protocols of the spec's servers
+    ///
+    /// Leaves an existing `default_content_type` alone, and does nothing when
+    /// [`ContentTypeDefaults::select`] can't settle on one content type. Messages
+    /// with their own `contentType` keep it.
+    pub fn apply_content_type_defaults(&mut self, defaults: &ContentTypeDefaults) {
+        if self.default_content_type.is_some() {
+            return;
+        }
+        let protocols = self
+            .servers
+            .iter()
+            .flat_map(|servers| servers.values())
+            .map(|server| server.protocol.as_str());
+        self.default_content_type = defaults.select(protocols).map(str::to_string);
+    }
+
+    /// Get the content type of `message` when sent on the channel named `channel`
+    ///
+    /// The message's own `contentType` wins, then the channel's
+    /// `default_content_type`, then the document's `defaultContentType`, and finally
+    /// `application/json`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::AsyncApiSpec;
+    ///
+    /// let spec = AsyncApiSpec::try_from(serde_json::json!({
+    ///     "asyncapi": "3.0.0",
+    ///     "info": { "title": "Sensors", "version": "1.0.0" },
+    ///     "defaultContentType": "application/octet-stream",
+    ///     "channels": { "readings": { "x-default-content-type": "application/cbor" } },
+    ///     "components": { "messages": { "reading": {} } }
+    /// }))
+    /// .unwrap();
+    ///
+    /// let reading = spec.find_message("reading").unwrap();
+    /// assert_eq!(spec.content_type_of("readings", reading), "application/cbor");
+    /// assert_eq!(spec.content_type_of("alerts", reading), "application/octet-stream");
+    /// ```
+    pub fn content_type_of<'a>(&'a self, channel: &str, message: &'a Message) -> &'a str {
+        let channel_default = self
+            .channels
+            .as_ref()
+            .and_then(|channels| channels.get(channel))
+            .and_then(|channel| channel.default_content_type.as_deref());
+        message
+            .content_type
+            .as_deref()
+            .or(channel_default)
+            .or(self.default_content_type.as_deref())
+            .unwrap_or("application/json")
+    }
+
+    /// Get the content type of the message named `name` in `components.messages`
+    ///
+    /// Like [`content_type_of`](Self::content_type_of) on the first channel, by
+    /// name, that references the message; the document's default applies to
+    /// messages on no channel. Unlike the derive's `asyncapi_content_type_for()`,
+    /// this follows the channel and document defaults. Returns `None` if there is
+    /// no such message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::AsyncApiSpec;
+    ///
+    /// let spec = AsyncApiSpec::try_from(serde_json::json!({
+    ///     "asyncapi": "3.0.0",
+    ///     "info": { "title": "Sensors", "version": "1.0.0" },
+    ///     "channels": {
+    ///         "readings": {
+    ///             "x-default-content-type": "application/cbor",
+    ///             "messages": { "reading": { "$ref": "#/components/messages/reading" } }
+    ///         }
+    ///     },
+    ///     "components": { "messages": { "reading": {}, "status": {} } }
+    /// }))
+    /// .unwrap();
+    ///
+    /// assert_eq!(spec.message_content_type("reading"), Some("application/cbor"));
+    /// assert_eq!(spec.message_content_type("status"), Some("application/json"));
+    /// assert_eq!(spec.message_content_type("missing"), None);
+    /// ```
+    pub fn message_content_type(&self, name: &str) -> Option<&str> {
+        let message = self.find_message(name)?;
+        let content_type = match self.channels_using_message(name).first() {
+            Some((channel, _)) => self.content_type_of(channel, message),
+            None => message
+                .content_type
+                .as_deref()
+                .or(self.default_content_type.as_deref())
+                .unwrap_or("application/json"),
+        };
+        Some(content_type)
+    }
+
+    /// Get the channels referencing the component message `name`, sorted by name
+    pub(crate) fn channels_using_message(&self, name: &str) -> Vec<(&String, &Channel)> {
+        let reference = format!("#/components/messages/{}", name);
+        self.channels_sorted()
+            .into_iter()
+            .filter(|(_, channel)| {
+                channel.messages.iter().flatten().any(|(_, message)| {
+                    message
+                        .as_reference()
+                        .is_some_and(|r| r == reference.as_str())
+                })
+            })
+            .collect()
+    }
+}
+
+impl Schema {
+    /// Create a Multi Format Schema Object payload: `schema` in `schema_format`
+    ///
+    /// Avro schemas are JSON, so pass them as parsed values; a `.proto` file is
+    /// passed as a string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::{PROTOBUF_SCHEMA_FORMAT, Schema};
+    ///
+    /// let payload = Schema::multi_format(
+    ///     PROTOBUF_SCHEMA_FORMAT,
+    ///     "syntax = \"proto3\"; message Ping { int64 ts = 1; }".into(),
+    /// );
+    /// assert_eq!(payload.schema_format(), Some(PROTOBUF_SCHEMA_FORMAT));
+    /// ```
+    pub fn multi_format(schema_format: impl Into<String>, schema: serde_json::Value) -> Self {
+        let additional = Map::from([
+            (
+                "schemaFormat".to_string(),
+                serde_json::Value::String(schema_format.into()),
+            ),
+            ("schema".to_string(), schema),
+        ]);
+        Schema::Object(Box::new(SchemaObject {
+            additional,
+            ..Default::default()
+        }))
+    }
+
+    /// Get the `schemaFormat` of a Multi Format Schema Object
+    ///
+    /// Returns `None` for plain JSON schemas.
+    pub fn schema_format(&self) -> Option<&str> {
+        match self {
+            Schema::Object(object) => object.additional.get("schemaFormat")?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Server;
+
+    fn server(protocol: &str) -> Server {
+        Server {
+            host: "broker.example.com".to_string(),
+            protocol: protocol.to_string(),
+            pathname: None,
+            description: None,
+            variables: None,
+            security: None,
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let defaults = ContentTypeDefaults::new()
+            .protocol("mqtt", "application/octet-stream")
+            .protocol("mqtt5", "application/octet-stream")
+            .protocol("kafka", "application/vnd.apache.avro+json");
+
+        assert_eq!(
+            defaults.select(["mqtt", "mqtt5"]),
+            Some("application/octet-stream")
+        );
+        assert_eq!(
+            defaults.select(["kafka"]),
+            Some("application/vnd.apache.avro+json")
+        );
+        assert_eq!(defaults.select(["mqtt", "kafka"]), None);
+        // Unknown protocols have no opinion, so nothing is chosen
+        assert_eq!(defaults.select(["mqtt", "amqp"]), None);
+        assert_eq!(defaults.select([]), None);
+
+        // Later entries replace earlier ones
+        let defaults = defaults.protocol("kafka", "application/json");
+        assert_eq!(defaults.for_protocol("kafka"), Some("application/json"));
+    }
+
+    #[test]
+    fn test_apply_content_type_defaults() {
+        let defaults = ContentTypeDefaults::default().protocol("mqtt", "application/octet-stream");
+
+        let mut spec = AsyncApiSpec::default()
+            .with_server("broker", server("mqtt"))
+            .with_server("backup", server("mqtt"));
+        spec.apply_content_type_defaults(&defaults);
+        assert_eq!(
+            spec.default_content_type.as_deref(),
+            Some("application/octet-stream")
+        );
+        let json = serde_json::to_value(&spec).unwrap();
+        assert_eq!(json["defaultContentType"], "application/octet-stream");
+
+        // Mixed protocols leave it unset
+        let mut spec = spec.with_server("web", server("wss"));
+        spec.default_content_type = None;
+        spec.apply_content_type_defaults(&defaults);
+        assert!(spec.default_content_type.is_none());
+
+        // An explicit default wins
+        spec.default_content_type = Some("text/plain".to_string());
+        spec.apply_content_type_defaults(&ContentTypeDefaults::default());
+        assert_eq!(spec.default_content_type.as_deref(), Some("text/plain"));
+
+        // No servers, no default
+        let mut spec = AsyncApiSpec::default();
+        spec.apply_content_type_defaults(&defaults);
+        assert!(spec.default_content_type.is_none());
+    }
+
+    #[test]
+    fn test_content_type_of_precedence() {
+        let mut spec = AsyncApiSpec::default().with_channel(
+            "readings",
+            crate::Channel {
+                default_content_type: Some("application/cbor".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut message: Message = serde_json::from_value(serde_json::json!({})).unwrap();
+
+        assert_eq!(
+            spec.content_type_of("readings", &message),
+            "application/cbor"
+        );
+        assert_eq!(spec.content_type_of("alerts", &message), "application/json");
+
+        spec.default_content_type = Some("application/octet-stream".to_string());
+        assert_eq!(
+            spec.content_type_of("readings", &message),
+            "application/cbor"
+        );
+        assert_eq!(
+            spec.content_type_of("alerts", &message),
+            "application/octet-stream"
+        );
+
+        message.content_type = Some("text/plain".to_string());
+        assert_eq!(spec.content_type_of("readings", &message), "text/plain");
+        assert_eq!(spec.content_type_of("alerts", &message), "text/plain");
+    }
+
+    #[test]
+    fn test_schema_format_for() {
+        for content_type in [
+            "application/x-protobuf",
+            "application/protobuf",
+            "application/vnd.google.protobuf; proto=chat.Message",
+        ] {
+            assert_eq!(
+                schema_format_for(content_type),
+                Some(PROTOBUF_SCHEMA_FORMAT),
+                "{}",
+                content_type
+            );
+        }
+        for content_type in ["application/avro", "avro/binary", "Application/Avro"] {
+            assert_eq!(
+                schema_format_for(content_type),
+                Some(AVRO_SCHEMA_FORMAT),
+                "{}",
+                content_type
+            );
+        }
+        assert_eq!(schema_format_for("application/json"), None);
+        assert_eq!(schema_format_for("application/octet-stream"), None);
+    }
+
+    #[test]
+    fn test_multi_format_payload() {
+        let avro = serde_json::json!({
+            "type": "record",
+            "name": "Ping",
+            "fields": [{"name": "ts", "type": "long"}]
+        });
+        let payload = Schema::multi_format(AVRO_SCHEMA_FORMAT, avro.clone());
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            serde_json::json!({"schemaFormat": AVRO_SCHEMA_FORMAT, "schema": avro})
+        );
+
+        let round_trip: Schema =
+            serde_json::from_value(serde_json::to_value(&payload).unwrap()).unwrap();
+        assert_eq!(round_trip.schema_format(), Some(AVRO_SCHEMA_FORMAT));
+        assert_eq!(Schema::Bool(true).schema_format(), None);
+    }
+}
@@ -0,0 +1,271 @@
+//! Conversion between JSON Schema drafts
+//!
+//! AsyncAPI 3.0 payloads default to JSON Schema draft 2020-12, which is also what
+//! schemars 1.x emits. Some validators only understand draft-07, so
+//! [`Schema::to_json_schema_draft`] rewrites the keywords that differ between the
+//! two. The conversion is best effort: keywords without an equivalent are left as-is.
+
+use crate::Schema;
+use crate::prelude::*;
+use serde_json::{Map, Value};
+
+/// JSON Schema draft to convert a [`Schema`] to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonSchemaDraft {
+    /// Draft-07: `definitions`, array-form `items` with `additionalItems`, `dependencies`
+    Draft07,
+    /// Draft 2020-12: `$defs`, `prefixItems`, `dependentRequired`/`dependentSchemas`
+    #[default]
+    Draft2020_12,
+}
+
+impl Schema {
+    /// Rewrite this schema for the given JSON Schema draft
+    ///
+    /// Handles `$defs`/`definitions` (including `$ref` paths into them), tuple
+    /// `items`/`prefixItems`, and `dependencies`. OpenAPI-style `example` and
+    /// `nullable: true` keywords, which no JSON Schema draft defines, are turned into
+    /// `examples` and a `"null"` type respectively.
+    pub fn to_json_schema_draft(&self, draft: JsonSchemaDraft) -> Schema {
+        // Every Schema serializes to an object or boolean, both of which deserialize
+        let mut value = serde_json::to_value(self).expect("Schema always serializes to JSON");
+        rewrite(&mut value, draft);
+        serde_json::from_value(value).expect("rewritten schema is still an object or boolean")
+    }
+}
+
+/// Keywords whose value is a map from names to subschemas
+const SCHEMA_MAPS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+];
+
+/// Keywords whose value is data rather than a schema
+const DATA_KEYWORDS: &[&str] = &[
+    "enum",
+    "const",
+    "default",
+    "examples",
+    "required",
+    "dependentRequired",
+];
+
+fn rewrite(value: &mut Value, draft: JsonSchemaDraft) {
+    let Value::Object(object) = value else {
+        return;
+    };
+    rewrite_keywords(object, draft);
+
+    for (key, child) in object.iter_mut() {
+        if SCHEMA_MAPS.contains(&key.as_str()) {
+            if let Value::Object(schemas) = child {
+                schemas
+                    .values_mut()
+                    .for_each(|schema| rewrite(schema, draft));
+            }
+        } else if key == "dependencies" {
+            // Array values are property lists, everything else is a schema
+            if let Value::Object(dependencies) = child {
+                for dependency in dependencies.values_mut() {
+                    rewrite(dependency, draft);
+                }
+            }
+        } else if key == "$ref" {
+            if let Value::String(reference) = child {
+                let (from, to) = match draft {
+                    JsonSchemaDraft::Draft07 => ("#/$defs/", "#/definitions/"),
+                    JsonSchemaDraft::Draft2020_12 => ("#/definitions/", "#/$defs/"),
+                };
+                if let Some(rest) = reference.strip_prefix(from) {
+                    *reference = format!("{}{}", to, rest);
+                }
+            }
+        } else if !DATA_KEYWORDS.contains(&key.as_str()) {
+            match child {
+                Value::Array(items) => items.iter_mut().for_each(|item| rewrite(item, draft)),
+                _ => rewrite(child, draft),
+            }
+        }
+    }
+}
+
+/// Rewrite the keywords of a single schema object (not its subschemas)
+fn rewrite_keywords(object: &mut Map<String, Value>, draft: JsonSchemaDraft) {
+    if let Some(example) = object.remove("example") {
+        object
+            .entry("examples")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Some(Value::Array(examples)) = object.get_mut("examples") {
+            examples.push(example);
+        }
+    }
+    if object.get("nullable") == Some(&Value::Bool(true)) {
+        object.remove("nullable");
+        match object.get_mut("type") {
+            Some(Value::String(schema_type)) => {
+                let schema_type = core::mem::take(schema_type);
+                object.insert("type".to_string(), serde_json::json!([schema_type, "null"]));
+            }
+            Some(Value::Array(types)) if !types.iter().any(|t| t == "null") => {
+                types.push(Value::from("null"));
+            }
+            _ => {}
+        }
+    }
+
+    match draft {
+        JsonSchemaDraft::Draft07 => {
+            move_keyword(object, "$defs", "definitions");
+            if let Some(prefix_items) = object.remove("prefixItems") {
+                if let Some(items) = object.remove("items") {
+                    object.insert("additionalItems".to_string(), items);
+                }
+                object.insert("items".to_string(), prefix_items);
+            }
+            for keyword in ["dependentRequired", "dependentSchemas"] {
+                if let Some(Value::Object(dependencies)) = object.remove(keyword) {
+                    let merged = object
+                        .entry("dependencies")
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Value::Object(merged) = merged {
+                        merged.extend(dependencies);
+                    }
+                }
+            }
+        }
+        JsonSchemaDraft::Draft2020_12 => {
+            move_keyword(object, "definitions", "$defs");
+            if matches!(object.get("items"), Some(Value::Array(_))) {
+                if let Some(items) = object.remove("items") {
+                    object.insert("prefixItems".to_string(), items);
+                }
+                if let Some(additional_items) = object.remove("additionalItems") {
+                    object.insert("items".to_string(), additional_items);
+                }
+            }
+            if let Some(Value::Object(dependencies)) = object.remove("dependencies") {
+                let (required, schemas): (Map<_, _>, Map<_, _>) = dependencies
+                    .into_iter()
+                    .partition(|(_, dependency)| dependency.is_array());
+                if !required.is_empty() {
+                    object.insert("dependentRequired".to_string(), Value::Object(required));
+                }
+                if !schemas.is_empty() {
+                    object.insert("dependentSchemas".to_string(), Value::Object(schemas));
+                }
+            }
+        }
+    }
+}
+
+/// Move `from` to `to`, merging into any existing `to` map
+fn move_keyword(object: &mut Map<String, Value>, from: &str, to: &str) {
+    let Some(moved) = object.remove(from) else {
+        return;
+    };
+    match (object.get_mut(to), moved) {
+        (Some(Value::Object(existing)), Value::Object(moved)) => {
+            for (name, schema) in moved {
+                existing.entry(name).or_insert(schema);
+            }
+        }
+        (_, moved) => {
+            object.insert(to.to_string(), moved);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn convert(value: Value, draft: JsonSchemaDraft) -> Value {
+        let schema = Schema::from_json(value).unwrap();
+        serde_json::to_value(schema.to_json_schema_draft(draft)).unwrap()
+    }
+
+    #[test]
+    fn test_defs_to_definitions_and_back() {
+        let draft_2020 = json!({
+            "type": "object",
+            "properties": {
+                "user": { "$ref": "#/$defs/User" },
+                // A property that happens to be called `$defs` keeps its name
+                "$defs": { "type": "string" }
+            },
+            "$defs": {
+                "User": {
+                    "type": "object",
+                    "properties": { "manager": { "$ref": "#/$defs/User" } }
+                }
+            }
+        });
+        let draft_07 = json!({
+            "type": "object",
+            "properties": {
+                "user": { "$ref": "#/definitions/User" },
+                "$defs": { "type": "string" }
+            },
+            "definitions": {
+                "User": {
+                    "type": "object",
+                    "properties": { "manager": { "$ref": "#/definitions/User" } }
+                }
+            }
+        });
+
+        assert_eq!(
+            convert(draft_2020.clone(), JsonSchemaDraft::Draft07),
+            draft_07
+        );
+        assert_eq!(convert(draft_07, JsonSchemaDraft::Draft2020_12), draft_2020);
+    }
+
+    #[test]
+    fn test_tuple_items_and_dependencies() {
+        let draft_2020 = json!({
+            "type": "array",
+            "prefixItems": [{ "type": "string" }, { "type": "integer" }],
+            "items": false,
+            "dependentRequired": { "billing": ["address"] },
+            "dependentSchemas": { "card": { "required": ["cvv"] } }
+        });
+        let draft_07 = json!({
+            "type": "array",
+            "items": [{ "type": "string" }, { "type": "integer" }],
+            "additionalItems": false,
+            "dependencies": {
+                "billing": ["address"],
+                "card": { "required": ["cvv"] }
+            }
+        });
+
+        assert_eq!(
+            convert(draft_2020.clone(), JsonSchemaDraft::Draft07),
+            draft_07
+        );
+        assert_eq!(convert(draft_07, JsonSchemaDraft::Draft2020_12), draft_2020);
+    }
+
+    #[test]
+    fn test_openapi_keywords() {
+        let schema = json!({
+            "type": "string",
+            "nullable": true,
+            "example": "hello",
+            "enum": [{ "nullable": true }]
+        });
+        assert_eq!(
+            convert(schema, JsonSchemaDraft::Draft07),
+            json!({
+                "type": ["string", "null"],
+                "examples": ["hello"],
+                "enum": [{ "nullable": true }]
+            })
+        );
+    }
+}
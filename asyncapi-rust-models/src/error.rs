@@ -0,0 +1,59 @@
+//! Error type for fallible AsyncAPI operations
+
+use crate::prelude::*;
+
+/// Error returned by fallible AsyncAPI model operations
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum AsyncApiError {
+    /// A JSON schema nests deeper than the allowed maximum
+    ///
+    /// Guards recursive conversions against stack overflows on pathological schemas.
+    SchemaTooDeep {
+        /// Maximum nesting depth that was allowed
+        max_depth: usize,
+    },
+    /// A JSON value could not be converted into a [`Schema`](crate::Schema)
+    InvalidSchema(String),
+    /// A merged component has the same name as a different existing one
+    ///
+    /// Returned under [`MergePolicy::Error`](crate::MergePolicy::Error).
+    ComponentConflict {
+        /// Components section, as named in the document (e.g. `"schemas"`)
+        section: &'static str,
+        /// Name of the component
+        name: String,
+    },
+    /// A `$ref` points at a definition that isn't in the document
+    ///
+    /// Returned by [`AsyncApiSpec::to_bundle`](crate::AsyncApiSpec::to_bundle).
+    UnresolvedRef {
+        /// The unresolved `$ref`
+        reference: String,
+    },
+}
+
+impl fmt::Display for AsyncApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsyncApiError::SchemaTooDeep { max_depth } => {
+                write!(f, "schema exceeds maximum depth of {}", max_depth)
+            }
+            AsyncApiError::InvalidSchema(message) => write!(f, "invalid schema: {}", message),
+            AsyncApiError::ComponentConflict { section, name } => write!(
+                f,
+                "component '{}' in '{}' conflicts with an existing definition",
+                name, section
+            ),
+            AsyncApiError::UnresolvedRef { reference } => {
+                write!(
+                    f,
+                    "reference '{}' does not resolve within the document",
+                    reference
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for AsyncApiError {}
@@ -16,12 +16,28 @@
 //! - [`Message`] - Message definitions
 //! - [`Schema`] - JSON Schema definitions
 //! - [`Components`] - Reusable components
+//! - [`SpecBuilder`] - Runtime builder for dynamically registered APIs
+//! - [`spec!`] - Inline spec construction on top of [`SpecBuilder`]
 //!
 //! ## Serialization
 //!
 //! All types implement [`serde::Serialize`] and [`serde::Deserialize`] for JSON
 //! serialization, following the AsyncAPI 3.0 specification's JSON Schema.
 //!
+//! ## Features
+//!
+//! - `std` (default) - Uses `HashMap` for the spec's maps and adds the schemars
+//!   integration (`Schema::from_type`, `ToAsyncApiMessage::asyncapi_messages`,
+//!   `ChannelBuilder::message`). Without it the crate is `#![no_std]` and only needs
+//!   `alloc`; [`Map`] is a `BTreeMap` instead, and [`IndexMap`] uses foldhash's
+//!   randomly seeded hasher
+//! - `validate-schema` - Adds `AsyncApiSpec::validate_schema()`, which checks the
+//!   serialized document against the AsyncAPI 3.0 JSON Schema using `jsonschema`, and
+//!   `Message::payload_validator()` / `Message::validate_payload()` for checking runtime
+//!   payloads against a message's schema
+//! - `yaml` - Adds `AsyncApiSpec::to_yaml()` and `AsyncApiSpec::from_yaml()` using
+//!   `serde_yaml` 0.9, which is deprecated upstream and no longer maintained
+//!
 //! ## Example
 //!
 //! ```rust
@@ -35,8 +51,13 @@
 //!         title: "My API".to_string(),
 //!         version: "1.0.0".to_string(),
 //!         description: Some("A simple API".to_string()),
+//!         summary: None,
+//!         contact: None,
+//!         license: None,
+//!         tags: None,
 //!     },
 //!     servers: None,
+//!     default_content_type: None,
 //!     channels: None,
 //!     operations: None,
 //!     components: None,
@@ -46,11 +67,221 @@
 //! let json = serde_json::to_string_pretty(&spec).unwrap();
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![warn(clippy::all)]
 
+extern crate alloc;
+
+use prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+/// Map type of the spec's maps: `HashMap` with `std`, `BTreeMap` without
+///
+/// Serialization writes the keys in sorted order either way, so output is stable
+/// across runs.
+#[cfg(feature = "std")]
+pub type Map<K, V> = std::collections::HashMap<K, V>;
+/// Map type of the spec's maps: `HashMap` with `std`, `BTreeMap` without
+///
+/// Serialization writes the keys in sorted order either way, so output is stable
+/// across runs.
+#[cfg(not(feature = "std"))]
+pub type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+
+#[cfg(feature = "std")]
+pub use indexmap::IndexMap;
+/// [`indexmap::IndexMap`] keyed with foldhash's randomly seeded hasher, as `std`'s
+/// `RandomState` isn't available
+///
+/// Construct it with `IndexMap::default()`; `IndexMap::new()` needs `std`.
+#[cfg(not(feature = "std"))]
+pub type IndexMap<K, V> = indexmap::IndexMap<K, V, foldhash::fast::RandomState>;
+
+mod builder;
+mod content_type;
+mod draft;
+mod error;
+mod merge;
+mod prelude;
+mod sorted;
+mod validate;
+
+/// Protocol values recognized by the AsyncAPI 3.0 specification and its bindings
+pub const KNOWN_PROTOCOLS: &[&str] = &[
+    "amqp",
+    "amqps",
+    "anypointmq",
+    "googlepubsub",
+    "http",
+    "https",
+    "ibmmq",
+    "jms",
+    "kafka",
+    "kafka-secure",
+    "mercure",
+    "mqtt",
+    "mqtt5",
+    "nats",
+    "pulsar",
+    "redis",
+    "secure-mqtt",
+    "sns",
+    "solace",
+    "sqs",
+    "stomp",
+    "stomps",
+    "ws",
+    "wss",
+];
+
+/// Returns `true` if `protocol` is one of the [`KNOWN_PROTOCOLS`]
+pub fn is_known_protocol(protocol: &str) -> bool {
+    KNOWN_PROTOCOLS.contains(&protocol)
+}
+
+/// Get the port a protocol uses when a server host doesn't specify one
+pub fn default_port(protocol: &str) -> Option<u16> {
+    match protocol {
+        "ws" | "http" => Some(80),
+        "wss" | "https" => Some(443),
+        "amqp" => Some(5672),
+        "amqps" => Some(5671),
+        "kafka" | "kafka-secure" => Some(9092),
+        "mqtt" | "mqtt5" => Some(1883),
+        "secure-mqtt" => Some(8883),
+        "nats" => Some(4222),
+        "redis" => Some(6379),
+        "stomp" | "stomps" => Some(61613),
+        _ => None,
+    }
+}
+
+/// Check that a server host is a bare authority (`host` or `host:port`)
+///
+/// Catches URLs pasted into `host` (e.g. `http://example.com` for a `wss` server),
+/// paths that belong in `pathname`, and non-numeric ports. Returns a message
+/// describing the first problem found.
+pub fn check_server_host(host: &str, protocol: &str) -> Result<(), String> {
+    if host.is_empty() {
+        return Err("host is empty".to_string());
+    }
+    if let Some((scheme, _)) = host.split_once("://") {
+        return Err(if scheme == protocol {
+            format!(
+                "host '{}' includes the scheme '{}://'; use a bare authority (host:port)",
+                host, scheme
+            )
+        } else {
+            format!(
+                "host '{}' includes the scheme '{}://', which conflicts with protocol '{}'; use a bare authority (host:port)",
+                host, scheme, protocol
+            )
+        });
+    }
+    if host.contains('/') {
+        return Err(format!(
+            "host '{}' includes a path; move it to pathname",
+            host
+        ));
+    }
+    // Skip the colons inside a bracketed IPv6 address
+    let port = match host.rsplit_once(']') {
+        Some((_, rest)) => rest.strip_prefix(':'),
+        None => host.rsplit_once(':').map(|(_, port)| port),
+    };
+    // A `{variable}` port is substituted from the server's variables
+    if let Some(port) = port.filter(|port| !port.starts_with('{')) {
+        if port.parse::<u16>().is_err() {
+            return Err(format!("host '{}' has an invalid port '{}'", host, port));
+        }
+    }
+    Ok(())
+}
+
+/// Get the distinct `{name}` placeholders in `template`, in order of appearance
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start + 1..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+        rest = &rest[start + 2 + len..];
+    }
+    names
+}
+
+pub use builder::{ChannelBuilder, OperationBuilder, SpecBuilder};
+pub use content_type::{
+    AVRO_SCHEMA_FORMAT, ContentTypeDefaults, PROTOBUF_SCHEMA_FORMAT, schema_format_for,
+};
+pub use draft::JsonSchemaDraft;
+pub use error::AsyncApiError;
+pub use merge::MergePolicy;
+pub use validate::{Severity, ValidationIssue};
+
+/// Types that describe one or more AsyncAPI messages
+///
+/// Implemented by `#[derive(ToAsyncApiMessage)]`. The derive also generates
+/// inherent methods with the same names, so the trait only needs to be in scope
+/// for generic code such as [`ChannelBuilder::message`].
+pub trait ToAsyncApiMessage {
+    /// Get AsyncAPI message names for this type
+    fn asyncapi_message_names() -> Vec<&'static str>;
+
+    /// Get the names of messages used by operations with the given action
+    ///
+    /// `action` is `"send"` or `"receive"`. The default returns every name; the derive
+    /// leaves out messages marked `#[asyncapi(direction = "...")]` with the other one.
+    fn asyncapi_message_names_for(_action: &str) -> Vec<&'static str> {
+        Self::asyncapi_message_names()
+    }
+
+    /// Get the documented content type of the message with the given name
+    ///
+    /// Useful for choosing between text and binary frames at runtime. The default
+    /// returns `None`; the derive generates a lookup over its message names.
+    ///
+    /// Only the message type is consulted, so messages without their own content type
+    /// are `application/json` here even when a spec gives them a channel's or the
+    /// document's default; use [`AsyncApiSpec::message_content_type`] for those.
+    fn asyncapi_content_type_for(_name: &str) -> Option<&'static str> {
+        None
+    }
+
+    /// Get the content type the message with the given name declares itself
+    ///
+    /// Unlike [`asyncapi_content_type_for`](Self::asyncapi_content_type_for), this is
+    /// `None` for messages documented as `application/json` only by default, which
+    /// the `AsyncApi` derive then gives their channel's or the document's default. The
+    /// default treats every content type as declared.
+    fn asyncapi_declared_content_type_for(name: &str) -> Option<&'static str> {
+        Self::asyncapi_content_type_for(name)
+    }
+
+    /// Generate AsyncAPI Message objects with JSON schemas
+    #[cfg(feature = "std")]
+    fn asyncapi_messages() -> Vec<Message>
+    where
+        Self: schemars::JsonSchema;
+
+    /// Iterate over AsyncAPI Message objects without collecting them
+    ///
+    /// The default collects [`asyncapi_messages`](Self::asyncapi_messages); the
+    /// derive builds each message lazily instead.
+    #[cfg(feature = "std")]
+    fn asyncapi_messages_iter() -> impl Iterator<Item = Message>
+    where
+        Self: schemars::JsonSchema,
+    {
+        Self::asyncapi_messages().into_iter()
+    }
+}
 
 /// AsyncAPI 3.0 Specification
 ///
@@ -70,14 +301,19 @@ use std::collections::HashMap;
 ///         title: "My WebSocket API".to_string(),
 ///         version: "1.0.0".to_string(),
 ///         description: Some("Real-time messaging API".to_string()),
+///         summary: None,
+///         contact: None,
+///         license: None,
+///         tags: None,
 ///     },
 ///     servers: None,
+///     default_content_type: None,
 ///     channels: None,
 ///     operations: None,
 ///     components: None,
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AsyncApiSpec {
     /// AsyncAPI version (e.g., "3.0.0")
     pub asyncapi: String,
@@ -86,16 +322,28 @@ pub struct AsyncApiSpec {
     pub info: Info,
 
     /// Server connection details
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub servers: Option<HashMap<String, Server>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub servers: Option<Map<String, Server>>,
+
+    /// Content type of messages that don't set their own `contentType`
+    ///
+    /// See [`ContentTypeDefaults`] for picking one from the server protocols.
+    #[serde(rename = "defaultContentType", skip_serializing_if = "Option::is_none")]
+    pub default_content_type: Option<String>,
 
     /// Available channels (communication paths)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub channels: Option<HashMap<String, Channel>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub channels: Option<Map<String, Channel>>,
 
     /// Operations (send/receive)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub operations: Option<HashMap<String, Operation>>,
+    pub operations: Option<IndexMap<String, Operation>>,
 
     /// Reusable components (messages, schemas, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -107,7 +355,7 @@ pub struct AsyncApiSpec {
 /// Contains general metadata about the API such as title, version, and description.
 /// This information is displayed in documentation tools and helps users understand
 /// the purpose and version of the API.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Info {
     /// API title
     ///
@@ -124,6 +372,132 @@ pub struct Info {
     /// A longer description of the API's purpose and functionality (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Short one-line summary of the API (optional)
+    ///
+    /// AsyncAPI 3.0 has no `summary` field on Info, so this is emitted as the
+    /// `x-summary` extension.
+    #[serde(rename = "x-summary", skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    /// Contact information for the exposed API (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+
+    /// License information for the exposed API (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<License>,
+
+    /// API tags
+    ///
+    /// Tags for logical grouping and categorization of the API (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+}
+
+/// Contact information for the exposed API
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::Contact;
+///
+/// let contact = Contact {
+///     name: Some("API Support".to_string()),
+///     url: Some("https://example.com/support".to_string()),
+///     email: Some("support@example.com".to_string()),
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    /// Identifying name of the contact person or organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// URL pointing to the contact information
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Email address of the contact person or organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// License information for the exposed API
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::License;
+///
+/// let license = License {
+///     name: "Apache 2.0".to_string(),
+///     url: Some("https://www.apache.org/licenses/LICENSE-2.0".to_string()),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct License {
+    /// License name used for the API
+    pub name: String,
+
+    /// URL to the license used for the API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Tag for grouping and categorizing API elements
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::{ExternalDocumentation, Tag};
+///
+/// let tag = Tag {
+///     name: "chat".to_string(),
+///     description: Some("Chat operations".to_string()),
+///     external_docs: Some(ExternalDocumentation {
+///         description: None,
+///         url: "https://example.com/docs/chat".to_string(),
+///     }),
+/// };
+/// ```
+///
+/// AsyncAPI 3.0 only allows document-level tags inside `info`, so there is no
+/// root `tags` field on [`AsyncApiSpec`]; use [`Info::tags`] instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tag {
+    /// Tag name
+    pub name: String,
+
+    /// Tag description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Additional external documentation for this tag
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+}
+
+/// Reference to external documentation
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::ExternalDocumentation;
+///
+/// let docs = ExternalDocumentation {
+///     description: Some("Find more info here".to_string()),
+///     url: "https://example.com/docs".to_string(),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalDocumentation {
+    /// Short description of the target documentation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// URL of the target documentation
+    pub url: String,
 }
 
 /// Server connection information
@@ -151,9 +525,10 @@ pub struct Info {
 ///     pathname: Some("/api/ws/{userId}".to_string()),
 ///     description: Some("Production WebSocket server".to_string()),
 ///     variables: Some(variables),
+///     security: None,
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Server {
     /// Server URL or host
     ///
@@ -161,10 +536,11 @@ pub struct Server {
     /// Examples: "localhost:8080", "api.example.com", "ws.example.com:443"
     pub host: String,
 
-    /// Protocol (e.g., "wss", "ws", "grpc")
+    /// Protocol (e.g., "wss", "ws", "kafka")
     ///
     /// The protocol used to communicate with the server.
-    /// Common values: "ws" (WebSocket), "wss" (WebSocket Secure), "grpc", "mqtt"
+    /// Common values: "ws" (WebSocket), "wss" (WebSocket Secure), "kafka", "mqtt".
+    /// See [`KNOWN_PROTOCOLS`] for the values AsyncAPI recognizes.
     pub protocol: String,
 
     /// Optional pathname for the server URL
@@ -182,8 +558,15 @@ pub struct Server {
     /// Server variables
     ///
     /// A map of variable name to ServerVariable definition for variables used in the pathname
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub variables: Option<Map<String, ServerVariable>>,
+
+    /// Security schemes that can be used with this server (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub variables: Option<HashMap<String, ServerVariable>>,
+    pub security: Option<Vec<SecuritySchemeRef>>,
 }
 
 /// Server variable definition
@@ -203,7 +586,7 @@ pub struct Server {
 ///     examples: Some(vec!["12".to_string(), "13".to_string()]),
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServerVariable {
     /// Variable description
     ///
@@ -225,11 +608,31 @@ pub struct ServerVariable {
 
     /// Example values
     ///
-    /// A list of example values for documentation purposes
+    /// A list of example values for documentation purposes. Server variables are
+    /// always substituted as strings, so examples are strings too. Typed examples
+    /// belong in a [`Parameter`] schema's `examples` instead.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub examples: Option<Vec<String>>,
 }
 
+/// Mismatch between a server's `{variable}` placeholders and its `variables`
+///
+/// Returned by [`Server::check_variables`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariableCheck {
+    /// Variables declared in `variables` but not used in `host` or `pathname`, sorted
+    pub unused: Vec<String>,
+    /// Variables used in `pathname` but not declared, in order of appearance
+    pub undefined: Vec<String>,
+}
+
+impl VariableCheck {
+    /// Whether the placeholders and declared variables agree
+    pub fn is_empty(&self) -> bool {
+        self.unused.is_empty() && self.undefined.is_empty()
+    }
+}
+
 /// Communication channel
 ///
 /// Represents a communication path through which messages are exchanged.
@@ -247,28 +650,24 @@ pub struct ServerVariable {
 ///     description: Some("User ID for this WebSocket connection".to_string()),
 ///     schema: Some(Schema::Object(Box::new(SchemaObject {
 ///         schema_type: Some(serde_json::json!("integer")),
-///         properties: None,
-///         required: None,
-///         description: None,
-///         title: None,
-///         enum_values: None,
-///         const_value: None,
-///         items: None,
-///         additional_properties: None,
-///         one_of: None,
-///         any_of: None,
-///         all_of: None,
-///         additional: HashMap::new(),
+///         ..SchemaObject::default()
 ///     }))),
+///     ..Parameter::default()
 /// });
 ///
 /// let channel = Channel {
 ///     address: Some("/ws/chat/{userId}".to_string()),
+///     title: Some("User Chat".to_string()),
 ///     messages: None,
 ///     parameters: Some(parameters),
+///     tags: None,
+///     external_docs: None,
+///     bindings: None,
+///     deprecated: None,
+///     default_content_type: None,
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct Channel {
     /// Channel address/path
     ///
@@ -278,18 +677,66 @@ pub struct Channel {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
 
+    /// Channel title
+    ///
+    /// A human-readable title for the channel. Renderers fall back to the channel's
+    /// key when absent; see [`Channel::display_title`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
     /// Messages available on this channel
     ///
     /// A map of message identifiers to message definitions or references.
     /// Messages define the structure of data that flows through this channel.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub messages: Option<HashMap<String, MessageRef>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub messages: Option<Map<String, MessageRef>>,
 
     /// Channel parameters
     ///
     /// A map of parameter names to their schema definitions for variables used in the address
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub parameters: Option<Map<String, Parameter>>,
+
+    /// Channel tags
+    ///
+    /// Tags for logical grouping of channels (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+
+    /// Additional external documentation for this channel (optional)
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+
+    /// Protocol-specific channel bindings
+    ///
+    /// Usually a reference to `#/components/channelBindings/{name}`, so several
+    /// channels can share the same binding
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub parameters: Option<HashMap<String, Parameter>>,
+    pub bindings: Option<BindingsRef>,
+
+    /// Whether the channel is scheduled for removal
+    ///
+    /// AsyncAPI 3.0 has no `deprecated` field for channels, so this is emitted as
+    /// the `x-deprecated` extension, as for deprecated messages and operations.
+    #[serde(rename = "x-deprecated", skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// Content type of messages on this channel that don't set their own
+    ///
+    /// Takes precedence over the document's `defaultContentType`; see
+    /// [`AsyncApiSpec::content_type_of`]. AsyncAPI 3.0 channels have no such field,
+    /// so this is emitted as the `x-default-content-type` extension.
+    #[serde(
+        rename = "x-default-content-type",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_content_type: Option<String>,
 }
 
 /// Channel parameter definition
@@ -301,28 +748,17 @@ pub struct Channel {
 ///
 /// ```rust
 /// use asyncapi_rust_models::{Parameter, Schema, SchemaObject};
-/// use std::collections::HashMap;
 ///
 /// let user_id_param = Parameter {
 ///     description: Some("User ID for this WebSocket connection".to_string()),
 ///     schema: Some(Schema::Object(Box::new(SchemaObject {
 ///         schema_type: Some(serde_json::json!("integer")),
-///         properties: None,
-///         required: None,
-///         description: None,
-///         title: None,
-///         enum_values: None,
-///         const_value: None,
-///         items: None,
-///         additional_properties: None,
-///         one_of: None,
-///         any_of: None,
-///         all_of: None,
-///         additional: HashMap::new(),
+///         ..SchemaObject::default()
 ///     }))),
+///     ..Parameter::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     /// Parameter description
     ///
@@ -330,9 +766,38 @@ pub struct Parameter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Enumeration of allowed values
+    ///
+    /// If specified, only these values are valid for this parameter
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<String>>,
+
+    /// Default value
+    ///
+    /// The value to substitute if no value is provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Example values
+    ///
+    /// Parameters are substituted as strings, so examples are strings too, like
+    /// [`ServerVariable::examples`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<String>>,
+
+    /// Runtime expression for where the value is found in a message
+    ///
+    /// e.g. `$message.payload#/user/id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
     /// Parameter schema
     ///
-    /// The JSON Schema definition for this parameter's type and validation rules
+    /// The JSON Schema definition for this parameter's type and validation rules.
+    /// Examples live in the schema's `examples` keyword as JSON values of any type
+    /// (e.g. numbers for an integer parameter), unlike [`ServerVariable::examples`].
+    /// The AsyncAPI 3.0 Parameter Object has no `schema`, so
+    /// `AsyncApiSpec::validate_schema()` reports parameters that set one.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
 }
@@ -346,6 +811,7 @@ pub struct Parameter {
 ///
 /// ```rust
 /// use asyncapi_rust_models::{MessageRef, Message};
+/// use std::collections::HashMap;
 ///
 /// // Reference to a component message
 /// let ref_msg = MessageRef::Reference {
@@ -359,10 +825,15 @@ pub struct Parameter {
 ///     summary: Some("A chat message".to_string()),
 ///     description: None,
 ///     content_type: Some("application/json".to_string()),
+///     headers: None,
+///     correlation_id: None,
 ///     payload: None,
+///     tags: None,
+///     external_docs: None,
+///     extensions: HashMap::new(),
 /// }));
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum MessageRef {
     /// Reference to component message
@@ -380,6 +851,30 @@ pub enum MessageRef {
     Inline(Box<Message>),
 }
 
+impl<'de> Deserialize<'de> for MessageRef {
+    /// Any object with a `$ref` key is a [`MessageRef::Reference`]
+    ///
+    /// Reference Objects can't be extended, so sibling keys are ignored as the
+    /// AsyncAPI spec requires. A `$ref` that isn't a string is an error rather than
+    /// an empty inline message.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut object = serde_json::Map::deserialize(deserializer)?;
+        match object.remove("$ref") {
+            Some(serde_json::Value::String(reference)) => Ok(MessageRef::Reference { reference }),
+            Some(other) => Err(serde::de::Error::custom(format!(
+                "expected `$ref` to be a string, found {}",
+                other
+            ))),
+            None => serde_json::from_value(serde_json::Value::Object(object))
+                .map(|message| MessageRef::Inline(Box::new(message)))
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 /// Message definition
 ///
 /// Represents a message that can be sent or received through a channel.
@@ -398,6 +893,8 @@ pub enum MessageRef {
 ///     summary: Some("A message in a chat room".to_string()),
 ///     description: Some("Sent when a user posts a message".to_string()),
 ///     content_type: Some("application/json".to_string()),
+///     headers: None,
+///     correlation_id: None,
 ///     payload: Some(Schema::Object(Box::new(SchemaObject {
 ///         schema_type: Some(serde_json::json!("object")),
 ///         properties: None,
@@ -411,11 +908,16 @@ pub enum MessageRef {
 ///         one_of: None,
 ///         any_of: None,
 ///         all_of: None,
+///         read_only: None,
+///         write_only: None,
 ///         additional: HashMap::new(),
 ///     }))),
+///     tags: None,
+///     external_docs: None,
+///     extensions: HashMap::new(),
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Message {
     /// Message name
     ///
@@ -451,11 +953,76 @@ pub struct Message {
     #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
 
+    /// Message headers schema
+    ///
+    /// JSON Schema (of type object) defining the application headers of the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Schema>,
+
+    /// Correlation ID
+    ///
+    /// Identifies where the correlation ID for request/reply tracing is located
+    #[serde(rename = "correlationId", skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
+
     /// Message payload schema
     ///
     /// JSON Schema defining the structure of the message payload
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<Schema>,
+
+    /// Message tags
+    ///
+    /// Tags for logical grouping of messages (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+
+    /// Additional external documentation for this message (optional)
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+
+    /// Specification extensions and other keys without a typed field
+    ///
+    /// `x-*` keys such as `x-deprecated-reason`, kept verbatim
+    #[serde(flatten, serialize_with = "sorted::map")]
+    pub extensions: Map<String, serde_json::Value>,
+}
+
+/// Message extension marking a message as intentionally payload-less
+///
+/// Emitted for `#[asyncapi(no_payload)]` so [`AsyncApiSpec::validate_message_payloads`]
+/// doesn't report the message.
+pub const NO_PAYLOAD_EXTENSION: &str = "x-no-payload";
+
+/// Operation extension marking an operation whose name was declared again
+///
+/// Set on the kept declaration when a later one with the same name is dropped,
+/// e.g. by `#[asyncapi_operation_fn]`, so [`AsyncApiSpec::validate`] reports it.
+pub const REDECLARED_EXTENSION: &str = "x-redeclared";
+
+/// Correlation ID definition
+///
+/// Specifies where a message's correlation ID is located using a runtime
+/// expression (e.g. `$message.header#/requestId`).
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::CorrelationId;
+///
+/// let correlation_id = CorrelationId {
+///     description: Some("Request ID echoed in the reply".to_string()),
+///     location: "$message.header#/requestId".to_string(),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationId {
+    /// Correlation ID description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Runtime expression locating the correlation ID
+    pub location: String,
 }
 
 /// Operation (send or receive)
@@ -467,16 +1034,26 @@ pub struct Message {
 ///
 /// ```rust
 /// use asyncapi_rust_models::{Operation, OperationAction, ChannelRef};
+/// use std::collections::HashMap;
 ///
 /// let operation = Operation {
 ///     action: OperationAction::Send,
 ///     channel: ChannelRef {
 ///         reference: "#/channels/chat".to_string(),
 ///     },
+///     title: None,
+///     summary: Some("Send a chat message".to_string()),
+///     description: None,
 ///     messages: None,
+///     reply: None,
+///     tags: None,
+///     external_docs: None,
+///     security: None,
+///     bindings: None,
+///     extensions: HashMap::new(),
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Operation {
     /// Operation action (send or receive)
     ///
@@ -488,41 +1065,319 @@ pub struct Operation {
     /// Points to the channel where this operation takes place
     pub channel: ChannelRef,
 
+    /// Human-friendly title of the operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Short summary of what the operation does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    /// Detailed description of the operation
+    ///
+    /// CommonMark syntax can be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
     /// Messages for this operation
     ///
     /// Optional list of messages that can be used with this operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub messages: Option<Vec<MessageRef>>,
-}
 
-/// Operation action type
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum OperationAction {
-    /// Send message
-    Send,
-    /// Receive message
-    Receive,
-}
+    /// Reply definition
+    ///
+    /// Describes the response to this operation in request/reply interactions, inline
+    /// or as a reference to `#/components/replies/{name}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply: Option<OperationReplyRef>,
 
-/// Reference to a channel
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ChannelRef {
-    /// $ref path
-    #[serde(rename = "$ref")]
-    pub reference: String,
-}
+    /// Operation tags
+    ///
+    /// Tags for logical grouping of operations (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+
+    /// Additional external documentation for this operation (optional)
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+
+    /// Security schemes that can be used with this operation (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecuritySchemeRef>>,
+
+    /// Protocol-specific operation bindings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bindings: Option<OperationBindings>,
+
+    /// Specification extensions and other keys without a typed field
+    ///
+    /// `x-*` keys such as `x-deprecated`, kept verbatim
+    #[serde(flatten, serialize_with = "sorted::map")]
+    pub extensions: Map<String, serde_json::Value>,
+}
+
+/// Operation reply
+///
+/// Describes the reply to a request/reply operation: where it is sent and which
+/// messages it may carry.
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::{ChannelRef, MessageRef, OperationReply, OperationReplyAddress};
+///
+/// let reply = OperationReply {
+///     address: Some(OperationReplyAddress {
+///         description: Some("Reply inbox chosen by the requester".to_string()),
+///         location: "$message.header#/replyTo".to_string(),
+///     }),
+///     channel: Some(ChannelRef {
+///         reference: "#/channels/replies".to_string(),
+///     }),
+///     messages: Some(vec![MessageRef::Reference {
+///         reference: "#/channels/replies/messages/Pong".to_string(),
+///     }]),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct OperationReply {
+    /// Runtime address of the reply, for replies without a fixed channel address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<OperationReplyAddress>,
+
+    /// Channel the reply is sent on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<ChannelRef>,
+
+    /// Messages the reply may carry
+    ///
+    /// References point at messages of the reply channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<Vec<MessageRef>>,
+}
+
+/// Operation reply or a reference to a reusable one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OperationReplyRef {
+    /// Reference to a component reply, e.g. `#/components/replies/{name}`
+    Reference {
+        /// $ref path
+        #[serde(rename = "$ref")]
+        reference: String,
+    },
+    /// Inline reply definition
+    Inline(OperationReply),
+}
+
+impl OperationReplyRef {
+    /// Get the `$ref` path if this is a reference
+    pub fn as_reference(&self) -> Option<&str> {
+        match self {
+            OperationReplyRef::Reference { reference } => Some(reference),
+            OperationReplyRef::Inline(_) => None,
+        }
+    }
+
+    /// Get the reply definition if this is an inline reply
+    pub fn as_inline(&self) -> Option<&OperationReply> {
+        match self {
+            OperationReplyRef::Reference { .. } => None,
+            OperationReplyRef::Inline(reply) => Some(reply),
+        }
+    }
+}
+
+/// Operation reply address
+///
+/// Locates the reply address in the request message at runtime.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationReplyAddress {
+    /// Reply address description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Runtime expression locating the reply address (e.g. `$message.header#/replyTo`)
+    pub location: String,
+}
+
+/// Protocol-specific bindings for an operation
+///
+/// Keys are protocol names. WebSocket has a typed entry; other protocols are kept
+/// as raw JSON in `additional`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct OperationBindings {
+    /// WebSocket operation binding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ws: Option<WebSocketOperationBinding>,
+
+    /// Bindings for other protocols, keyed by protocol name
+    #[serde(flatten, serialize_with = "sorted::map")]
+    pub additional: Map<String, serde_json::Value>,
+}
+
+/// WebSocket operation binding
+///
+/// The WebSockets binding reserves its operation object without defining fields,
+/// so the negotiated subprotocol is documented as the `x-subprotocol` extension.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct WebSocketOperationBinding {
+    /// WebSocket subprotocol negotiated for this operation (e.g. "json")
+    #[serde(rename = "x-subprotocol", skip_serializing_if = "Option::is_none")]
+    pub subprotocol: Option<String>,
+}
+
+/// Operation action type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationAction {
+    /// Send message
+    Send,
+    /// Receive message
+    Receive,
+}
+
+impl OperationAction {
+    /// Get the action as written in the document, `"send"` or `"receive"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationAction::Send => "send",
+            OperationAction::Receive => "receive",
+        }
+    }
+}
+
+impl fmt::Display for OperationAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Reference to a channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelRef {
+    /// $ref path
+    #[serde(rename = "$ref")]
+    pub reference: String,
+}
 
 /// Reusable components
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct Components {
     /// Message definitions
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub messages: Option<HashMap<String, Message>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub messages: Option<Map<String, Message>>,
 
     /// Schema definitions
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub schemas: Option<Map<String, Schema>>,
+
+    /// Security scheme definitions
+    #[serde(
+        rename = "securitySchemes",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub security_schemes: Option<Map<String, SecurityScheme>>,
+
+    /// Reusable operation replies
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub replies: Option<Map<String, OperationReply>>,
+
+    /// Reusable reply addresses
+    #[serde(
+        rename = "replyAddresses",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub reply_addresses: Option<Map<String, OperationReplyAddress>>,
+
+    /// Reusable channel bindings, keyed by name and then by protocol
+    #[serde(
+        rename = "channelBindings",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::nested_option"
+    )]
+    pub channel_bindings: Option<Map<String, Map<String, serde_json::Value>>>,
+}
+
+/// Security scheme definition
+///
+/// Describes how clients authenticate. Only the common fields are typed; the
+/// type-specific ones (e.g. `name`/`in` for `httpApiKey`, `scheme` for `http`,
+/// `flows` for `oauth2`) are kept in `additional`.
+///
+/// # Example
+///
+/// ```rust
+/// use asyncapi_rust_models::SecurityScheme;
+/// use std::collections::HashMap;
+///
+/// let bearer = SecurityScheme {
+///     scheme_type: "http".to_string(),
+///     description: Some("JWT passed in the Authorization header".to_string()),
+///     additional: HashMap::from([
+///         ("scheme".to_string(), serde_json::json!("bearer")),
+///         ("bearerFormat".to_string(), serde_json::json!("JWT")),
+///     ]),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityScheme {
+    /// Scheme type (e.g. "httpApiKey", "http", "oauth2")
+    #[serde(rename = "type")]
+    pub scheme_type: String,
+
+    /// Scheme description
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schemas: Option<HashMap<String, Schema>>,
+    pub description: Option<String>,
+
+    /// Type-specific fields, kept verbatim
+    #[serde(flatten, serialize_with = "sorted::map")]
+    pub additional: Map<String, serde_json::Value>,
+}
+
+/// Security scheme used by a server or operation
+///
+/// Usually a reference to `#/components/securitySchemes/{name}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SecuritySchemeRef {
+    /// Reference to a component security scheme
+    Reference {
+        /// $ref path
+        #[serde(rename = "$ref")]
+        reference: String,
+    },
+    /// Inline security scheme definition
+    Inline(Box<SecurityScheme>),
+}
+
+/// Bindings object or a reference to reusable bindings
+///
+/// Inline bindings map protocol names (e.g. `kafka`, `ws`) to their binding objects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BindingsRef {
+    /// Reference to bindings declared in components, e.g.
+    /// `#/components/channelBindings/{name}`
+    Reference {
+        /// $ref path
+        #[serde(rename = "$ref")]
+        reference: String,
+    },
+    /// Inline bindings, keyed by protocol
+    Inline(#[serde(serialize_with = "sorted::map")] Map<String, serde_json::Value>),
 }
 
 /// JSON Schema object
@@ -564,10 +1419,12 @@ pub struct Components {
 ///     one_of: None,
 ///     any_of: None,
 ///     all_of: None,
+///     read_only: None,
+///     write_only: None,
 ///     additional: HashMap::new(),
 /// }));
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Schema {
     /// Reference to another schema ($ref)
@@ -583,6 +1440,94 @@ pub enum Schema {
     ///
     /// Contains a complete JSON Schema definition with all properties inline
     Object(Box<SchemaObject>),
+    /// Boolean schema
+    ///
+    /// `true` accepts any value and `false` rejects every value. schemars emits these
+    /// for e.g. `additionalProperties` of `#[serde(deny_unknown_fields)]` types.
+    Bool(bool),
+}
+
+impl<'de> Deserialize<'de> for Schema {
+    /// Tolerant conversion of any JSON Schema object or boolean
+    ///
+    /// Only a bare `{"$ref": ...}` is read as [`Schema::Reference`]. A `$ref` with
+    /// siblings (e.g. the `description` schemars emits for a documented field of a
+    /// referenced type) becomes a [`Schema::Object`] that keeps `$ref` in `additional`,
+    /// so the extra keywords aren't dropped. Keywords whose value doesn't fit the typed
+    /// [`SchemaObject`] field (e.g. a draft-07 tuple `items` array) are kept verbatim in
+    /// `additional` instead of failing.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Bool(value) => Ok(Schema::Bool(value)),
+            serde_json::Value::Object(object) => {
+                if let (1, Some(serde_json::Value::String(reference))) =
+                    (object.len(), object.get("$ref"))
+                {
+                    return Ok(Schema::Reference {
+                        reference: reference.clone(),
+                    });
+                }
+                tolerant_schema_object(object)
+                    .map(|object| Schema::Object(Box::new(object)))
+                    .map_err(serde::de::Error::custom)
+            }
+            other => Err(serde::de::Error::custom(format!(
+                "expected a JSON Schema object or boolean, found {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Deserialize a [`SchemaObject`], moving keywords that don't fit their typed field
+/// into `additional`
+fn tolerant_schema_object(
+    mut object: serde_json::Map<String, serde_json::Value>,
+) -> Result<SchemaObject, serde_json::Error> {
+    // A `null` keyword would read as `None` for an optional typed field and be
+    // dropped on serialization, so keep null keywords verbatim
+    let null_keys: Vec<String> = object
+        .iter()
+        .filter(|(_, value)| value.is_null())
+        .map(|(key, _)| key.clone())
+        .collect();
+    let mut untyped: Map<String, serde_json::Value> = null_keys
+        .iter()
+        .filter_map(|key| object.remove_entry(key))
+        .collect();
+
+    let value = serde_json::Value::Object(object);
+    // Fast path: the common case parses as-is
+    let err = match SchemaObject::deserialize(&value) {
+        Ok(mut schema_object) => {
+            schema_object.additional.extend(untyped);
+            return Ok(schema_object);
+        }
+        Err(err) => err,
+    };
+    let serde_json::Value::Object(object) = value else {
+        return Err(err);
+    };
+
+    let mut typed = serde_json::Map::new();
+    for (key, value) in object {
+        let single = serde_json::Value::Object(serde_json::Map::from_iter([(key, value)]));
+        let fits = SchemaObject::deserialize(&single).is_ok();
+        if let serde_json::Value::Object(single) = single {
+            if fits {
+                typed.extend(single);
+            } else {
+                untyped.extend(single);
+            }
+        }
+    }
+
+    let mut schema_object: SchemaObject = serde_json::from_value(serde_json::Value::Object(typed))?;
+    schema_object.additional.extend(untyped);
+    Ok(schema_object)
 }
 
 /// Schema object with all JSON Schema properties
@@ -610,6 +1555,8 @@ pub enum Schema {
 ///     one_of: None,
 ///     any_of: None,
 ///     all_of: None,
+///     read_only: None,
+///     write_only: None,
 ///     additional: HashMap::new(),
 /// }));
 ///
@@ -630,10 +1577,12 @@ pub enum Schema {
 ///     one_of: None,
 ///     any_of: None,
 ///     all_of: None,
+///     read_only: None,
+///     write_only: None,
 ///     additional: HashMap::new(),
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct SchemaObject {
     /// Schema type
     ///
@@ -645,8 +1594,11 @@ pub struct SchemaObject {
     /// Properties (for object type)
     ///
     /// Map of property names to their schemas when schema_type is "object"
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub properties: Option<HashMap<String, Box<Schema>>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted::option"
+    )]
+    pub properties: Option<Map<String, Box<Schema>>>,
 
     /// Required properties
     ///
@@ -686,7 +1638,8 @@ pub struct SchemaObject {
 
     /// Additional properties
     ///
-    /// Schema for additional properties not explicitly defined (for object types)
+    /// Schema for additional properties not explicitly defined (for object types).
+    /// The common `additionalProperties: false` is [`Schema::Bool`]`(false)`.
     #[serde(
         rename = "additionalProperties",
         skip_serializing_if = "Option::is_none"
@@ -711,11 +1664,23 @@ pub struct SchemaObject {
     #[serde(rename = "allOf", skip_serializing_if = "Option::is_none")]
     pub all_of: Option<Vec<Schema>>,
 
+    /// Read-only flag
+    ///
+    /// Marks a property as set by the server and ignored if sent by clients
+    #[serde(rename = "readOnly", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+
+    /// Write-only flag
+    ///
+    /// Marks a property as sent by clients but never returned by the server
+    #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+
     /// Additional fields that may be present in the schema
     ///
     /// Captures any additional JSON Schema properties not explicitly defined above
-    #[serde(flatten)]
-    pub additional: HashMap<String, serde_json::Value>,
+    #[serde(flatten, serialize_with = "sorted::map")]
+    pub additional: Map<String, serde_json::Value>,
 }
 
 impl Default for AsyncApiSpec {
@@ -726,8 +1691,13 @@ impl Default for AsyncApiSpec {
                 title: "API".to_string(),
                 version: "1.0.0".to_string(),
                 description: None,
+                summary: None,
+                contact: None,
+                license: None,
+                tags: None,
             },
             servers: None,
+            default_content_type: None,
             channels: None,
             operations: None,
             components: None,
@@ -735,29 +1705,3492 @@ impl Default for AsyncApiSpec {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl From<&AsyncApiSpec> for serde_json::Value {
+    /// Serialize the spec into a JSON value
+    fn from(spec: &AsyncApiSpec) -> Self {
+        // Every map in the spec has string keys, so serialization can't fail
+        serde_json::to_value(spec).expect("AsyncApiSpec always serializes to JSON")
+    }
+}
 
-    #[test]
-    fn test_spec_serialization() {
-        let spec = AsyncApiSpec::default();
-        let json = serde_json::to_string(&spec).unwrap();
-        assert!(json.contains("asyncapi"));
-        assert!(json.contains("3.0.0"));
+impl From<AsyncApiSpec> for serde_json::Value {
+    fn from(spec: AsyncApiSpec) -> Self {
+        serde_json::Value::from(&spec)
     }
+}
 
-    #[test]
-    fn test_spec_deserialization() {
-        let json = r#"{
-            "asyncapi": "3.0.0",
-            "info": {
-                "title": "Test API",
-                "version": "1.0.0"
+impl TryFrom<serde_json::Value> for AsyncApiSpec {
+    type Error = serde_json::Error;
+
+    /// Deserialize a spec from a JSON value
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+impl AsyncApiSpec {
+    /// Create a [`SpecBuilder`] for constructing a specification at runtime
+    pub fn builder() -> SpecBuilder {
+        SpecBuilder::default()
+    }
+
+    /// Add a server, replacing any server with the same name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::{AsyncApiSpec, Channel, Server};
+    ///
+    /// let spec = AsyncApiSpec::default()
+    ///     .with_server(
+    ///         "production",
+    ///         Server {
+    ///             host: "chat.example.com:443".to_string(),
+    ///             protocol: "wss".to_string(),
+    ///             pathname: None,
+    ///             description: None,
+    ///             variables: None,
+    ///             security: None,
+    ///         },
+    ///     )
+    ///     .with_channel(
+    ///         "chat",
+    ///         Channel {
+    ///             address: Some("/ws/chat".to_string()),
+    ///             ..Channel::default()
+    ///         },
+    ///     );
+    ///
+    /// assert!(spec.servers.unwrap().contains_key("production"));
+    /// assert!(spec.channels.unwrap().contains_key("chat"));
+    /// ```
+    pub fn with_server(mut self, name: impl Into<String>, server: Server) -> Self {
+        self.servers
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), server);
+        self
+    }
+
+    /// Add a channel, replacing any channel with the same name
+    ///
+    /// See [`with_server`](Self::with_server) for an example.
+    pub fn with_channel(mut self, name: impl Into<String>, channel: Channel) -> Self {
+        self.channels
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), channel);
+        self
+    }
+
+    /// Declare reusable channel bindings in `components.channelBindings`
+    ///
+    /// Channels reference them with `#/components/channelBindings/{name}`, e.g. via
+    /// `#[asyncapi_channel(bindings_ref = "...")]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::AsyncApiSpec;
+    /// use std::collections::HashMap;
+    ///
+    /// let spec = AsyncApiSpec::default().with_channel_bindings(
+    ///     "ordersTopic",
+    ///     HashMap::from([(
+    ///         "kafka".to_string(),
+    ///         serde_json::json!({ "partitions": 3, "bindingVersion": "0.5.0" }),
+    ///     )]),
+    /// );
+    ///
+    /// let components = spec.components.unwrap();
+    /// assert!(components.channel_bindings.unwrap().contains_key("ordersTopic"));
+    /// ```
+    pub fn with_channel_bindings(
+        mut self,
+        name: impl Into<String>,
+        bindings: Map<String, serde_json::Value>,
+    ) -> Self {
+        self.components
+            .get_or_insert_with(Components::default)
+            .channel_bindings
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), bindings);
+        self
+    }
+
+    /// Get all servers using the given protocol (e.g. "wss"), sorted by name
+    pub fn servers_by_protocol(&self, protocol: &str) -> Vec<(&String, &Server)> {
+        sorted_entries(
+            self.servers
+                .iter()
+                .flatten()
+                .filter(|(_, server)| server.protocol == protocol),
+        )
+    }
+
+    /// Get all channels sorted by name, e.g. for deterministic rendering
+    pub fn channels_sorted(&self) -> Vec<(&String, &Channel)> {
+        sorted_entries(self.channels.iter().flatten())
+    }
+
+    /// Get all operations sorted by name, ignoring their declaration order
+    pub fn operations_sorted(&self) -> Vec<(&String, &Operation)> {
+        sorted_entries(self.operations.iter().flatten())
+    }
+
+    /// Get all messages in `components.messages` sorted by name
+    pub fn messages_sorted(&self) -> Vec<(&String, &Message)> {
+        sorted_entries(
+            self.components
+                .iter()
+                .flat_map(|c| c.messages.iter().flatten()),
+        )
+    }
+
+    /// Get the server names, sorted
+    pub fn server_names(&self) -> Vec<&str> {
+        sorted_names(self.servers.iter().flat_map(|servers| servers.keys()))
+    }
+
+    /// Get the channel names, sorted
+    pub fn channel_names(&self) -> Vec<&str> {
+        sorted_names(self.channels.iter().flat_map(|channels| channels.keys()))
+    }
+
+    /// Get the operation names, sorted rather than in declaration order
+    pub fn operation_names(&self) -> Vec<&str> {
+        sorted_names(
+            self.operations
+                .iter()
+                .flat_map(|operations| operations.keys()),
+        )
+    }
+
+    /// Look up a message in `components.messages` by name
+    pub fn find_message(&self, name: &str) -> Option<&Message> {
+        self.components.as_ref()?.messages.as_ref()?.get(name)
+    }
+
+    /// Declare a tag in `info.tags`, unless one with the same name already exists
+    ///
+    /// Returns `true` if the tag was added.
+    pub fn add_tag(&mut self, tag: Tag) -> bool {
+        let tags = self.info.tags.get_or_insert_with(Vec::new);
+        if tags.iter().any(|existing| existing.name == tag.name) {
+            return false;
+        }
+        tags.push(tag);
+        true
+    }
+
+    /// Collect the tags used by channels, operations and messages
+    ///
+    /// Tags are deduplicated by name and sorted, preferring a variant with a
+    /// description when the same name is used with different descriptions. Tags
+    /// declared only in `info.tags` are not included.
+    pub fn collect_tags(&self) -> Vec<&Tag> {
+        let channels = self.channels.iter().flat_map(|c| c.values());
+        let channel_messages = channels
+            .clone()
+            .flat_map(|c| c.messages.iter().flat_map(|m| m.values()))
+            .filter_map(MessageRef::as_inline);
+        let component_messages = self
+            .components
+            .iter()
+            .flat_map(|c| c.messages.iter().flat_map(|m| m.values()));
+
+        let mut used: Vec<&Tag> = channels
+            .flat_map(|c| c.tags.iter().flatten())
+            .chain(
+                self.operations
+                    .iter()
+                    .flat_map(|ops| ops.values())
+                    .flat_map(|op| op.tags.iter().flatten()),
+            )
+            .chain(
+                channel_messages
+                    .chain(component_messages)
+                    .flat_map(|m| m.tags.iter().flatten()),
+            )
+            .collect();
+        used.sort_by(|a, b| {
+            (&a.name, a.description.is_none(), &a.description).cmp(&(
+                &b.name,
+                b.description.is_none(),
+                &b.description,
+            ))
+        });
+        used.dedup_by(|a, b| a.name == b.name);
+        used
+    }
+
+    /// Declare every tag used by channels, operations and messages in `info.tags`
+    ///
+    /// Already declared tags are left untouched; missing ones are appended in
+    /// [`collect_tags`](Self::collect_tags) order. Returns the number of tags added.
+    pub fn normalize_tags(&mut self) -> usize {
+        let used: Vec<Tag> = self.collect_tags().into_iter().cloned().collect();
+        used.into_iter()
+            .filter(|tag| self.add_tag(tag.clone()))
+            .count()
+    }
+
+    /// Count the servers, channels, operations, messages and schemas in this spec
+    pub fn summary_stats(&self) -> SpecStats {
+        let operations = self.operations.iter().flat_map(|ops| ops.values());
+        let component_messages = self
+            .components
+            .iter()
+            .flat_map(|c| c.messages.iter().flat_map(|m| m.values()));
+
+        SpecStats {
+            servers: self.servers.as_ref().map_or(0, |s| s.len()),
+            channels: self.channels.as_ref().map_or(0, |c| c.len()),
+            send_operations: operations.clone().filter(|op| op.action_is_send()).count(),
+            receive_operations: operations.filter(|op| op.action_is_receive()).count(),
+            messages: component_messages.clone().count(),
+            schemas: self
+                .components
+                .as_ref()
+                .and_then(|c| c.schemas.as_ref())
+                .map_or(0, |s| s.len()),
+            payload_bytes: component_messages
+                .filter_map(|message| message.payload.as_ref())
+                .map(|payload| serde_json::to_string(payload).map_or(0, |json| json.len()))
+                .sum(),
+        }
+    }
+
+    /// Rename a channel and rewrite every reference to it
+    ///
+    /// Updates operation and reply `channel` refs and message refs of the form
+    /// `#/channels/{old}/messages/...`, in operation replies and `components.replies`
+    /// too. Returns `false` without changing anything if `old` doesn't exist or `new`
+    /// is already taken.
+    pub fn rename_channel(&mut self, old: &str, new: &str) -> bool {
+        if !rename_key(&mut self.channels, old, new) {
+            return false;
+        }
+
+        let old_ref = format!("#/channels/{}", old);
+        let new_ref = format!("#/channels/{}", new);
+        for operation in self.operations.iter_mut().flat_map(|ops| ops.values_mut()) {
+            rewrite_ref(&mut operation.channel.reference, &old_ref, &new_ref);
+        }
+        for reply in self.replies_mut() {
+            if let Some(channel) = reply.channel.as_mut() {
+                rewrite_ref(&mut channel.reference, &old_ref, &new_ref);
             }
-        }"#;
-        let spec: AsyncApiSpec = serde_json::from_str(json).unwrap();
-        assert_eq!(spec.asyncapi, "3.0.0");
-        assert_eq!(spec.info.title, "Test API");
+        }
+        self.rewrite_message_refs(&old_ref, &new_ref);
+        true
+    }
+
+    /// Rename an operation, keeping its position in the operations map
+    ///
+    /// Returns `false` without changing anything if `old` doesn't exist or `new`
+    /// is already taken.
+    pub fn rename_operation(&mut self, old: &str, new: &str) -> bool {
+        let Some(operations) = self.operations.as_mut() else {
+            return false;
+        };
+        if operations.contains_key(new) {
+            return false;
+        }
+        match operations.shift_remove_full(old) {
+            Some((index, _, operation)) => {
+                operations.shift_insert(index, new.to_string(), operation);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rename a component message and rewrite every reference to it
+    ///
+    /// Updates channel, operation and reply message refs pointing at
+    /// `#/components/messages/{old}`. Returns `false` without changing anything if
+    /// `old` doesn't exist or `new` is already taken.
+    pub fn rename_message(&mut self, old: &str, new: &str) -> bool {
+        let Some(components) = self.components.as_mut() else {
+            return false;
+        };
+        if !rename_key(&mut components.messages, old, new) {
+            return false;
+        }
+
+        self.rewrite_message_refs(
+            &format!("#/components/messages/{}", old),
+            &format!("#/components/messages/{}", new),
+        );
+        true
+    }
+
+    /// Remove `components.messages` and `components.schemas` entries nothing refers to
+    ///
+    /// A component is kept if a `$ref` in the channels, operations (including their
+    /// replies), the other component sections such as `components.replies`, or another
+    /// kept component points at it. Returns the number of entries removed.
+    pub fn prune_unused_components(&mut self) -> usize {
+        let mut pending = Vec::new();
+        collect_refs(
+            &serde_json::to_value(&self.channels).unwrap_or_default(),
+            &mut pending,
+        );
+        collect_refs(
+            &serde_json::to_value(&self.operations).unwrap_or_default(),
+            &mut pending,
+        );
+        // Sections that aren't pruned are roots too
+        if let Ok(serde_json::Value::Object(mut sections)) = serde_json::to_value(&self.components)
+        {
+            sections.remove("messages");
+            sections.remove("schemas");
+            collect_refs(&serde_json::Value::Object(sections), &mut pending);
+        }
+        let Some(components) = self.components.as_mut() else {
+            return 0;
+        };
+
+        // Follow references transitively through the components themselves
+        let mut used_messages = BTreeSet::new();
+        let mut used_schemas = BTreeSet::new();
+        while let Some(reference) = pending.pop() {
+            let component = match component_ref(&reference) {
+                Some(("messages", name)) if used_messages.insert(name.clone()) => components
+                    .messages
+                    .as_ref()
+                    .and_then(|messages| messages.get(&name))
+                    .and_then(|message| serde_json::to_value(message).ok()),
+                Some(("schemas", name)) if used_schemas.insert(name.clone()) => components
+                    .schemas
+                    .as_ref()
+                    .and_then(|schemas| schemas.get(&name))
+                    .and_then(|schema| serde_json::to_value(schema).ok()),
+                _ => None,
+            };
+            if let Some(component) = component {
+                collect_refs(&component, &mut pending);
+            }
+        }
+
+        let mut removed = 0;
+        if let Some(messages) = components.messages.as_mut() {
+            let before = messages.len();
+            messages.retain(|name, _| used_messages.contains(name));
+            removed += before - messages.len();
+        }
+        if let Some(schemas) = components.schemas.as_mut() {
+            let before = schemas.len();
+            schemas.retain(|name, _| used_schemas.contains(name));
+            removed += before - schemas.len();
+        }
+        removed
+    }
+
+    /// Inline component messages into channels, without their `name`
+    ///
+    /// Channel `messages` maps are keyed by message name, so a `name` there only repeats
+    /// the key. Channel messages that reference `#/components/messages/{name}` are
+    /// replaced by a copy of the component message without `name`, and inline channel
+    /// messages whose name matches their key drop it. `components.messages` keep their
+    /// names, as do names that differ from the key and refs to unknown or external
+    /// messages. This is what `#[asyncapi(omit_channel_message_names)]` and
+    /// [`SpecBuilder::omit_channel_message_names`] apply. Returns the number of channel
+    /// messages changed.
+    pub fn omit_channel_message_names(&mut self) -> usize {
+        let component_messages = self.components.as_ref().and_then(|c| c.messages.as_ref());
+        let mut changed = 0;
+        for channel in self.channels.iter_mut().flat_map(|c| c.values_mut()) {
+            for (key, message) in channel.messages.iter_mut().flatten() {
+                match message {
+                    MessageRef::Inline(message) => {
+                        if message.name.as_deref() == Some(key.as_str()) {
+                            message.name = None;
+                            changed += 1;
+                        }
+                    }
+                    MessageRef::Reference { reference } => {
+                        let component = reference
+                            .strip_prefix("#/components/messages/")
+                            .and_then(|name| component_messages?.get(name));
+                        if let Some(component) = component {
+                            *message = MessageRef::Inline(Box::new(Message {
+                                name: None,
+                                ..component.clone()
+                            }));
+                            changed += 1;
+                        }
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    /// Remove message payload and header schemas and `components.schemas`
+    ///
+    /// Covers component messages and inline messages in channels, operations and
+    /// replies; names, descriptions and refs are kept, so the spec still lists every
+    /// message. Returns the number of schemas removed. A stripped spec will report
+    /// [`ValidationIssue::MissingPayload`](crate::ValidationIssue) warnings.
+    pub fn strip_schemas(&mut self) -> usize {
+        let mut removed = 0;
+        let mut strip = |message: &mut Message| {
+            removed += usize::from(message.payload.take().is_some());
+            removed += usize::from(message.headers.take().is_some());
+        };
+
+        for channel in self.channels.iter_mut().flat_map(|c| c.values_mut()) {
+            for message in channel.messages.iter_mut().flat_map(|m| m.values_mut()) {
+                if let MessageRef::Inline(message) = message {
+                    strip(message);
+                }
+            }
+        }
+        for operation in self.operations.iter_mut().flat_map(|o| o.values_mut()) {
+            let reply_messages = match operation.reply.as_mut() {
+                Some(OperationReplyRef::Inline(reply)) => reply.messages.as_mut(),
+                _ => None,
+            };
+            for message in operation
+                .messages
+                .iter_mut()
+                .chain(reply_messages)
+                .flatten()
+            {
+                if let MessageRef::Inline(message) = message {
+                    strip(message);
+                }
+            }
+        }
+        if let Some(components) = self.components.as_mut() {
+            let reply_messages = components
+                .replies
+                .iter_mut()
+                .flat_map(|replies| replies.values_mut())
+                .flat_map(|reply| reply.messages.iter_mut().flatten());
+            for message in reply_messages {
+                if let MessageRef::Inline(message) = message {
+                    strip(message);
+                }
+            }
+            for message in components.messages.iter_mut().flat_map(|m| m.values_mut()) {
+                strip(message);
+            }
+            removed += components.schemas.take().map_or(0, |schemas| schemas.len());
+        }
+        removed
+    }
+
+    /// Get a copy of the spec without schemas, leaving this one intact
+    ///
+    /// Non-mutating counterpart to [`strip_schemas`](Self::strip_schemas), e.g. for
+    /// serving a compact index of messages next to the full spec.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::AsyncApiSpec;
+    ///
+    /// let spec = AsyncApiSpec::try_from(serde_json::json!({
+    ///     "asyncapi": "3.0.0",
+    ///     "info": { "title": "Chat", "version": "1.0.0" },
+    ///     "components": {
+    ///         "messages": { "ping": { "payload": { "type": "object" } } }
+    ///     }
+    /// }))
+    /// .unwrap();
+    ///
+    /// let index = spec.clone_without_schemas();
+    /// assert!(index.find_message("ping").unwrap().payload.is_none());
+    /// assert!(spec.find_message("ping").unwrap().payload.is_some());
+    /// ```
+    pub fn clone_without_schemas(&self) -> Self {
+        let mut spec = self.clone();
+        spec.strip_schemas();
+        spec
+    }
+
+    /// Copy the spec with external message refs pointed back at its own components
+    ///
+    /// Undoes `#[asyncapi(ref_base = "...")]`: a ref such as
+    /// `./messages.json#/components/messages/chat` in a channel, operation or reply
+    /// becomes `#/components/messages/chat`, so the result is one self-contained
+    /// document. The derive still generates `components.messages`, so every such ref
+    /// resolves locally.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AsyncApiError::UnresolvedRef`] for an external ref whose message is
+    /// not in `components.messages`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::AsyncApiSpec;
+    ///
+    /// let spec = AsyncApiSpec::try_from(serde_json::json!({
+    ///     "asyncapi": "3.0.0",
+    ///     "info": { "title": "Split API", "version": "1.0.0" },
+    ///     "channels": {
+    ///         "chat": {
+    ///             "messages": {
+    ///                 "ping": { "$ref": "./messages.json#/components/messages/ping" }
+    ///             }
+    ///         }
+    ///     },
+    ///     "components": { "messages": { "ping": { "payload": { "type": "object" } } } }
+    /// }))
+    /// .unwrap();
+    ///
+    /// let bundle = spec.to_bundle().unwrap();
+    /// let messages = bundle.channels.unwrap()["chat"].messages.clone().unwrap();
+    /// assert_eq!(messages["ping"].as_reference(), Some("#/components/messages/ping"));
+    /// ```
+    pub fn to_bundle(&self) -> Result<Self, AsyncApiError> {
+        let mut spec = self.clone();
+        let local_messages: BTreeSet<String> = spec
+            .components
+            .iter()
+            .flat_map(|c| c.messages.iter().flat_map(|m| m.keys().cloned()))
+            .collect();
+
+        let channel_messages = spec
+            .channels
+            .iter_mut()
+            .flat_map(|channels| channels.values_mut())
+            .flat_map(|channel| channel.messages.iter_mut().flat_map(|m| m.values_mut()));
+        let operation_messages = spec
+            .operations
+            .iter_mut()
+            .flat_map(|operations| operations.values_mut())
+            .flat_map(|operation| {
+                let reply = match operation.reply.as_mut() {
+                    Some(OperationReplyRef::Inline(reply)) => reply.messages.as_mut(),
+                    _ => None,
+                };
+                operation.messages.iter_mut().chain(reply).flatten()
+            });
+        let component_reply_messages = spec
+            .components
+            .iter_mut()
+            .flat_map(|c| c.replies.iter_mut().flat_map(|r| r.values_mut()))
+            .flat_map(|reply| reply.messages.iter_mut().flatten());
+
+        for message in channel_messages
+            .chain(operation_messages)
+            .chain(component_reply_messages)
+        {
+            let MessageRef::Reference { reference } = message else {
+                continue;
+            };
+            // Local refs are left alone
+            let Some((_, fragment)) = reference
+                .split_once('#')
+                .filter(|(base, _)| !base.is_empty())
+            else {
+                continue;
+            };
+            let resolves = fragment
+                .strip_prefix("/components/messages/")
+                .is_some_and(|name| local_messages.contains(name));
+            if !resolves {
+                return Err(AsyncApiError::UnresolvedRef {
+                    reference: reference.clone(),
+                });
+            }
+            *reference = format!("#{}", fragment);
+        }
+
+        Ok(spec)
+    }
+
+    /// Inline operation replies and `components.replies`
+    fn replies_mut(&mut self) -> impl Iterator<Item = &mut OperationReply> {
+        let operation_replies = self
+            .operations
+            .iter_mut()
+            .flat_map(|operations| operations.values_mut())
+            .filter_map(|operation| match operation.reply.as_mut() {
+                Some(OperationReplyRef::Inline(reply)) => Some(reply),
+                _ => None,
+            });
+        let component_replies = self
+            .components
+            .iter_mut()
+            .flat_map(|c| c.replies.iter_mut().flat_map(|r| r.values_mut()));
+        operation_replies.chain(component_replies)
+    }
+
+    /// Rewrite all channel, operation and reply message refs under `old_ref` to `new_ref`
+    fn rewrite_message_refs(&mut self, old_ref: &str, new_ref: &str) {
+        let channel_messages = self
+            .channels
+            .iter_mut()
+            .flat_map(|channels| channels.values_mut())
+            .flat_map(|channel| channel.messages.iter_mut().flat_map(|m| m.values_mut()));
+        let operation_messages = self
+            .operations
+            .iter_mut()
+            .flat_map(|operations| operations.values_mut())
+            .flat_map(|operation| operation.messages.iter_mut().flatten());
+
+        for message in channel_messages.chain(operation_messages) {
+            if let MessageRef::Reference { reference } = message {
+                rewrite_ref(reference, old_ref, new_ref);
+            }
+        }
+
+        let reply_messages = self
+            .replies_mut()
+            .flat_map(|reply| reply.messages.iter_mut().flatten());
+        for message in reply_messages {
+            if let MessageRef::Reference { reference } = message {
+                rewrite_ref(reference, old_ref, new_ref);
+            }
+        }
+    }
+}
+
+/// Copy the `$defs` entries a subschema references from its root schema
+///
+/// Subschemas taken out of a schemars root, such as one variant of an enum's
+/// `oneOf`, still point at `#/$defs/...` of the root. This adds the referenced
+/// definitions, and the ones they reference in turn, to the subschema's own `$defs`
+/// so its refs resolve on their own. Existing entries are kept.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+///
+/// let root = json!({
+///     "oneOf": [{ "properties": { "data": { "$ref": "#/$defs/Shared" } } }],
+///     "$defs": {
+///         "Shared": { "properties": { "inner": { "$ref": "#/$defs/Inner" } } },
+///         "Inner": { "type": "string" },
+///         "Unused": { "type": "integer" }
+///     }
+/// });
+/// let mut variant = root["oneOf"][0].clone();
+/// asyncapi_rust_models::lift_defs(&mut variant, &root);
+///
+/// let defs = variant["$defs"].as_object().unwrap();
+/// assert!(defs.contains_key("Shared") && defs.contains_key("Inner"));
+/// assert!(!defs.contains_key("Unused"));
+/// ```
+pub fn lift_defs(schema: &mut serde_json::Value, root: &serde_json::Value) {
+    let Some(defs) = root.get("$defs").and_then(serde_json::Value::as_object) else {
+        return;
+    };
+    let mut pending = Vec::new();
+    collect_refs(schema, &mut pending);
+
+    let mut lifted = serde_json::Map::new();
+    while let Some(reference) = pending.pop() {
+        let Some(name) = reference.strip_prefix("#/$defs/") else {
+            continue;
+        };
+        if lifted.contains_key(name) {
+            continue;
+        }
+        if let Some(definition) = defs.get(name) {
+            collect_refs(definition, &mut pending);
+            lifted.insert(name.to_string(), definition.clone());
+        }
+    }
+
+    if let (false, serde_json::Value::Object(object)) = (lifted.is_empty(), schema) {
+        let target = object
+            .entry("$defs")
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if let serde_json::Value::Object(target) = target {
+            for (name, definition) in lifted {
+                target.entry(name).or_insert(definition);
+            }
+        }
+    }
+}
+
+/// Collect every `$ref` string in a JSON value
+fn collect_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                match value {
+                    serde_json::Value::String(reference) if key == "$ref" => {
+                        refs.push(reference.clone())
+                    }
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_refs(value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Split `#/components/{section}/{name}[/...]` into its section and decoded name
+fn component_ref(reference: &str) -> Option<(&str, String)> {
+    let mut segments = reference.strip_prefix("#/components/")?.split('/');
+    let section = segments.next()?;
+    let name = segments.next()?.replace("~1", "/").replace("~0", "~");
+    Some((section, name))
+}
+
+/// Move `map[old]` to `map[new]`, returning `false` if `old` is missing or `new` exists
+fn rename_key<V>(map: &mut Option<Map<String, V>>, old: &str, new: &str) -> bool {
+    let Some(map) = map.as_mut() else {
+        return false;
+    };
+    if map.contains_key(new) {
+        return false;
+    }
+    match map.remove(old) {
+        Some(value) => {
+            map.insert(new.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Replace the `old_ref` prefix of `reference` with `new_ref`
+///
+/// Only whole path segments match, so renaming `chat` leaves `#/channels/chatRoom` alone.
+fn rewrite_ref(reference: &mut String, old_ref: &str, new_ref: &str) {
+    if let Some(rest) = reference.strip_prefix(old_ref) {
+        if rest.is_empty() || rest.starts_with('/') {
+            *reference = format!("{}{}", new_ref, rest);
+        }
+    }
+}
+
+/// Summary counts for an [`AsyncApiSpec`]
+///
+/// Returned by [`AsyncApiSpec::summary_stats`]. The `Display` impl prints one
+/// count per line, suitable for CI reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpecStats {
+    /// Number of servers
+    pub servers: usize,
+    /// Number of channels
+    pub channels: usize,
+    /// Number of send operations
+    pub send_operations: usize,
+    /// Number of receive operations
+    pub receive_operations: usize,
+    /// Number of messages in `components.messages`
+    pub messages: usize,
+    /// Number of schemas in `components.schemas`
+    pub schemas: usize,
+    /// Total size in bytes of the component message payload schemas, as compact JSON
+    pub payload_bytes: usize,
+}
+
+impl SpecStats {
+    /// Total number of operations
+    pub fn operations(&self) -> usize {
+        self.send_operations + self.receive_operations
+    }
+}
+
+impl fmt::Display for SpecStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Servers: {}", self.servers)?;
+        writeln!(f, "Channels: {}", self.channels)?;
+        writeln!(
+            f,
+            "Operations: {} ({} send, {} receive)",
+            self.operations(),
+            self.send_operations,
+            self.receive_operations
+        )?;
+        writeln!(f, "Messages: {}", self.messages)?;
+        writeln!(f, "Schemas: {}", self.schemas)?;
+        write!(f, "Payload size: {} bytes", self.payload_bytes)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl AsyncApiSpec {
+    /// Serialize the spec as a YAML document
+    ///
+    /// Keys are the same as in the JSON output (`$ref`, `contentType`, ...). Maps
+    /// are written in key order and operations in declaration order, so the same
+    /// spec always gives the same document. Requires the `yaml` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::AsyncApiSpec;
+    ///
+    /// let yaml = AsyncApiSpec::default().to_yaml().unwrap();
+    /// assert!(yaml.starts_with("asyncapi: 3.0.0\n"));
+    /// assert_eq!(AsyncApiSpec::from_yaml(&yaml).unwrap(), AsyncApiSpec::default());
+    /// ```
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Deserialize a spec from a YAML document
+    ///
+    /// Requires the `yaml` feature.
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(s)
+    }
+}
+
+/// AsyncAPI 3.0 JSON Schema used by [`AsyncApiSpec::validate_schema`]
+#[cfg(feature = "validate-schema")]
+const ASYNCAPI_3_0_SCHEMA: &str = include_str!("../schemas/asyncapi-3.0.0.json");
+
+/// [`ASYNCAPI_3_0_SCHEMA`] compiled on first use
+#[cfg(feature = "validate-schema")]
+fn asyncapi_validator() -> &'static jsonschema::Validator {
+    static VALIDATOR: std::sync::OnceLock<jsonschema::Validator> = std::sync::OnceLock::new();
+    VALIDATOR.get_or_init(|| {
+        let schema: serde_json::Value = serde_json::from_str(ASYNCAPI_3_0_SCHEMA)
+            .expect("Bundled AsyncAPI schema is valid JSON");
+        jsonschema::validator_for(&schema).expect("Bundled AsyncAPI schema is valid")
+    })
+}
+
+#[cfg(feature = "validate-schema")]
+impl AsyncApiSpec {
+    /// Validate the serialized document against the AsyncAPI 3.0 JSON Schema
+    ///
+    /// This catches structural problems such as unknown keys or invalid `$ref`
+    /// objects. Requires the `validate-schema` feature.
+    ///
+    /// The schema is compiled once, on the first call.
+    ///
+    /// Returns every validation error as `"{json pointer}: {message}"`.
+    pub fn validate_schema(&self) -> Result<(), Vec<String>> {
+        let document = serde_json::to_value(self).map_err(|e| vec![e.to_string()])?;
+        let errors: Vec<String> = asyncapi_validator()
+            .iter_errors(&document)
+            .map(|error| format!("{}: {}", error.instance_path, error))
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(feature = "validate-schema")]
+impl Message {
+    /// Validate a JSON payload against this message's `payload` schema
+    ///
+    /// Lets handlers enforce the documented schema on incoming messages. Refs are
+    /// resolved within the payload schema (e.g. schemars' `#/$defs/...`); use
+    /// [`validate_payload_in`](Self::validate_payload_in) for refs into a spec's
+    /// components. Messages without a payload schema accept anything. Requires the
+    /// `validate-schema` feature.
+    ///
+    /// A Multi Format payload is checked against its inner `schema` when its
+    /// `schemaFormat` is JSON Schema. Other formats, such as Avro or Protobuf, can't
+    /// be checked here, so they return an "unsupported payload schemaFormat" error
+    /// rather than accepting every payload.
+    ///
+    /// The schema is compiled on every call; for repeated validation build a
+    /// [`PayloadValidator`] once with [`payload_validator`](Self::payload_validator).
+    ///
+    /// Returns every validation error as `"{json pointer}: {message}"`.
+    pub fn validate_payload(&self, value: &serde_json::Value) -> Result<(), Vec<String>> {
+        self.check_payload(None)?.validate(value)
+    }
+
+    /// Validate a JSON payload, resolving `#/components/...` refs against `spec`
+    pub fn validate_payload_in(
+        &self,
+        spec: &AsyncApiSpec,
+        value: &serde_json::Value,
+    ) -> Result<(), Vec<String>> {
+        self.payload_validator(spec)?.validate(value)
+    }
+
+    /// Compile this message's payload schema, resolving `#/components/...` refs
+    /// against `spec`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::{AsyncApiSpec, Message};
+    ///
+    /// let message: Message =
+    ///     serde_json::from_value(serde_json::json!({ "payload": { "type": "string" } })).unwrap();
+    /// let validator = message.payload_validator(&AsyncApiSpec::default()).unwrap();
+    /// assert!(validator.validate(&serde_json::json!("hello")).is_ok());
+    /// assert!(validator.validate(&serde_json::json!(42)).is_err());
+    /// ```
+    pub fn payload_validator(&self, spec: &AsyncApiSpec) -> Result<PayloadValidator, Vec<String>> {
+        self.check_payload(spec.components.as_ref())
+    }
+
+    fn check_payload(
+        &self,
+        components: Option<&Components>,
+    ) -> Result<PayloadValidator, Vec<String>> {
+        let Some(payload) = &self.payload else {
+            return Ok(PayloadValidator { validator: None });
+        };
+        if let Some(format) = payload
+            .schema_format()
+            .filter(|format| !content_type::is_json_schema_format(format))
+        {
+            return Err(vec![format!(
+                "unsupported payload schemaFormat '{}'",
+                format
+            )]);
+        }
+        let mut schema = serde_json::to_value(payload).map_err(|e| vec![e.to_string()])?;
+        // A Multi Format Schema Object wraps the JSON Schema in `schema`
+        if payload.schema_format().is_some() {
+            schema = schema
+                .get_mut("schema")
+                .map(serde_json::Value::take)
+                .unwrap_or(serde_json::Value::Bool(true));
+        }
+        // Refs are resolved against the root document, so make the spec's components
+        // reachable from the payload schema under the same pointer
+        if let (Some(components), serde_json::Value::Object(object)) = (components, &mut schema) {
+            let components = serde_json::to_value(components).map_err(|e| vec![e.to_string()])?;
+            object.entry("components").or_insert(components);
+        }
+        let validator = jsonschema::validator_for(&schema)
+            .map_err(|e| vec![format!("invalid payload schema: {}", e)])?;
+        Ok(PayloadValidator {
+            validator: Some(validator),
+        })
+    }
+}
+
+/// A message's payload schema, compiled for validating many payloads
+///
+/// Built by [`Message::payload_validator`]. Requires the `validate-schema` feature.
+#[cfg(feature = "validate-schema")]
+#[derive(Debug)]
+pub struct PayloadValidator {
+    /// `None` for messages without a payload schema, which accept anything
+    validator: Option<jsonschema::Validator>,
+}
+
+#[cfg(feature = "validate-schema")]
+impl PayloadValidator {
+    /// Validate a JSON payload against the compiled schema
+    ///
+    /// Returns every validation error as `"{json pointer}: {message}"`.
+    pub fn validate(&self, value: &serde_json::Value) -> Result<(), Vec<String>> {
+        let Some(validator) = &self.validator else {
+            return Ok(());
+        };
+        let errors: Vec<String> = validator
+            .iter_errors(value)
+            .map(|error| format!("{}: {}", error.instance_path, error))
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Server {
+    /// Get the `{variable}` names used in `pathname`, in order of appearance
+    ///
+    /// Each name is listed once; a server without a pathname has none.
+    pub fn variables_in_pathname(&self) -> Vec<&str> {
+        self.pathname
+            .as_deref()
+            .map(placeholders)
+            .unwrap_or_default()
+    }
+
+    /// Compare the variables used in `pathname` with those declared in `variables`
+    ///
+    /// Runtime counterpart of the derive's compile-time check, for specs loaded from
+    /// files. Placeholders in `host` (e.g. `{region}.example.com`) count as uses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::{Server, ServerVariable};
+    /// use std::collections::HashMap;
+    ///
+    /// let variable = ServerVariable {
+    ///     description: None,
+    ///     default: None,
+    ///     enum_values: None,
+    ///     examples: None,
+    /// };
+    /// let server = Server {
+    ///     host: "api.example.com".to_string(),
+    ///     protocol: "wss".to_string(),
+    ///     pathname: Some("/ws/{userId}".to_string()),
+    ///     description: None,
+    ///     variables: Some(HashMap::from([("tenant".to_string(), variable)])),
+    ///     security: None,
+    /// };
+    ///
+    /// let check = server.check_variables();
+    /// assert_eq!(check.unused, vec!["tenant"]);
+    /// assert_eq!(check.undefined, vec!["userId"]);
+    /// ```
+    pub fn check_variables(&self) -> VariableCheck {
+        let declared = self.variables.as_ref();
+        let undefined = self
+            .variables_in_pathname()
+            .into_iter()
+            .filter(|name| !declared.is_some_and(|vars| vars.contains_key(*name)))
+            .map(str::to_string)
+            .collect();
+
+        let used = placeholders(&self.host)
+            .into_iter()
+            .chain(self.variables_in_pathname())
+            .collect::<Vec<_>>();
+        let mut unused: Vec<String> = declared
+            .into_iter()
+            .flat_map(|vars| vars.keys())
+            .filter(|name| !used.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        unused.sort();
+
+        VariableCheck { unused, undefined }
+    }
+
+    /// Get the port from `host`, or the protocol's default port if none is given
+    pub fn port(&self) -> Option<u16> {
+        let explicit = match self.host.rsplit_once(']') {
+            Some((_, rest)) => rest.strip_prefix(':'),
+            None => self.host.rsplit_once(':').map(|(_, port)| port),
+        };
+        match explicit {
+            Some(port) => port.parse().ok(),
+            None => default_port(&self.protocol),
+        }
+    }
+}
+
+impl Channel {
+    /// Get the title to display for this channel
+    ///
+    /// Returns `title` if set, otherwise `key` (the channel's name in the channels map).
+    pub fn display_title<'a>(&'a self, key: &'a str) -> &'a str {
+        self.title.as_deref().unwrap_or(key)
+    }
+
+    /// Get the channel's messages for modification, creating an empty map if unset
+    pub fn messages_mut(&mut self) -> &mut Map<String, MessageRef> {
+        self.messages.get_or_insert_with(Map::new)
+    }
+
+    /// Get the channel's parameters for modification, creating an empty map if unset
+    pub fn parameters_mut(&mut self) -> &mut Map<String, Parameter> {
+        self.parameters.get_or_insert_with(Map::new)
+    }
+
+    /// Get the declared parameters that don't appear as `{name}` in `address`, sorted
+    ///
+    /// Runtime counterpart of the derive's `strict` check. A channel without an
+    /// address uses none of its parameters.
+    pub fn unused_parameters(&self) -> Vec<&str> {
+        let used = self
+            .address
+            .as_deref()
+            .map(placeholders)
+            .unwrap_or_default();
+        let mut unused: Vec<&str> = self
+            .parameters
+            .iter()
+            .flat_map(|parameters| parameters.keys())
+            .map(String::as_str)
+            .filter(|name| !used.contains(name))
+            .collect();
+        unused.sort();
+        unused
+    }
+}
+
+impl Message {
+    /// Get the header referenced by `correlationId` if it is missing from `headers`
+    ///
+    /// Best-effort check: returns `None` when the correlation ID isn't located in a
+    /// header, or when `headers` is a `$ref` that can't be resolved here.
+    pub fn missing_correlation_header(&self) -> Option<&str> {
+        let location = &self.correlation_id.as_ref()?.location;
+        let pointer = location.strip_prefix("$message.header#/")?;
+        let header = pointer.split('/').next().filter(|h| !h.is_empty())?;
+
+        match &self.headers {
+            None => Some(header),
+            Some(Schema::Object(headers)) => {
+                let declared = headers
+                    .properties
+                    .as_ref()
+                    .is_some_and(|props| props.contains_key(header));
+                if declared { None } else { Some(header) }
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Make the payload an object schema or a `$ref`
+    ///
+    /// A boolean payload schema becomes the equivalent object: `true` (any value)
+    /// becomes the empty schema `{}`, and `false` (no value) becomes `{"not": {}}`.
+    /// Object and reference payloads, and a missing payload, are left alone.
+    pub fn normalize_payload(&mut self) {
+        let Some(Schema::Bool(accepts)) = self.payload else {
+            return;
+        };
+        let mut object = SchemaObject::default();
+        if !accepts {
+            object
+                .additional
+                .insert("not".to_string(), serde_json::json!({}));
+        }
+        self.payload = Some(Schema::Object(Box::new(object)));
+    }
+}
+
+impl MessageRef {
+    /// Get the `$ref` path if this is a reference
+    pub fn as_reference(&self) -> Option<&str> {
+        match self {
+            MessageRef::Reference { reference } => Some(reference),
+            MessageRef::Inline(_) => None,
+        }
+    }
+
+    /// Get the message definition if this is an inline message
+    pub fn as_inline(&self) -> Option<&Message> {
+        match self {
+            MessageRef::Reference { .. } => None,
+            MessageRef::Inline(message) => Some(message),
+        }
+    }
+
+    /// Returns `true` if this is a reference
+    pub fn is_reference(&self) -> bool {
+        matches!(self, MessageRef::Reference { .. })
+    }
+
+    /// Returns `true` if this is an inline message
+    pub fn is_inline(&self) -> bool {
+        matches!(self, MessageRef::Inline(_))
+    }
+}
+
+impl Operation {
+    /// Returns `true` if this is a send operation
+    pub fn action_is_send(&self) -> bool {
+        matches!(self.action, OperationAction::Send)
+    }
+
+    /// Returns `true` if this is a receive operation
+    pub fn action_is_receive(&self) -> bool {
+        matches!(self.action, OperationAction::Receive)
+    }
+}
+
+impl Schema {
+    /// Default maximum JSON nesting depth accepted by [`Schema::from_json`]
+    pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+    /// Generate a schema for `T` using schemars
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schemars output cannot be represented as a [`Schema`] or
+    /// nests deeper than [`Schema::DEFAULT_MAX_DEPTH`].
+    #[cfg(feature = "std")]
+    pub fn from_type<T: schemars::JsonSchema>() -> Self {
+        let schema = schemars::schema_for!(T);
+        let schema_json = serde_json::to_value(&schema).expect("Failed to serialize schema");
+        Self::from_json(schema_json).unwrap_or_else(|e| panic!("Failed to convert schema: {}", e))
+    }
+
+    /// Generate an array schema whose items are `T`, e.g. for batch messages
+    ///
+    /// The element schema lands in `$defs` with `items` referencing it, the same
+    /// as [`from_type`](Self::from_type) would produce for `Vec<T>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`from_type`](Self::from_type).
+    #[cfg(feature = "std")]
+    pub fn array_of<T: schemars::JsonSchema>() -> Self {
+        Self::from_type::<Vec<T>>()
+    }
+
+    /// A string schema restricted to `values`, e.g. for a tag discriminator or a
+    /// parameter with a fixed set of values
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::Schema;
+    ///
+    /// let schema = serde_json::to_value(Schema::string_enum(vec!["join", "leave"])).unwrap();
+    /// assert_eq!(schema, serde_json::json!({ "type": "string", "enum": ["join", "leave"] }));
+    /// ```
+    pub fn string_enum(values: Vec<&str>) -> Self {
+        Schema::Object(Box::new(SchemaObject {
+            schema_type: Some(serde_json::Value::String("string".to_string())),
+            enum_values: Some(values.into_iter().map(serde_json::Value::from).collect()),
+            ..SchemaObject::default()
+        }))
+    }
+
+    /// A `{ "type": "string", "format": "binary" }` schema, the JSON Schema
+    /// convention for raw bytes
+    ///
+    /// The derive uses it as the payload of `triggers_binary` messages, since the
+    /// schemars schema of e.g. `Vec<u8>` (an integer array) doesn't describe what
+    /// goes over the wire. `#[asyncapi(binary_payload = "schema")]` keeps the
+    /// schemars schema instead.
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::Schema;
+    ///
+    /// let schema = serde_json::to_value(Schema::binary()).unwrap();
+    /// assert_eq!(schema, serde_json::json!({ "type": "string", "format": "binary" }));
+    /// ```
+    pub fn binary() -> Self {
+        let mut object = SchemaObject {
+            schema_type: Some(serde_json::Value::String("string".to_string())),
+            ..SchemaObject::default()
+        };
+        object
+            .additional
+            .insert("format".to_string(), serde_json::Value::from("binary"));
+        Schema::Object(Box::new(object))
+    }
+
+    /// A schema matching exactly `value`, e.g. the tag of one tagged-enum variant
+    pub fn const_value(value: serde_json::Value) -> Self {
+        Schema::Object(Box::new(SchemaObject {
+            const_value: Some(value),
+            ..SchemaObject::default()
+        }))
+    }
+
+    /// Convert a JSON schema value, rejecting schemas nested deeper than
+    /// [`Schema::DEFAULT_MAX_DEPTH`]
+    pub fn from_json(value: serde_json::Value) -> Result<Self, AsyncApiError> {
+        Self::from_json_with_max_depth(value, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Convert a JSON schema value, rejecting schemas nested deeper than `max_depth`
+    ///
+    /// Depth counts nested JSON objects and arrays. It is checked without recursion
+    /// before deserializing, so pathological schemas return
+    /// [`AsyncApiError::SchemaTooDeep`] instead of overflowing the stack.
+    pub fn from_json_with_max_depth(
+        value: serde_json::Value,
+        max_depth: usize,
+    ) -> Result<Self, AsyncApiError> {
+        if json_depth_exceeds(&value, max_depth) {
+            return Err(AsyncApiError::SchemaTooDeep { max_depth });
+        }
+        serde_json::from_value(value).map_err(|e| AsyncApiError::InvalidSchema(e.to_string()))
+    }
+
+    /// Rewrite every nullable schema in this tree to the given representation
+    ///
+    /// schemars emits `Option<T>` either as `"type": ["T", "null"]` or as
+    /// `"anyOf": [T, {"type": "null"}]` depending on `T`. Normalizing makes the output
+    /// predictable for client generators. Nullable `$ref`s can't be written as a type
+    /// array and are left as `anyOf`. Subschemas under `$defs`/`definitions` are
+    /// normalized too.
+    pub fn normalize_nullable(&mut self, style: NullableStyle) {
+        let Schema::Object(object) = self else {
+            return;
+        };
+
+        // Children first, so merged branches are already normalized
+        let children = object
+            .properties
+            .iter_mut()
+            .flat_map(|props| props.values_mut().map(|s| s.as_mut()))
+            .chain(object.items.as_deref_mut())
+            .chain(object.additional_properties.as_deref_mut())
+            .chain(object.one_of.iter_mut().flatten())
+            .chain(object.any_of.iter_mut().flatten())
+            .chain(object.all_of.iter_mut().flatten());
+        for child in children {
+            child.normalize_nullable(style);
+        }
+        for key in ["$defs", "definitions"] {
+            if let Some(serde_json::Value::Object(defs)) = object.additional.get_mut(key) {
+                for def in defs.values_mut() {
+                    if let Ok(mut schema) = Schema::deserialize(&*def) {
+                        schema.normalize_nullable(style);
+                        if let Ok(normalized) = serde_json::to_value(&schema) {
+                            *def = normalized;
+                        }
+                    }
+                }
+            }
+        }
+
+        match style {
+            NullableStyle::TypeArray => nullable_any_of_to_type_array(object),
+            NullableStyle::AnyOf => nullable_type_array_to_any_of(object),
+        }
+    }
+
+    /// Render a one-line summary of the schema for logs and debugging
+    ///
+    /// Shows the title, type and top-level property names (optional ones marked
+    /// with `?`), but not nested subschemas; `$defs` are only counted. Arrays show
+    /// their item type, and composed schemas the number of alternatives.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::Schema;
+    ///
+    /// let schema = Schema::from_json(serde_json::json!({
+    ///     "title": "Join",
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "room": { "$ref": "#/$defs/Room" },
+    ///         "user": { "type": "string" },
+    ///         "nickname": { "type": ["string", "null"] }
+    ///     },
+    ///     "required": ["room", "user"],
+    ///     "$defs": { "Room": { "type": "object" } }
+    /// }))
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     schema.summary_string(),
+    ///     "Join: object { nickname?, room, user } (+1 $defs)"
+    /// );
+    /// ```
+    pub fn summary_string(&self) -> String {
+        let object = match self {
+            Schema::Object(object) => object,
+            _ => return self.type_label(),
+        };
+
+        let mut summary = match &object.title {
+            Some(title) => format!("{}: {}", title, self.type_label()),
+            None => self.type_label(),
+        };
+        if let Some(properties) = &object.properties {
+            let required = object.required.as_deref().unwrap_or_default();
+            let mut names: Vec<String> = properties
+                .keys()
+                .map(|name| match required.contains(name) {
+                    true => name.clone(),
+                    false => format!("{}?", name),
+                })
+                .collect();
+            names.sort();
+            summary.push_str(&format!(" {{ {} }}", names.join(", ")));
+        }
+        let defs: usize = ["$defs", "definitions"]
+            .iter()
+            .filter_map(|key| object.additional.get(*key))
+            .filter_map(serde_json::Value::as_object)
+            .map(serde_json::Map::len)
+            .sum();
+        if defs > 0 {
+            summary.push_str(&format!(" (+{} $defs)", defs));
+        }
+        summary
+    }
+
+    /// Short label for the kind of schema, without properties, e.g. `array<string>`
+    fn type_label(&self) -> String {
+        let object = match self {
+            Schema::Bool(value) => return value.to_string(),
+            Schema::Reference { reference } => return format!("$ref {}", reference),
+            Schema::Object(object) => object,
+        };
+
+        let mut parts = Vec::new();
+        match &object.schema_type {
+            Some(serde_json::Value::String(schema_type)) => parts.push(schema_type.clone()),
+            Some(serde_json::Value::Array(types)) => parts.push(
+                types
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join("|"),
+            ),
+            _ => {}
+        }
+        if let (Some(first), Some(items)) = (parts.first_mut(), &object.items) {
+            if first == "array" {
+                *first = format!("array<{}>", items.type_label());
+            }
+        }
+        if let Some(serde_json::Value::String(reference)) = object.additional.get("$ref") {
+            parts.push(format!("$ref {}", reference));
+        }
+        for (keyword, schemas) in [
+            ("oneOf", &object.one_of),
+            ("anyOf", &object.any_of),
+            ("allOf", &object.all_of),
+        ] {
+            if let Some(schemas) = schemas {
+                parts.push(format!("{}[{}]", keyword, schemas.len()));
+            }
+        }
+        if let Some(value) = &object.const_value {
+            parts.push(format!("= {}", value));
+        }
+        if let Some(values) = &object.enum_values {
+            parts.push(format!("enum[{}]", values.len()));
+        }
+
+        if parts.is_empty() {
+            "any".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    /// Copy the schema keeping only its first `max_properties` properties, for previews
+    ///
+    /// Properties are kept in sorted key order, so the result is deterministic, and
+    /// `required` is trimmed to match. A truncated schema is marked `x-truncated: true`.
+    /// Only the top level is truncated: kept properties, `$defs` and other subschemas
+    /// are copied as-is. References, boolean schemas and objects with at most
+    /// `max_properties` properties are returned unchanged.
+    pub fn truncated(&self, max_properties: usize) -> Schema {
+        let Schema::Object(object) = self else {
+            return self.clone();
+        };
+        let Some(properties) = object
+            .properties
+            .as_ref()
+            .filter(|p| p.len() > max_properties)
+        else {
+            return self.clone();
+        };
+
+        let mut names: Vec<&String> = properties.keys().collect();
+        names.sort();
+        names.truncate(max_properties);
+
+        let mut object = object.clone();
+        object.properties = Some(
+            names
+                .iter()
+                .map(|name| ((*name).clone(), properties[*name].clone()))
+                .collect(),
+        );
+        if let Some(required) = object.required.as_mut() {
+            required.retain(|name| names.contains(&name));
+        }
+        object
+            .additional
+            .insert("x-truncated".to_string(), serde_json::Value::Bool(true));
+        Schema::Object(object)
+    }
+
+    /// Returns `true` if this is exactly `{"type": "null"}`
+    fn is_null_type(&self) -> bool {
+        matches!(self, Schema::Object(object)
+            if object.schema_type.as_ref().and_then(|t| t.as_str()) == Some("null")
+                && object.properties.is_none()
+                && object.any_of.is_none()
+                && object.one_of.is_none()
+                && object.all_of.is_none())
+    }
+}
+
+/// Representation used for nullable schemas by [`Schema::normalize_nullable`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullableStyle {
+    /// `"type": ["string", "null"]`
+    #[default]
+    TypeArray,
+    /// `"anyOf": [{"type": "string"}, {"type": "null"}]`
+    AnyOf,
+}
+
+/// Merge `anyOf: [{type: T, ...}, {type: null}]` into `{type: [T, null], ...}`
+fn nullable_any_of_to_type_array(object: &mut SchemaObject) {
+    if object.schema_type.is_some() {
+        return;
+    }
+    let Some(any_of) = &object.any_of else {
+        return;
+    };
+    let [first, second] = any_of.as_slice() else {
+        return;
+    };
+    let branch = match (first.is_null_type(), second.is_null_type()) {
+        (false, true) => first,
+        (true, false) => second,
+        _ => return,
+    };
+    let Schema::Object(branch) = branch else {
+        return;
+    };
+    let Some(serde_json::Value::String(branch_type)) = &branch.schema_type else {
+        return;
+    };
+
+    let mut merged = branch.as_ref().clone();
+    merged.schema_type = Some(serde_json::json!([branch_type, "null"]));
+    if let Some(values) = merged.enum_values.as_mut() {
+        if !values.contains(&serde_json::Value::Null) {
+            values.push(serde_json::Value::Null);
+        }
+    }
+    // Annotations on the wrapper (e.g. a field's doc comment) win over the branch's
+    merged.description = object.description.take().or(merged.description);
+    merged.title = object.title.take().or(merged.title);
+    for (key, value) in core::mem::take(&mut object.additional) {
+        merged.additional.insert(key, value);
+    }
+    *object = merged;
+}
+
+/// Split `{type: [T, null], ...}` into `anyOf: [{type: T, ...}, {type: null}]`
+fn nullable_type_array_to_any_of(object: &mut SchemaObject) {
+    let Some(serde_json::Value::Array(types)) = &object.schema_type else {
+        return;
+    };
+    let non_null: Vec<&serde_json::Value> = types.iter().filter(|t| *t != "null").collect();
+    let [branch_type] = non_null.as_slice() else {
+        return;
+    };
+    if types.len() != 2 {
+        return;
+    }
+    let branch_type = (*branch_type).clone();
+
+    let mut branch = core::mem::take(object);
+    branch.schema_type = Some(branch_type);
+    if let Some(values) = branch.enum_values.as_mut() {
+        values.retain(|value| !value.is_null());
+    }
+    // Annotations stay on the wrapper
+    object.description = branch.description.take();
+    object.title = branch.title.take();
+    object.any_of = Some(vec![
+        Schema::Object(Box::new(branch)),
+        Schema::Object(Box::new(SchemaObject {
+            schema_type: Some(serde_json::json!("null")),
+            ..SchemaObject::default()
+        })),
+    ]);
+}
+
+/// Returns `true` if `value` nests objects/arrays deeper than `max_depth`
+fn json_depth_exceeds(value: &serde_json::Value, max_depth: usize) -> bool {
+    let mut stack = vec![(value, 1)];
+    while let Some((value, depth)) = stack.pop() {
+        let children: Box<dyn Iterator<Item = &serde_json::Value>> = match value {
+            serde_json::Value::Object(map) => Box::new(map.values()),
+            serde_json::Value::Array(items) => Box::new(items.iter()),
+            _ => continue,
+        };
+        if depth > max_depth {
+            return true;
+        }
+        stack.extend(children.map(|child| (child, depth + 1)));
+    }
+    false
+}
+
+/// Collect map entries sorted by key
+fn sorted_entries<'a, T>(
+    entries: impl Iterator<Item = (&'a String, &'a T)>,
+) -> Vec<(&'a String, &'a T)> {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn sorted_names<'a>(names: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+    let mut names: Vec<_> = names.map(String::as_str).collect();
+    names.sort_unstable();
+    names
+}
+
+impl Components {
+    /// Returns `true` if no component maps are populated
+    ///
+    /// An empty `Components` object serializes as `{}`, which some strict
+    /// validators reject. Callers should emit `None` instead when this is `true`.
+    pub fn is_empty(&self) -> bool {
+        self.messages.as_ref().is_none_or(|m| m.is_empty())
+            && self.schemas.as_ref().is_none_or(|s| s.is_empty())
+            && self.security_schemes.as_ref().is_none_or(|s| s.is_empty())
+            && self.replies.as_ref().is_none_or(|r| r.is_empty())
+            && self.reply_addresses.as_ref().is_none_or(|a| a.is_empty())
+            && self.channel_bindings.as_ref().is_none_or(|b| b.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_spec_serialization() {
+        let spec = AsyncApiSpec::default();
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(json.contains("asyncapi"));
+        assert!(json.contains("3.0.0"));
+    }
+
+    #[test]
+    fn test_spec_deserialization() {
+        let json = r#"{
+            "asyncapi": "3.0.0",
+            "info": {
+                "title": "Test API",
+                "version": "1.0.0"
+            }
+        }"#;
+        let spec: AsyncApiSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.asyncapi, "3.0.0");
+        assert_eq!(spec.info.title, "Test API");
+    }
+
+    #[test]
+    fn test_components_is_empty() {
+        let empty = Components {
+            messages: None,
+            schemas: Some(HashMap::new()),
+            security_schemes: None,
+            replies: None,
+            reply_addresses: None,
+            channel_bindings: None,
+        };
+        assert!(empty.is_empty());
+
+        let mut messages = HashMap::new();
+        messages.insert(
+            "Ping".to_string(),
+            Message {
+                name: Some("Ping".to_string()),
+                title: None,
+                summary: None,
+                description: None,
+                content_type: None,
+                headers: None,
+                correlation_id: None,
+                payload: None,
+                tags: None,
+                external_docs: None,
+                extensions: HashMap::new(),
+            },
+        );
+        let populated = Components {
+            messages: Some(messages),
+            schemas: None,
+            security_schemes: None,
+            replies: None,
+            reply_addresses: None,
+            channel_bindings: None,
+        };
+        assert!(!populated.is_empty());
+    }
+
+    fn test_server(host: &str, protocol: &str) -> Server {
+        Server {
+            host: host.to_string(),
+            protocol: protocol.to_string(),
+            pathname: None,
+            description: None,
+            variables: None,
+            security: None,
+        }
+    }
+
+    #[test]
+    fn test_servers_by_protocol() {
+        let mut servers = HashMap::new();
+        servers.insert("prod".to_string(), test_server("api.example.com", "wss"));
+        servers.insert("dev".to_string(), test_server("localhost:8080", "ws"));
+        servers.insert(
+            "staging".to_string(),
+            test_server("staging.example.com", "wss"),
+        );
+        let spec = AsyncApiSpec {
+            servers: Some(servers),
+            ..AsyncApiSpec::default()
+        };
+
+        let secure: Vec<&str> = spec
+            .servers_by_protocol("wss")
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(secure, vec!["prod", "staging"]);
+        assert!(spec.servers_by_protocol("kafka").is_empty());
+        assert!(
+            AsyncApiSpec::default()
+                .servers_by_protocol("wss")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_sorted_accessors() {
+        let mut spec = rename_test_spec();
+        spec.channels
+            .as_mut()
+            .unwrap()
+            .insert("alerts".to_string(), Channel::default());
+        let ack = spec.operations.as_ref().unwrap()["sendPing"].clone();
+        // Appended last, so only sorting puts it first
+        spec.operations
+            .as_mut()
+            .unwrap()
+            .insert("ackAlert".to_string(), ack);
+        let messages = spec
+            .components
+            .as_mut()
+            .and_then(|c| c.messages.as_mut())
+            .unwrap();
+        let ping = messages["Ping"].clone();
+        messages.insert("Pong".to_string(), ping.clone());
+        messages.insert("Chat".to_string(), ping);
+
+        fn keys<T>(entries: Vec<(&String, &T)>) -> Vec<String> {
+            entries.into_iter().map(|(name, _)| name.clone()).collect()
+        }
+        assert_eq!(
+            keys(spec.channels_sorted()),
+            vec!["alerts", "chat", "chatRoom"]
+        );
+        assert_eq!(
+            keys(spec.operations_sorted()),
+            vec!["ackAlert", "sendPing", "sendRoomPing"]
+        );
+        assert_eq!(keys(spec.messages_sorted()), vec!["Chat", "Ping", "Pong"]);
+        assert!(AsyncApiSpec::default().messages_sorted().is_empty());
+    }
+
+    #[test]
+    fn test_name_accessors() {
+        let spec = rename_test_spec()
+            .with_server("prod", test_server("api.example.com", "wss"))
+            .with_server("dev", test_server("localhost:8080", "ws"))
+            .with_channel("alerts", Channel::default());
+
+        assert_eq!(spec.server_names(), vec!["dev", "prod"]);
+        assert_eq!(spec.channel_names(), vec!["alerts", "chat", "chatRoom"]);
+        assert_eq!(spec.operation_names(), vec!["sendPing", "sendRoomPing"]);
+
+        let empty = AsyncApiSpec::default();
+        assert!(empty.server_names().is_empty());
+        assert!(empty.channel_names().is_empty());
+        assert!(empty.operation_names().is_empty());
+    }
+
+    #[test]
+    fn test_with_server_and_channel() {
+        let spec = AsyncApiSpec::default()
+            .with_server("prod", test_server("api.example.com", "wss"))
+            .with_server("prod", test_server("api.example.com:8443", "wss"))
+            .with_server("dev", test_server("localhost:8080", "ws"))
+            .with_channel("chat", Channel::default());
+
+        let servers = spec.servers.unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers["prod"].host, "api.example.com:8443");
+        assert!(spec.channels.unwrap().contains_key("chat"));
+    }
+
+    #[test]
+    fn test_check_server_host() {
+        for host in [
+            "api.example.com",
+            "api.example.com:8443",
+            "localhost:9092",
+            "[::1]:8080",
+            "[::1]",
+            "localhost:{port}",
+        ] {
+            assert!(check_server_host(host, "wss").is_ok(), "{}", host);
+        }
+
+        let err = check_server_host("http://api.example.com", "wss").unwrap_err();
+        assert!(err.contains("conflicts with protocol 'wss'"));
+        let err = check_server_host("wss://api.example.com", "wss").unwrap_err();
+        assert!(err.contains("includes the scheme 'wss://'"));
+        let err = check_server_host("api.example.com/ws", "wss").unwrap_err();
+        assert!(err.contains("move it to pathname"));
+        let err = check_server_host("api.example.com:https", "wss").unwrap_err();
+        assert!(err.contains("invalid port 'https'"));
+        assert!(check_server_host("", "wss").is_err());
+    }
+
+    #[test]
+    fn test_server_port() {
+        let server = |host: &str, protocol: &str| Server {
+            host: host.to_string(),
+            protocol: protocol.to_string(),
+            pathname: None,
+            description: None,
+            variables: None,
+            security: None,
+        };
+        assert_eq!(server("api.example.com", "wss").port(), Some(443));
+        assert_eq!(server("api.example.com:8080", "wss").port(), Some(8080));
+        assert_eq!(server("[::1]:9093", "kafka").port(), Some(9093));
+        assert_eq!(server("broker", "kafka").port(), Some(9092));
+        assert_eq!(server("api.example.com", "custom").port(), None);
+    }
+
+    #[test]
+    fn test_server_check_variables() {
+        let variable = || ServerVariable {
+            description: None,
+            default: None,
+            enum_values: None,
+            examples: None,
+        };
+        let server = |host: &str, pathname: Option<&str>, variables: &[&str]| Server {
+            host: host.to_string(),
+            protocol: "wss".to_string(),
+            pathname: pathname.map(str::to_string),
+            description: None,
+            variables: (!variables.is_empty()).then(|| {
+                variables
+                    .iter()
+                    .map(|name| (name.to_string(), variable()))
+                    .collect()
+            }),
+            security: None,
+        };
+
+        let matching = server("api.example.com", Some("/ws/{userId}"), &["userId"]);
+        assert_eq!(matching.variables_in_pathname(), vec!["userId"]);
+        assert!(matching.check_variables().is_empty());
+
+        let repeated = server("api.example.com", Some("/{org}/{room}/{org}"), &[]);
+        assert_eq!(repeated.variables_in_pathname(), vec!["org", "room"]);
+        assert_eq!(repeated.check_variables().undefined, vec!["org", "room"]);
+
+        let mismatched = server(
+            "{region}.example.com",
+            Some("/ws/{userId}/{room}"),
+            &["userId", "tenant", "region", "env"],
+        );
+        assert_eq!(
+            mismatched.check_variables(),
+            VariableCheck {
+                unused: vec!["env".to_string(), "tenant".to_string()],
+                undefined: vec!["room".to_string()],
+            }
+        );
+
+        let no_pathname = server("api.example.com", None, &["userId"]);
+        assert!(no_pathname.variables_in_pathname().is_empty());
+        assert_eq!(no_pathname.check_variables().unused, vec!["userId"]);
+
+        // Unclosed and empty braces aren't placeholders
+        assert_eq!(placeholders("/ws/{}/{open"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_is_known_protocol() {
+        assert!(is_known_protocol("wss"));
+        assert!(is_known_protocol("kafka"));
+        assert!(!is_known_protocol("websocket"));
+        assert!(!is_known_protocol("WSS"));
+    }
+
+    #[test]
+    fn test_channel_display_title() {
+        let mut channel = Channel::default();
+        assert_eq!(channel.display_title("chat"), "chat");
+
+        channel.title = Some("Chat Room".to_string());
+        assert_eq!(channel.display_title("chat"), "Chat Room");
+    }
+
+    #[test]
+    fn test_channel_lazy_init_accessors() {
+        let mut channel = Channel::default();
+        channel.messages_mut().insert(
+            "Ping".to_string(),
+            MessageRef::Reference {
+                reference: "#/components/messages/Ping".to_string(),
+            },
+        );
+        channel.messages_mut().insert(
+            "Pong".to_string(),
+            MessageRef::Reference {
+                reference: "#/components/messages/Pong".to_string(),
+            },
+        );
+        assert_eq!(channel.messages.as_ref().unwrap().len(), 2);
+
+        channel.parameters_mut().insert(
+            "roomId".to_string(),
+            Parameter {
+                description: Some("Room ID".to_string()),
+                ..Parameter::default()
+            },
+        );
+        assert!(channel.parameters.as_ref().unwrap().contains_key("roomId"));
+
+        // Existing maps are reused rather than replaced
+        channel.messages_mut().remove("Ping");
+        assert_eq!(
+            channel.messages.unwrap().keys().collect::<Vec<_>>(),
+            vec!["Pong"]
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_round_trip() {
+        let spec = rename_test_spec();
+        let yaml = spec.to_yaml().unwrap();
+        assert!(yaml.contains("$ref: '#/channels/chat'"));
+        assert!(!yaml.contains("reference:"));
+
+        assert_eq!(AsyncApiSpec::from_yaml(&yaml).unwrap(), spec);
+        assert!(AsyncApiSpec::from_yaml("asyncapi: [").is_err());
+    }
+
+    #[cfg(feature = "validate-schema")]
+    #[test]
+    fn test_validate_schema_rejects_broken_spec() {
+        let mut spec = AsyncApiSpec::default();
+        assert!(spec.validate_schema().is_ok());
+
+        spec.asyncapi = "2.6.0".to_string();
+        let errors = spec.validate_schema().unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("/asyncapi")));
+    }
+
+    #[cfg(feature = "validate-schema")]
+    #[test]
+    fn test_message_validate_payload() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Room {
+            name: String,
+        }
+
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Join {
+            username: String,
+            room: Room,
+        }
+
+        let mut message = Message {
+            name: Some("join".to_string()),
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: None,
+            correlation_id: None,
+            payload: Some(Schema::from_type::<Join>()),
+            tags: None,
+            external_docs: None,
+            extensions: HashMap::new(),
+        };
+
+        let valid = serde_json::json!({ "username": "alice", "room": { "name": "lobby" } });
+        assert!(message.validate_payload(&valid).is_ok());
+
+        // Errors point into the payload, including through `#/$defs` refs
+        let invalid = serde_json::json!({ "username": 42, "room": {} });
+        let errors = message.validate_payload(&invalid).unwrap_err();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.starts_with("/username:")));
+        assert!(errors.iter().any(|e| e.starts_with("/room:")));
+
+        // Refs into the spec's components
+        message.payload = Some(
+            Schema::from_json(serde_json::json!({ "$ref": "#/components/schemas/Room" })).unwrap(),
+        );
+        let spec = AsyncApiSpec {
+            components: Some(Components {
+                schemas: Some(HashMap::from([(
+                    "Room".to_string(),
+                    Schema::from_type::<Room>(),
+                )])),
+                ..Components::default()
+            }),
+            ..AsyncApiSpec::default()
+        };
+        let room = serde_json::json!({ "name": "lobby" });
+        assert!(message.validate_payload_in(&spec, &room).is_ok());
+
+        // A compiled validator checks any number of payloads
+        let validator = message.payload_validator(&spec).unwrap();
+        assert!(validator.validate(&room).is_ok());
+        let errors = validator
+            .validate(&serde_json::json!({ "name": 1 }))
+            .unwrap_err();
+        assert!(errors[0].starts_with("/name:"), "{:?}", errors);
+        assert!(
+            message
+                .validate_payload_in(&spec, &serde_json::json!({ "name": 1 }))
+                .is_err()
+        );
+        assert!(message.validate_payload(&room).is_err());
+
+        // No payload schema, nothing to enforce
+        message.payload = None;
+        let validator = message.payload_validator(&spec).unwrap();
+        assert!(validator.validate(&serde_json::json!(42)).is_ok());
+        assert!(
+            message
+                .validate_payload(&serde_json::json!("anything"))
+                .is_ok()
+        );
+    }
+
+    #[cfg(feature = "validate-schema")]
+    #[test]
+    fn test_validate_payload_schema_formats() {
+        let message = |payload: Schema| Message {
+            name: Some("ping".to_string()),
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: None,
+            correlation_id: None,
+            payload: Some(payload),
+            tags: None,
+            external_docs: None,
+            extensions: HashMap::new(),
+        };
+        let anything = serde_json::json!({ "not": ["the", "documented", "shape"] });
+
+        // Avro and Protobuf schemas aren't JSON Schema, so payloads aren't accepted
+        // just because their keywords are unknown
+        let avro = message(Schema::multi_format(
+            AVRO_SCHEMA_FORMAT,
+            serde_json::json!({
+                "type": "record",
+                "name": "Ping",
+                "fields": [{ "name": "ts", "type": "long" }]
+            }),
+        ));
+        let protobuf = message(Schema::multi_format(
+            PROTOBUF_SCHEMA_FORMAT,
+            "syntax = \"proto3\"; message Ping { int64 ts = 1; }".into(),
+        ));
+        for message in [avro, protobuf] {
+            let errors = message.validate_payload(&anything).unwrap_err();
+            assert!(
+                errors[0].starts_with("unsupported payload schemaFormat"),
+                "{:?}",
+                errors
+            );
+            assert!(message.payload_validator(&AsyncApiSpec::default()).is_err());
+        }
+
+        // A JSON Schema in a Multi Format Schema Object checks the inner schema
+        let json_schema = message(Schema::multi_format(
+            "application/schema+json;version=draft-07",
+            serde_json::json!({ "type": "object", "required": ["ts"] }),
+        ));
+        assert!(json_schema.validate_payload(&anything).is_err());
+        assert!(
+            json_schema
+                .validate_payload(&serde_json::json!({ "ts": 1 }))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_missing_correlation_header() {
+        let headers: Schema = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": { "requestId": { "type": "string" } }
+        }))
+        .unwrap();
+        let mut message = Message {
+            name: Some("Request".to_string()),
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: Some(headers),
+            correlation_id: Some(CorrelationId {
+                description: None,
+                location: "$message.header#/requestId".to_string(),
+            }),
+            payload: None,
+            tags: None,
+            external_docs: None,
+            extensions: HashMap::new(),
+        };
+        assert_eq!(message.missing_correlation_header(), None);
+
+        message.correlation_id.as_mut().unwrap().location = "$message.header#/traceId".to_string();
+        assert_eq!(message.missing_correlation_header(), Some("traceId"));
+
+        message.headers = None;
+        assert_eq!(message.missing_correlation_header(), Some("traceId"));
+
+        // Payload locations are not checked against headers
+        message.correlation_id.as_mut().unwrap().location = "$message.payload#/id".to_string();
+        assert_eq!(message.missing_correlation_header(), None);
+    }
+
+    #[test]
+    fn test_message_normalize_payload() {
+        let normalized = |payload: Option<Schema>| {
+            let mut message = Message {
+                name: Some("Ping".to_string()),
+                title: None,
+                summary: None,
+                description: None,
+                content_type: None,
+                headers: None,
+                correlation_id: None,
+                payload,
+                tags: None,
+                external_docs: None,
+                extensions: HashMap::new(),
+            };
+            message.normalize_payload();
+            message.payload.map(|p| serde_json::to_value(p).unwrap())
+        };
+
+        assert_eq!(
+            normalized(Some(Schema::Bool(true))),
+            Some(serde_json::json!({}))
+        );
+        assert_eq!(
+            normalized(Some(Schema::Bool(false))),
+            Some(serde_json::json!({ "not": {} }))
+        );
+        assert_eq!(
+            normalized(Some(Schema::Reference {
+                reference: "#/components/schemas/Ping".to_string(),
+            })),
+            Some(serde_json::json!({ "$ref": "#/components/schemas/Ping" }))
+        );
+        assert_eq!(
+            normalized(Some(Schema::string_enum(vec!["ping"]))),
+            Some(serde_json::json!({ "type": "string", "enum": ["ping"] }))
+        );
+        assert_eq!(normalized(None), None);
+    }
+
+    #[test]
+    fn test_operation_action_predicates() {
+        let mut operation = Operation {
+            action: OperationAction::Send,
+            channel: ChannelRef {
+                reference: "#/channels/chat".to_string(),
+            },
+            title: None,
+            summary: None,
+            description: None,
+            messages: None,
+            reply: None,
+            tags: None,
+            external_docs: None,
+            security: None,
+            bindings: None,
+            extensions: HashMap::new(),
+        };
+        assert!(operation.action_is_send());
+        assert!(!operation.action_is_receive());
+
+        operation.action = OperationAction::Receive;
+        assert!(!operation.action_is_send());
+        assert!(operation.action_is_receive());
+    }
+
+    #[test]
+    fn test_message_ref_accessors() {
+        let reference = MessageRef::Reference {
+            reference: "#/components/messages/Ping".to_string(),
+        };
+        assert!(reference.is_reference());
+        assert!(!reference.is_inline());
+        assert_eq!(reference.as_reference(), Some("#/components/messages/Ping"));
+        assert!(reference.as_inline().is_none());
+
+        let inline = MessageRef::Inline(Box::new(Message {
+            name: Some("Ping".to_string()),
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: None,
+            correlation_id: None,
+            payload: None,
+            tags: None,
+            external_docs: None,
+            extensions: HashMap::new(),
+        }));
+        assert!(inline.is_inline());
+        assert!(!inline.is_reference());
+        assert!(inline.as_reference().is_none());
+        assert_eq!(inline.as_inline().unwrap().name, Some("Ping".to_string()));
+    }
+
+    #[test]
+    fn test_message_ref_deserialize_ambiguous_objects() {
+        // Stray keys next to `$ref` are ignored rather than turning it into a message
+        let with_siblings: MessageRef = serde_json::from_value(serde_json::json!({
+            "$ref": "#/components/messages/Ping",
+            "name": "Ping",
+            "summary": "Ignored"
+        }))
+        .unwrap();
+        assert_eq!(
+            with_siblings.as_reference(),
+            Some("#/components/messages/Ping")
+        );
+
+        let inline: MessageRef = serde_json::from_value(serde_json::json!({
+            "name": "Ping",
+            "contentType": "application/json"
+        }))
+        .unwrap();
+        assert_eq!(inline.as_inline().unwrap().name, Some("Ping".to_string()));
+
+        // Previously this silently became an empty inline message
+        let err =
+            serde_json::from_value::<MessageRef>(serde_json::json!({ "$ref": 42 })).unwrap_err();
+        assert!(err.to_string().contains("`$ref` to be a string"));
+        assert!(serde_json::from_value::<MessageRef>(serde_json::json!("Ping")).is_err());
+    }
+
+    #[test]
+    fn test_security_schemes_round_trip() {
+        let json = serde_json::json!({
+            "messages": {},
+            "securitySchemes": {
+                "apiKey": { "type": "httpApiKey", "name": "token", "in": "query" }
+            }
+        });
+        let components: Components = serde_json::from_value(json.clone()).unwrap();
+        let api_key = &components.security_schemes.as_ref().unwrap()["apiKey"];
+        assert_eq!(api_key.scheme_type, "httpApiKey");
+        assert_eq!(api_key.additional["in"], "query");
+        assert!(!components.is_empty());
+        assert_eq!(serde_json::to_value(&components).unwrap(), json);
+
+        let security: Vec<SecuritySchemeRef> = serde_json::from_value(serde_json::json!([
+            { "$ref": "#/components/securitySchemes/apiKey" },
+            { "type": "http", "scheme": "bearer" }
+        ]))
+        .unwrap();
+        assert!(matches!(security[0], SecuritySchemeRef::Reference { .. }));
+        assert!(matches!(security[1], SecuritySchemeRef::Inline(_)));
+    }
+
+    #[test]
+    fn test_schema_array_of() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        enum ChatEvent {
+            Joined { user: String },
+            Left { user: String },
+        }
+
+        let schema = serde_json::to_value(Schema::array_of::<ChatEvent>()).unwrap();
+        assert_eq!(schema["type"], "array");
+        assert_eq!(schema["items"]["$ref"], "#/$defs/ChatEvent");
+        assert!(schema["$defs"]["ChatEvent"]["oneOf"].is_array());
+    }
+
+    #[test]
+    fn test_operation_action_round_trip() {
+        for (action, text) in [
+            (OperationAction::Send, "send"),
+            (OperationAction::Receive, "receive"),
+        ] {
+            let json = serde_json::to_value(&action).unwrap();
+            assert_eq!(json, serde_json::json!(text));
+            assert_eq!(action.to_string(), text);
+            assert_eq!(action.as_str(), text);
+            let round_trip: OperationAction = serde_json::from_value(json).unwrap();
+            assert_eq!(round_trip, action);
+        }
+        assert!(serde_json::from_value::<OperationAction>(serde_json::json!("Send")).is_err());
+    }
+
+    #[test]
+    fn test_schema_string_enum() {
+        let schema = Schema::string_enum(vec!["join", "leave"]);
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap(),
+            serde_json::json!({ "type": "string", "enum": ["join", "leave"] })
+        );
+        assert_eq!(schema.summary_string(), "string enum[2]");
+
+        let round_trip: Schema =
+            serde_json::from_value(serde_json::to_value(&schema).unwrap()).unwrap();
+        assert_eq!(round_trip, schema);
+    }
+
+    #[test]
+    fn test_schema_binary() {
+        let schema = Schema::binary();
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap(),
+            serde_json::json!({ "type": "string", "format": "binary" })
+        );
+
+        let round_trip: Schema =
+            serde_json::from_value(serde_json::json!({ "type": "string", "format": "binary" }))
+                .unwrap();
+        assert_eq!(round_trip, schema);
+    }
+
+    #[test]
+    fn test_schema_const_value() {
+        let schema = Schema::const_value(serde_json::json!("Closed"));
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap(),
+            serde_json::json!({ "const": "Closed" })
+        );
+
+        let schema = Schema::const_value(serde_json::json!({ "version": 2 }));
+        let Schema::Object(object) = &schema else {
+            panic!("Expected schema object");
+        };
+        assert_eq!(
+            object.const_value,
+            Some(serde_json::json!({ "version": 2 }))
+        );
+        assert!(object.schema_type.is_none());
+    }
+
+    #[test]
+    fn test_schema_truncated() {
+        let properties: serde_json::Map<String, serde_json::Value> = (0..20)
+            .map(|i| {
+                (
+                    format!("field{:02}", i),
+                    serde_json::json!({ "type": "integer" }),
+                )
+            })
+            .collect();
+        let schema = Schema::from_json(serde_json::json!({
+            "title": "Wide",
+            "type": "object",
+            "properties": properties,
+            "required": ["field01", "field05", "field19"]
+        }))
+        .unwrap();
+
+        let preview = serde_json::to_value(schema.truncated(3)).unwrap();
+        let mut kept: Vec<&String> = preview["properties"].as_object().unwrap().keys().collect();
+        kept.sort();
+        assert_eq!(kept, ["field00", "field01", "field02"]);
+        assert_eq!(preview["required"], serde_json::json!(["field01"]));
+        assert_eq!(preview["x-truncated"], true);
+        assert_eq!(preview["title"], "Wide");
+
+        // Same result every time, and narrow schemas are left alone
+        assert_eq!(schema.truncated(3), schema.truncated(3));
+        assert_eq!(schema.truncated(20), schema);
+        let reference = Schema::Reference {
+            reference: "#/components/schemas/Wide".to_string(),
+        };
+        assert_eq!(reference.truncated(0), reference);
+    }
+
+    #[test]
+    fn test_schema_summary_string() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Room {
+            id: u64,
+            members: Vec<Member>,
+        }
+
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Member {
+            name: String,
+            nickname: Option<String>,
+        }
+
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        #[serde(tag = "type")]
+        enum RoomEvent {
+            Created { room: Room },
+            Closed { id: u64, reason: Option<String> },
+        }
+
+        assert_eq!(
+            Schema::from_type::<Room>().summary_string(),
+            "Room: object { id, members } (+1 $defs)"
+        );
+        assert_eq!(
+            Schema::from_type::<RoomEvent>().summary_string(),
+            "RoomEvent: oneOf[2] (+2 $defs)"
+        );
+        assert_eq!(
+            Schema::array_of::<Member>().summary_string(),
+            "Array_of_Member: array<$ref #/$defs/Member> (+1 $defs)"
+        );
+
+        let closed = Schema::from_json(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "type": { "type": "string", "const": "Closed" },
+                "reason": { "type": ["string", "null"] }
+            },
+            "required": ["type"]
+        }))
+        .unwrap();
+        assert_eq!(closed.summary_string(), "object { reason?, type }");
+        let Schema::Object(object) = &closed else {
+            panic!("Expected schema object");
+        };
+        let properties = object.properties.as_ref().unwrap();
+        assert_eq!(properties["type"].summary_string(), "string = \"Closed\"");
+        assert_eq!(properties["reason"].summary_string(), "string|null");
+
+        assert_eq!(Schema::Bool(true).summary_string(), "true");
+        assert_eq!(
+            Schema::Reference {
+                reference: "#/components/schemas/Room".to_string()
+            }
+            .summary_string(),
+            "$ref #/components/schemas/Room"
+        );
+        assert_eq!(
+            Schema::from_json(serde_json::json!({}))
+                .unwrap()
+                .summary_string(),
+            "any"
+        );
+    }
+
+    #[test]
+    fn test_schema_keeps_non_string_ref_verbatim() {
+        // Unlike a MessageRef, any object is a valid schema; an odd `$ref` survives
+        // in `additional` rather than being turned into a reference
+        let json = serde_json::json!({ "$ref": 42, "type": "object" });
+        let schema: Schema = serde_json::from_value(json.clone()).unwrap();
+        let Schema::Object(object) = &schema else {
+            panic!("Expected schema object");
+        };
+        assert_eq!(object.additional["$ref"], 42);
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn test_schema_object_read_write_only_round_trip() {
+        let json = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer", "readOnly": true },
+                "password": { "type": "string", "writeOnly": true }
+            }
+        });
+
+        let schema: Schema = serde_json::from_value(json.clone()).unwrap();
+        let Schema::Object(object) = &schema else {
+            panic!("Expected schema object");
+        };
+        let properties = object.properties.as_ref().unwrap();
+        let Schema::Object(id) = properties["id"].as_ref() else {
+            panic!("Expected schema object");
+        };
+        assert_eq!(id.read_only, Some(true));
+        assert_eq!(id.write_only, None);
+        assert!(!id.additional.contains_key("readOnly"));
+        let Schema::Object(password) = properties["password"].as_ref() else {
+            panic!("Expected schema object");
+        };
+        assert_eq!(password.write_only, Some(true));
+        assert!(!password.additional.contains_key("writeOnly"));
+
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn test_schema_reference_with_siblings_keeps_description() {
+        let bare: Schema = serde_json::from_value(serde_json::json!({
+            "$ref": "#/$defs/Room"
+        }))
+        .unwrap();
+        assert!(matches!(bare, Schema::Reference { ref reference } if reference == "#/$defs/Room"));
+
+        let json = serde_json::json!({
+            "$ref": "#/$defs/Room",
+            "description": "The room"
+        });
+        let documented: Schema = serde_json::from_value(json.clone()).unwrap();
+        let Schema::Object(object) = &documented else {
+            panic!("Expected schema object");
+        };
+        assert_eq!(object.description, Some("The room".to_string()));
+        assert_eq!(serde_json::to_value(&documented).unwrap(), json);
+    }
+
+    fn rename_test_spec() -> AsyncApiSpec {
+        let message_ref = |reference: &str| MessageRef::Reference {
+            reference: reference.to_string(),
+        };
+        let channel = |messages: Vec<(&str, &str)>| Channel {
+            messages: Some(
+                messages
+                    .into_iter()
+                    .map(|(name, reference)| (name.to_string(), message_ref(reference)))
+                    .collect(),
+            ),
+            ..Channel::default()
+        };
+        let operation = |channel: &str, messages: Vec<&str>| Operation {
+            action: OperationAction::Send,
+            channel: ChannelRef {
+                reference: channel.to_string(),
+            },
+            title: None,
+            summary: None,
+            description: None,
+            messages: Some(messages.into_iter().map(message_ref).collect()),
+            reply: None,
+            tags: None,
+            external_docs: None,
+            security: None,
+            bindings: None,
+            extensions: HashMap::new(),
+        };
+        let message = Message {
+            name: Some("Ping".to_string()),
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: None,
+            correlation_id: None,
+            payload: None,
+            tags: None,
+            external_docs: None,
+            extensions: HashMap::new(),
+        };
+
+        AsyncApiSpec {
+            channels: Some(HashMap::from([
+                (
+                    "chat".to_string(),
+                    channel(vec![("Ping", "#/components/messages/Ping")]),
+                ),
+                (
+                    "chatRoom".to_string(),
+                    channel(vec![("Ping", "#/components/messages/Ping")]),
+                ),
+            ])),
+            operations: Some(IndexMap::from([
+                (
+                    "sendPing".to_string(),
+                    operation("#/channels/chat", vec!["#/channels/chat/messages/Ping"]),
+                ),
+                (
+                    "sendRoomPing".to_string(),
+                    operation(
+                        "#/channels/chatRoom",
+                        vec!["#/channels/chatRoom/messages/Ping"],
+                    ),
+                ),
+            ])),
+            components: Some(Components {
+                messages: Some(HashMap::from([("Ping".to_string(), message)])),
+                schemas: None,
+                security_schemes: None,
+                replies: None,
+                reply_addresses: None,
+                channel_bindings: None,
+            }),
+            ..AsyncApiSpec::default()
+        }
+    }
+
+    fn tag(name: &str, description: Option<&str>) -> Tag {
+        Tag {
+            name: name.to_string(),
+            description: description.map(str::to_string),
+            external_docs: None,
+        }
+    }
+
+    #[test]
+    fn test_add_tag_dedupes_by_name() {
+        let mut spec = AsyncApiSpec::default();
+        assert!(spec.add_tag(tag("chat", Some("Chat"))));
+        assert!(spec.add_tag(tag("admin", None)));
+        assert!(!spec.add_tag(tag("chat", Some("Other"))));
+
+        let tags = spec.info.tags.unwrap();
+        assert_eq!(tags, vec![tag("chat", Some("Chat")), tag("admin", None)]);
+    }
+
+    #[test]
+    fn test_tag_external_docs_serialization() {
+        let mut spec = AsyncApiSpec::default();
+        spec.add_tag(Tag {
+            external_docs: Some(ExternalDocumentation {
+                description: Some("Chat guide".to_string()),
+                url: "https://example.com/chat".to_string(),
+            }),
+            ..tag("chat", Some("Chat"))
+        });
+        spec.add_tag(tag("admin", None));
+
+        let json = serde_json::to_value(&spec).unwrap();
+        assert_eq!(
+            json["info"]["tags"],
+            serde_json::json!([
+                {
+                    "name": "chat",
+                    "description": "Chat",
+                    "externalDocs": {
+                        "description": "Chat guide",
+                        "url": "https://example.com/chat"
+                    }
+                },
+                { "name": "admin" }
+            ])
+        );
+        assert!(json.get("tags").is_none());
+
+        let parsed = AsyncApiSpec::try_from(json).unwrap();
+        assert_eq!(parsed.info.tags, spec.info.tags);
+    }
+
+    #[test]
+    fn test_normalize_tags_declares_used_tags() {
+        let mut spec = rename_test_spec();
+        spec.add_tag(tag("chat", Some("Declared")));
+        spec.channels
+            .as_mut()
+            .unwrap()
+            .get_mut("chat")
+            .unwrap()
+            .tags = Some(vec![tag("chat", Some("Channel")), tag("rooms", None)]);
+        spec.operations.as_mut().unwrap()["sendPing"].tags = Some(vec![tag("ping", None)]);
+        spec.operations.as_mut().unwrap()["sendRoomPing"].tags =
+            Some(vec![tag("rooms", Some("Room channels"))]);
+        let components = spec.components.as_mut().unwrap();
+        components
+            .messages
+            .as_mut()
+            .unwrap()
+            .get_mut("Ping")
+            .unwrap()
+            .tags = Some(vec![tag("heartbeat", None), tag("ping", None)]);
+
+        assert_eq!(spec.normalize_tags(), 3);
+        assert_eq!(
+            spec.info.tags.as_ref().unwrap(),
+            &vec![
+                tag("chat", Some("Declared")),
+                tag("heartbeat", None),
+                tag("ping", None),
+                tag("rooms", Some("Room channels")),
+            ]
+        );
+
+        // Nothing left to declare on a second pass
+        assert_eq!(spec.normalize_tags(), 0);
+    }
+
+    #[test]
+    fn test_rename_channel_rewrites_refs() {
+        let mut spec = rename_test_spec();
+        assert!(spec.rename_channel("chat", "lobby"));
+
+        let channels = spec.channels.as_ref().unwrap();
+        assert!(channels.contains_key("lobby"));
+        assert!(!channels.contains_key("chat"));
+
+        let operations = spec.operations.as_ref().unwrap();
+        let send_ping = &operations["sendPing"];
+        assert_eq!(send_ping.channel.reference, "#/channels/lobby");
+        assert_eq!(
+            send_ping.messages.as_ref().unwrap()[0].as_reference(),
+            Some("#/channels/lobby/messages/Ping")
+        );
+
+        // Channels that merely share a prefix are untouched
+        let send_room_ping = &operations["sendRoomPing"];
+        assert_eq!(send_room_ping.channel.reference, "#/channels/chatRoom");
+        assert_eq!(
+            send_room_ping.messages.as_ref().unwrap()[0].as_reference(),
+            Some("#/channels/chatRoom/messages/Ping")
+        );
+    }
+
+    #[test]
+    fn test_rename_channel_rejects_missing_or_taken_names() {
+        let mut spec = rename_test_spec();
+        assert!(!spec.rename_channel("missing", "lobby"));
+        assert!(!spec.rename_channel("chat", "chatRoom"));
+        assert_eq!(
+            spec.operations.as_ref().unwrap()["sendPing"]
+                .channel
+                .reference,
+            "#/channels/chat"
+        );
+        assert!(!AsyncApiSpec::default().rename_channel("chat", "lobby"));
+    }
+
+    #[test]
+    fn test_rename_operation() {
+        let mut spec = rename_test_spec();
+        assert!(spec.rename_operation("sendPing", "publishPing"));
+        let operations = spec.operations.as_ref().unwrap();
+        let names: Vec<&str> = operations.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["publishPing", "sendRoomPing"]);
+        assert!(!spec.rename_operation("sendPing", "other"));
+    }
+
+    #[test]
+    fn test_rename_message_rewrites_refs() {
+        let mut spec = rename_test_spec();
+        assert!(spec.rename_message("Ping", "Heartbeat"));
+
+        let messages = spec.components.as_ref().unwrap().messages.as_ref().unwrap();
+        assert!(messages.contains_key("Heartbeat"));
+        assert!(!messages.contains_key("Ping"));
+
+        for channel in spec.channels.as_ref().unwrap().values() {
+            let channel_messages = channel.messages.as_ref().unwrap();
+            assert_eq!(
+                channel_messages["Ping"].as_reference(),
+                Some("#/components/messages/Heartbeat")
+            );
+        }
+        assert!(!spec.rename_message("Ping", "Other"));
+    }
+
+    #[test]
+    fn test_renames_rewrite_reply_refs() {
+        let reply = |messages: &str| OperationReply {
+            address: None,
+            channel: Some(ChannelRef {
+                reference: "#/channels/chat".to_string(),
+            }),
+            messages: Some(vec![MessageRef::Reference {
+                reference: messages.to_string(),
+            }]),
+        };
+        let mut spec = rename_test_spec();
+        spec.operations
+            .as_mut()
+            .unwrap()
+            .get_mut("sendPing")
+            .unwrap()
+            .reply = Some(OperationReplyRef::Inline(reply(
+            "#/channels/chat/messages/Ping",
+        )));
+        spec.components.as_mut().unwrap().replies = Some(HashMap::from([(
+            "pong".to_string(),
+            reply("#/components/messages/Ping"),
+        )]));
+
+        assert!(spec.rename_channel("chat", "lobby"));
+        assert!(spec.rename_message("Ping", "Heartbeat"));
+
+        let Some(OperationReplyRef::Inline(inline)) =
+            &spec.operations.as_ref().unwrap()["sendPing"].reply
+        else {
+            panic!("Expected an inline reply");
+        };
+        let component = &spec.components.as_ref().unwrap().replies.as_ref().unwrap()["pong"];
+        for reply in [inline, component] {
+            assert_eq!(
+                reply.channel.as_ref().unwrap().reference,
+                "#/channels/lobby"
+            );
+        }
+        assert_eq!(
+            inline.messages.as_ref().unwrap()[0].as_reference(),
+            Some("#/channels/lobby/messages/Ping")
+        );
+        assert_eq!(
+            component.messages.as_ref().unwrap()[0].as_reference(),
+            Some("#/components/messages/Heartbeat")
+        );
+    }
+
+    #[test]
+    fn test_operation_reply_round_trip() {
+        let operation = Operation {
+            action: OperationAction::Send,
+            channel: ChannelRef {
+                reference: "#/channels/requests".to_string(),
+            },
+            title: None,
+            summary: None,
+            description: None,
+            messages: Some(vec![MessageRef::Reference {
+                reference: "#/channels/requests/messages/Ping".to_string(),
+            }]),
+            reply: Some(OperationReplyRef::Inline(OperationReply {
+                address: Some(OperationReplyAddress {
+                    description: Some("Reply inbox".to_string()),
+                    location: "$message.header#/replyTo".to_string(),
+                }),
+                channel: Some(ChannelRef {
+                    reference: "#/channels/replies".to_string(),
+                }),
+                messages: Some(vec![MessageRef::Reference {
+                    reference: "#/channels/replies/messages/Pong".to_string(),
+                }]),
+            })),
+            tags: None,
+            external_docs: None,
+            security: None,
+            bindings: None,
+            extensions: HashMap::new(),
+        };
+
+        let expected = serde_json::json!({
+            "action": "send",
+            "channel": { "$ref": "#/channels/requests" },
+            "messages": [{ "$ref": "#/channels/requests/messages/Ping" }],
+            "reply": {
+                "address": {
+                    "description": "Reply inbox",
+                    "location": "$message.header#/replyTo"
+                },
+                "channel": { "$ref": "#/channels/replies" },
+                "messages": [{ "$ref": "#/channels/replies/messages/Pong" }]
+            }
+        });
+        assert_eq!(serde_json::to_value(&operation).unwrap(), expected);
+
+        let parsed: Operation = serde_json::from_value(expected.clone()).unwrap();
+        let reply = parsed
+            .reply
+            .as_ref()
+            .and_then(OperationReplyRef::as_inline)
+            .expect("Should have inline reply");
+        assert_eq!(
+            reply.channel.as_ref().unwrap().reference,
+            "#/channels/replies"
+        );
+        assert_eq!(
+            reply.address.as_ref().unwrap().location,
+            "$message.header#/replyTo"
+        );
+        assert_eq!(
+            reply.messages.as_ref().unwrap()[0].as_reference(),
+            Some("#/channels/replies/messages/Pong")
+        );
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_operation_reply_omits_empty_fields() {
+        let reply = OperationReply {
+            channel: Some(ChannelRef {
+                reference: "#/channels/replies".to_string(),
+            }),
+            ..OperationReply::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&reply).unwrap(),
+            serde_json::json!({ "channel": { "$ref": "#/channels/replies" } })
+        );
+    }
+
+    #[test]
+    fn test_schema_from_json_depth_guard() {
+        let mut deep = serde_json::json!({ "type": "string" });
+        for _ in 0..10 {
+            deep = serde_json::json!({ "type": "array", "items": deep });
+        }
+
+        assert!(Schema::from_json_with_max_depth(deep.clone(), 11).is_ok());
+        assert_eq!(
+            Schema::from_json_with_max_depth(deep.clone(), 10).unwrap_err(),
+            AsyncApiError::SchemaTooDeep { max_depth: 10 }
+        );
+        assert!(Schema::from_json(deep).is_ok());
+
+        assert!(matches!(
+            Schema::from_json(serde_json::json!(42)),
+            Err(AsyncApiError::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn test_summary_stats() {
+        let spec = rename_test_spec();
+        let stats = spec.summary_stats();
+
+        assert_eq!(
+            stats,
+            SpecStats {
+                servers: 0,
+                channels: 2,
+                send_operations: 2,
+                receive_operations: 0,
+                messages: 1,
+                schemas: 0,
+                payload_bytes: 0,
+            }
+        );
+        assert_eq!(stats.operations(), 2);
+        assert_eq!(
+            AsyncApiSpec::default().summary_stats(),
+            SpecStats::default()
+        );
+    }
+
+    #[test]
+    fn test_summary_stats_payload_size_and_display() {
+        let mut spec = rename_test_spec();
+        let payload = Schema::from_json(serde_json::json!({ "type": "string" })).unwrap();
+        spec.components
+            .as_mut()
+            .unwrap()
+            .messages
+            .as_mut()
+            .unwrap()
+            .get_mut("Ping")
+            .unwrap()
+            .payload = Some(payload);
+        spec.operations
+            .as_mut()
+            .unwrap()
+            .get_mut("sendPing")
+            .unwrap()
+            .action = OperationAction::Receive;
+
+        let stats = spec.summary_stats();
+        assert_eq!(stats.payload_bytes, r#"{"type":"string"}"#.len());
+        assert_eq!(
+            stats.to_string(),
+            "Servers: 0\nChannels: 2\nOperations: 2 (1 send, 1 receive)\nMessages: 1\nSchemas: 0\nPayload size: 17 bytes"
+        );
+    }
+
+    #[test]
+    fn test_schema_boolean_schemas() {
+        let json = serde_json::json!({
+            "type": "object",
+            "properties": { "anything": true },
+            "additionalProperties": false
+        });
+        let schema: Schema = serde_json::from_value(json.clone()).unwrap();
+        let Schema::Object(object) = &schema else {
+            panic!("Expected schema object");
+        };
+        assert!(matches!(
+            object.additional_properties.as_deref(),
+            Some(Schema::Bool(false))
+        ));
+        assert!(matches!(
+            object.properties.as_ref().unwrap()["anything"].as_ref(),
+            Schema::Bool(true)
+        ));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn test_schema_top_level_boolean_round_trip() {
+        for value in [true, false] {
+            let json = serde_json::Value::Bool(value);
+            let schema: Schema = serde_json::from_value(json.clone()).unwrap();
+            assert!(matches!(schema, Schema::Bool(b) if b == value));
+            assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+        }
+
+        // Boolean `additionalProperties: true` is preserved too
+        let json = serde_json::json!({ "type": "object", "additionalProperties": true });
+        let schema = Schema::from_json(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn test_schema_null_keywords_kept() {
+        let json = serde_json::json!({ "type": null, "description": null, "title": "Nothing" });
+        let schema: Schema = serde_json::from_value(json.clone()).unwrap();
+        let Schema::Object(object) = &schema else {
+            panic!("Expected schema object");
+        };
+        assert_eq!(object.title.as_deref(), Some("Nothing"));
+        assert_eq!(object.additional["type"], serde_json::Value::Null);
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn test_schema_unusual_keywords_kept_in_additional() {
+        // Draft-07 tuple validation uses an array for `items`
+        let json = serde_json::json!({
+            "type": "array",
+            "items": [{ "type": "string" }, { "type": "integer" }],
+            "required": "not-a-list",
+            "description": "Tuple"
+        });
+        let schema: Schema = serde_json::from_value(json.clone()).unwrap();
+        let Schema::Object(object) = &schema else {
+            panic!("Expected schema object");
+        };
+        assert!(object.items.is_none());
+        assert!(object.additional.contains_key("items"));
+        assert!(object.additional.contains_key("required"));
+        assert_eq!(object.description, Some("Tuple".to_string()));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+
+        assert!(serde_json::from_value::<Schema>(serde_json::json!("string")).is_err());
+    }
+
+    #[test]
+    fn test_normalize_nullable_any_of_to_type_array() {
+        let mut schema: Schema = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "description": "Optional name",
+                    "anyOf": [{ "type": "string", "minLength": 1 }, { "type": "null" }]
+                },
+                "room": {
+                    "anyOf": [{ "$ref": "#/$defs/Room" }, { "type": "null" }]
+                }
+            },
+            "$defs": {
+                "Room": {
+                    "type": "object",
+                    "properties": {
+                        "topic": { "anyOf": [{ "type": "string" }, { "type": "null" }] }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        schema.normalize_nullable(NullableStyle::TypeArray);
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            json["properties"]["name"],
+            serde_json::json!({
+                "type": ["string", "null"],
+                "minLength": 1,
+                "description": "Optional name"
+            })
+        );
+        // Nullable refs can't use a type array
+        assert_eq!(
+            json["properties"]["room"]["anyOf"][0]["$ref"],
+            "#/$defs/Room"
+        );
+        assert_eq!(
+            json["$defs"]["Room"]["properties"]["topic"],
+            serde_json::json!({ "type": ["string", "null"] })
+        );
+    }
+
+    #[test]
+    fn test_normalize_nullable_type_array_to_any_of() {
+        let mut schema: Schema = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "count": {
+                    "description": "Optional count",
+                    "type": ["integer", "null"],
+                    "format": "uint32"
+                },
+                "kind": { "type": ["string", "null"], "enum": ["a", "b", null] },
+                "mixed": { "type": ["string", "integer"] }
+            }
+        }))
+        .unwrap();
+
+        schema.normalize_nullable(NullableStyle::AnyOf);
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            json["properties"]["count"],
+            serde_json::json!({
+                "description": "Optional count",
+                "anyOf": [{ "type": "integer", "format": "uint32" }, { "type": "null" }]
+            })
+        );
+        assert_eq!(
+            json["properties"]["kind"]["anyOf"][0],
+            serde_json::json!({ "type": "string", "enum": ["a", "b"] })
+        );
+        assert_eq!(
+            json["properties"]["mixed"],
+            serde_json::json!({ "type": ["string", "integer"] })
+        );
+
+        // Converting back restores the type array form
+        schema.normalize_nullable(NullableStyle::TypeArray);
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            json["properties"]["count"],
+            serde_json::json!({
+                "description": "Optional count",
+                "type": ["integer", "null"],
+                "format": "uint32"
+            })
+        );
+    }
+
+    #[test]
+    fn test_prune_unused_components() {
+        let message = |payload: &str| Message {
+            name: None,
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: None,
+            correlation_id: None,
+            payload: Some(Schema::Reference {
+                reference: format!("#/components/schemas/{}", payload),
+            }),
+            tags: None,
+            external_docs: None,
+            extensions: HashMap::new(),
+        };
+        let object = |reference: &str| {
+            Schema::Object(Box::new(SchemaObject {
+                schema_type: Some(serde_json::json!("object")),
+                properties: Some(HashMap::from([(
+                    "nested".to_string(),
+                    Box::new(Schema::Reference {
+                        reference: reference.to_string(),
+                    }),
+                )])),
+                ..SchemaObject::default()
+            }))
+        };
+
+        let mut spec = rename_test_spec();
+        let components = spec.components.as_mut().unwrap();
+        components.messages = Some(HashMap::from([
+            ("Ping".to_string(), message("PingPayload")),
+            ("Orphan".to_string(), message("OrphanPayload")),
+        ]));
+        components.schemas = Some(HashMap::from([
+            (
+                "PingPayload".to_string(),
+                object("#/components/schemas/Timestamp"),
+            ),
+            ("Timestamp".to_string(), Schema::Bool(true)),
+            (
+                "OrphanPayload".to_string(),
+                object("#/components/schemas/Unused"),
+            ),
+            ("Unused".to_string(), Schema::Bool(true)),
+        ]));
+
+        assert_eq!(spec.prune_unused_components(), 3);
+        let components = spec.components.as_ref().unwrap();
+        let mut messages: Vec<_> = components.messages.as_ref().unwrap().keys().collect();
+        messages.sort();
+        assert_eq!(messages, vec!["Ping"]);
+        let mut schemas: Vec<_> = components.schemas.as_ref().unwrap().keys().collect();
+        schemas.sort();
+        assert_eq!(schemas, vec!["PingPayload", "Timestamp"]);
+
+        // Pruning again is a no-op
+        assert_eq!(spec.prune_unused_components(), 0);
+    }
+
+    #[test]
+    fn test_omit_channel_message_names() {
+        let json = serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Chat", "version": "1.0.0" },
+            "channels": {
+                "chat": {
+                    "messages": {
+                        "chat.message": { "name": "chat.message", "payload": { "type": "object" } },
+                        "chat.legacy": { "name": "legacyMessage" },
+                        "chat.ping": { "$ref": "#/components/messages/chat.ping" },
+                        "chat.shared": { "$ref": "./shared.json#/components/messages/chat.shared" }
+                    }
+                }
+            },
+            "components": {
+                "messages": {
+                    "chat.ping": { "name": "chat.ping", "summary": "Keepalive" }
+                }
+            }
+        });
+        let mut spec = AsyncApiSpec::try_from(json.clone()).unwrap();
+
+        // Without the option the output matches the input
+        assert_eq!(serde_json::to_value(&spec).unwrap(), json);
+
+        assert_eq!(spec.omit_channel_message_names(), 2);
+        let output = serde_json::to_value(&spec).unwrap();
+        let messages = &output["channels"]["chat"]["messages"];
+        assert_eq!(
+            messages["chat.message"],
+            serde_json::json!({ "payload": { "type": "object" } })
+        );
+        // Component refs are inlined without the name, which components keep
+        assert_eq!(
+            messages["chat.ping"],
+            serde_json::json!({ "summary": "Keepalive" })
+        );
+        assert_eq!(
+            output["components"]["messages"]["chat.ping"]["name"],
+            "chat.ping"
+        );
+        // Names that say something the key doesn't and external refs are kept
+        assert_eq!(messages["chat.legacy"]["name"], "legacyMessage");
+        assert_eq!(
+            messages["chat.shared"]["$ref"],
+            "./shared.json#/components/messages/chat.shared"
+        );
+
+        assert_eq!(spec.omit_channel_message_names(), 0);
+    }
+
+    #[test]
+    fn test_clone_without_schemas() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Chat", "version": "1.0.0" },
+            "channels": {
+                "chat": {
+                    "messages": {
+                        "chat.message": { "$ref": "#/components/messages/chat.message" },
+                        "chat.inline": { "payload": { "type": "string" } }
+                    }
+                }
+            },
+            "operations": {
+                "sendChat": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/chat" },
+                    "messages": [{ "$ref": "#/channels/chat/messages/chat.message" }],
+                    "reply": { "messages": [{ "payload": { "type": "object" } }] }
+                }
+            },
+            "components": {
+                "messages": {
+                    "chat.message": {
+                        "summary": "A chat message",
+                        "headers": { "type": "object" },
+                        "payload": { "$ref": "#/components/schemas/ChatPayload" }
+                    }
+                },
+                "schemas": { "ChatPayload": { "type": "object" } }
+            }
+        }))
+        .unwrap();
+        let original = serde_json::to_value(&spec).unwrap();
+
+        let stripped = serde_json::to_value(spec.clone_without_schemas()).unwrap();
+        let message = &stripped["components"]["messages"]["chat.message"];
+        assert_eq!(message["summary"], "A chat message");
+        assert!(message.get("payload").is_none() && message.get("headers").is_none());
+        assert!(stripped["components"].get("schemas").is_none());
+        assert!(
+            stripped["channels"]["chat"]["messages"]["chat.inline"]
+                .get("payload")
+                .is_none()
+        );
+        assert!(
+            stripped["operations"]["sendChat"]["reply"]["messages"][0]
+                .get("payload")
+                .is_none()
+        );
+        assert_eq!(
+            stripped["operations"]["sendChat"]["messages"][0]["$ref"],
+            "#/channels/chat/messages/chat.message"
+        );
+
+        // The original keeps its schemas
+        assert_eq!(serde_json::to_value(&spec).unwrap(), original);
+        assert!(spec.find_message("chat.message").unwrap().payload.is_some());
+
+        let mut spec = spec;
+        assert_eq!(spec.strip_schemas(), 5);
+        assert_eq!(spec.strip_schemas(), 0);
+    }
+
+    #[test]
+    fn test_spec_json_value_conversions() {
+        let spec = rename_test_spec();
+        let value = serde_json::Value::from(&spec);
+        assert_eq!(value["asyncapi"], "3.0.0");
+        assert_eq!(
+            value["channels"]["chat"]["messages"]["Ping"]["$ref"],
+            "#/components/messages/Ping"
+        );
+
+        let round_trip = AsyncApiSpec::try_from(value.clone()).unwrap();
+        assert_eq!(serde_json::Value::from(round_trip), value);
+
+        let err = AsyncApiSpec::try_from(serde_json::json!({ "asyncapi": "3.0.0" }));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_find_message() {
+        let spec = rename_test_spec();
+        let ping = spec.find_message("Ping").expect("Should find Ping");
+        assert_eq!(ping.name, Some("Ping".to_string()));
+        assert!(spec.find_message("Pong").is_none());
+
+        // No components at all
+        assert!(AsyncApiSpec::default().find_message("Ping").is_none());
+
+        // Components without messages
+        let spec = AsyncApiSpec {
+            components: Some(Components {
+                messages: None,
+                schemas: None,
+                security_schemes: None,
+                replies: None,
+                reply_addresses: None,
+                channel_bindings: None,
+            }),
+            ..AsyncApiSpec::default()
+        };
+        assert!(spec.find_message("Ping").is_none());
+    }
+
+    mod schema_proptest {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Keys that hit the typed `SchemaObject` fields, plus a few that don't
+        const KEYS: &[&str] = &[
+            "type",
+            "properties",
+            "required",
+            "description",
+            "title",
+            "enum",
+            "const",
+            "items",
+            "additionalProperties",
+            "oneOf",
+            "anyOf",
+            "allOf",
+            "readOnly",
+            "writeOnly",
+            "$ref",
+            "$defs",
+            "format",
+            "examples",
+        ];
+
+        fn json_value() -> impl Strategy<Value = serde_json::Value> {
+            let leaf = prop_oneof![
+                Just(serde_json::Value::Null),
+                any::<bool>().prop_map(serde_json::Value::Bool),
+                any::<i64>().prop_map(serde_json::Value::from),
+                any::<f64>()
+                    .prop_filter("finite", |f| f.is_finite())
+                    .prop_map(serde_json::Value::from),
+                "[a-z#/]{0,8}".prop_map(serde_json::Value::String),
+            ];
+            leaf.prop_recursive(4, 64, 6, |inner| {
+                let key = prop_oneof![
+                    proptest::sample::select(KEYS).prop_map(str::to_string),
+                    "[a-zA-Z$]{1,6}",
+                ];
+                prop_oneof![
+                    prop::collection::vec(inner.clone(), 0..4).prop_map(serde_json::Value::Array),
+                    prop::collection::vec((key, inner), 0..6).prop_map(|entries| {
+                        serde_json::Value::Object(entries.into_iter().collect())
+                    }),
+                ]
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn any_object_or_bool_deserializes_and_round_trips(value in json_value()) {
+                let result = serde_json::from_value::<Schema>(value.clone());
+                match &value {
+                    serde_json::Value::Object(_) | serde_json::Value::Bool(_) => {
+                        let schema = result.expect("objects and booleans are always schemas");
+                        prop_assert_eq!(serde_json::to_value(&schema).unwrap(), value);
+                    }
+                    _ => prop_assert!(result.is_err()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        // Key order doesn't matter, including for keywords kept in `additional`
+        let a: Schema = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "minProperties": 1,
+            "properties": {
+                "a": { "type": "string" },
+                "b": { "$ref": "#/components/schemas/B" }
+            }
+        }))
+        .unwrap();
+        let b: Schema = serde_json::from_value(serde_json::json!({
+            "properties": {
+                "b": { "$ref": "#/components/schemas/B" },
+                "a": { "type": "string" }
+            },
+            "minProperties": 1,
+            "type": "object"
+        }))
+        .unwrap();
+        assert_eq!(a, b);
+
+        let mut c = b.clone();
+        if let Schema::Object(object) = &mut c {
+            object
+                .additional
+                .insert("minProperties".to_string(), serde_json::json!(2));
+        }
+        assert_ne!(a, c);
+        assert_ne!(Schema::Bool(true), Schema::Bool(false));
+
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "name": "Ping",
+            "payload": a,
+        }))
+        .unwrap();
+        assert_eq!(
+            MessageRef::Inline(Box::new(message.clone())),
+            MessageRef::Inline(Box::new(message))
+        );
+        assert_ne!(
+            MessageRef::Reference {
+                reference: "#/components/messages/Ping".to_string()
+            },
+            MessageRef::Reference {
+                reference: "#/components/messages/Pong".to_string()
+            }
+        );
+
+        // Whole documents compare equal after a round trip
+        let spec = rename_test_spec().with_server("prod", test_server("api.example.com", "wss"));
+        let round_trip = AsyncApiSpec::try_from(serde_json::Value::from(&spec)).unwrap();
+        assert_eq!(round_trip, spec);
+        let mut renamed = spec.clone();
+        renamed.rename_operation("sendPing", "ping");
+        assert_ne!(renamed, spec);
     }
 }
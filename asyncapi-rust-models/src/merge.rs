@@ -0,0 +1,261 @@
+//! Merging components into a specification
+//!
+//! Components often come from several places: the messages of each
+//! `ToAsyncApiMessage` type, shared schemas, security schemes added at runtime.
+//! [`AsyncApiSpec::merge_components`] folds a [`Components`] into a spec, with a
+//! [`MergePolicy`] deciding what happens when a name is already taken.
+
+use crate::prelude::*;
+use crate::{AsyncApiError, AsyncApiSpec, Components, Map};
+
+/// What to do when a merged component's name is already taken
+///
+/// Identical definitions never conflict, so merging the same component twice is
+/// always fine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Replace the existing definition
+    Overwrite,
+    /// Keep the existing definition and drop the merged one
+    KeepExisting,
+    /// Fail with [`AsyncApiError::ComponentConflict`], merging nothing
+    #[default]
+    Error,
+}
+
+impl AsyncApiSpec {
+    /// Merge `components` into `components` of this spec
+    ///
+    /// Every section is merged, creating it if needed. Under [`MergePolicy::Error`]
+    /// the spec is left unchanged when any component conflicts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use asyncapi_rust_models::{AsyncApiSpec, Components, MergePolicy, Schema};
+    /// use std::collections::HashMap;
+    ///
+    /// let shared = Components {
+    ///     schemas: Some(HashMap::from([("Flag".to_string(), Schema::Bool(true))])),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut spec = AsyncApiSpec::default();
+    /// spec.merge_components(shared.clone(), MergePolicy::Error).unwrap();
+    /// // Merging the same definitions again is not a conflict
+    /// spec.merge_components(shared, MergePolicy::Error).unwrap();
+    ///
+    /// let other = Components {
+    ///     schemas: Some(HashMap::from([("Flag".to_string(), Schema::Bool(false))])),
+    ///     ..Default::default()
+    /// };
+    /// assert!(spec.merge_components(other, MergePolicy::Error).is_err());
+    /// ```
+    pub fn merge_components(
+        &mut self,
+        components: Components,
+        policy: MergePolicy,
+    ) -> Result<(), AsyncApiError> {
+        let existing = self.components.get_or_insert_with(Components::default);
+        if policy == MergePolicy::Error {
+            let conflict = first_conflict("messages", &existing.messages, &components.messages)
+                .or_else(|| first_conflict("schemas", &existing.schemas, &components.schemas))
+                .or_else(|| {
+                    first_conflict(
+                        "securitySchemes",
+                        &existing.security_schemes,
+                        &components.security_schemes,
+                    )
+                })
+                .or_else(|| first_conflict("replies", &existing.replies, &components.replies))
+                .or_else(|| {
+                    first_conflict(
+                        "replyAddresses",
+                        &existing.reply_addresses,
+                        &components.reply_addresses,
+                    )
+                })
+                .or_else(|| {
+                    first_conflict(
+                        "channelBindings",
+                        &existing.channel_bindings,
+                        &components.channel_bindings,
+                    )
+                });
+            if let Some(conflict) = conflict {
+                if existing.is_empty() {
+                    self.components = None;
+                }
+                return Err(conflict);
+            }
+        }
+
+        let overwrite = policy != MergePolicy::KeepExisting;
+        merge_section(&mut existing.messages, components.messages, overwrite);
+        merge_section(&mut existing.schemas, components.schemas, overwrite);
+        merge_section(
+            &mut existing.security_schemes,
+            components.security_schemes,
+            overwrite,
+        );
+        merge_section(&mut existing.replies, components.replies, overwrite);
+        merge_section(
+            &mut existing.reply_addresses,
+            components.reply_addresses,
+            overwrite,
+        );
+        merge_section(
+            &mut existing.channel_bindings,
+            components.channel_bindings,
+            overwrite,
+        );
+        if existing.is_empty() {
+            self.components = None;
+        }
+        Ok(())
+    }
+}
+
+/// Find the alphabetically first name defined differently in both sections
+fn first_conflict<T: PartialEq>(
+    section: &'static str,
+    existing: &Option<Map<String, T>>,
+    merged: &Option<Map<String, T>>,
+) -> Option<AsyncApiError> {
+    let (existing, merged) = (existing.as_ref()?, merged.as_ref()?);
+    merged
+        .iter()
+        .filter(|(name, value)| existing.get(*name).is_some_and(|current| current != *value))
+        .map(|(name, _)| name)
+        .min()
+        .map(|name| AsyncApiError::ComponentConflict {
+            section,
+            name: name.clone(),
+        })
+}
+
+fn merge_section<T>(
+    existing: &mut Option<Map<String, T>>,
+    merged: Option<Map<String, T>>,
+    overwrite: bool,
+) {
+    let Some(merged) = merged else {
+        return;
+    };
+    let existing = existing.get_or_insert_with(Map::new);
+    for (name, value) in merged {
+        if overwrite || !existing.contains_key(&name) {
+            existing.insert(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Schema, SecurityScheme};
+    use std::collections::HashMap;
+
+    fn schemas(entries: &[(&str, bool)]) -> Components {
+        Components {
+            schemas: Some(
+                entries
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), Schema::Bool(*value)))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn spec() -> AsyncApiSpec {
+        let mut spec = AsyncApiSpec::default();
+        spec.merge_components(schemas(&[("A", true), ("B", true)]), MergePolicy::Error)
+            .unwrap();
+        spec
+    }
+
+    fn schema(spec: &AsyncApiSpec, name: &str) -> Schema {
+        spec.components.as_ref().unwrap().schemas.as_ref().unwrap()[name].clone()
+    }
+
+    #[test]
+    fn test_merge_overwrite() {
+        let mut spec = spec();
+        spec.merge_components(
+            schemas(&[("B", false), ("C", false)]),
+            MergePolicy::Overwrite,
+        )
+        .unwrap();
+        assert_eq!(schema(&spec, "A"), Schema::Bool(true));
+        assert_eq!(schema(&spec, "B"), Schema::Bool(false));
+        assert_eq!(schema(&spec, "C"), Schema::Bool(false));
+    }
+
+    #[test]
+    fn test_merge_keep_existing() {
+        let mut spec = spec();
+        spec.merge_components(
+            schemas(&[("B", false), ("C", false)]),
+            MergePolicy::KeepExisting,
+        )
+        .unwrap();
+        assert_eq!(schema(&spec, "B"), Schema::Bool(true));
+        assert_eq!(schema(&spec, "C"), Schema::Bool(false));
+    }
+
+    #[test]
+    fn test_merge_error() {
+        let mut spec = spec();
+        let before = spec.clone();
+
+        // Conflicts leave the spec untouched, even for non-conflicting entries
+        let err = spec
+            .merge_components(
+                schemas(&[("D", false), ("C", true), ("B", false), ("A", false)]),
+                MergePolicy::Error,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AsyncApiError::ComponentConflict {
+                section: "schemas",
+                name: "A".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "component 'A' in 'schemas' conflicts with an existing definition"
+        );
+        assert_eq!(spec, before);
+
+        // Identical definitions merge cleanly, alongside new sections
+        let mut components = schemas(&[("A", true), ("C", true)]);
+        components.security_schemes = Some(HashMap::from([(
+            "token".to_string(),
+            SecurityScheme {
+                scheme_type: "httpApiKey".to_string(),
+                description: None,
+                additional: HashMap::new(),
+            },
+        )]));
+        spec.merge_components(components, MergePolicy::Error)
+            .unwrap();
+        assert_eq!(schema(&spec, "C"), Schema::Bool(true));
+        let components = spec.components.as_ref().unwrap();
+        assert!(
+            components
+                .security_schemes
+                .as_ref()
+                .unwrap()
+                .contains_key("token")
+        );
+
+        // Merging nothing into a spec without components doesn't leave an empty one
+        let mut empty = AsyncApiSpec::default();
+        empty
+            .merge_components(Components::default(), MergePolicy::Error)
+            .unwrap();
+        assert!(empty.components.is_none());
+    }
+}
@@ -0,0 +1,9 @@
+//! Items the crate uses from `std`, taken from `alloc` and `core` so they're also
+//! available without `std`
+
+pub use alloc::boxed::Box;
+pub use alloc::collections::{BTreeMap, BTreeSet};
+pub use alloc::string::{String, ToString};
+pub use alloc::vec::Vec;
+pub use alloc::{format, vec};
+pub use core::fmt;
@@ -0,0 +1,103 @@
+//! Serialize the spec's [`Map`]s with their keys in sorted order
+//!
+//! `HashMap` iteration order changes from run to run, so without this the same
+//! spec would render differently every time.
+
+use crate::Map;
+use crate::prelude::*;
+use serde::{Serialize, Serializer};
+
+/// A map serialized in key order
+struct Sorted<'a, V>(&'a Map<String, V>);
+
+impl<V: Serialize> Serialize for Sorted<'_, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_by_key(|&(key, _)| key);
+        serializer.collect_map(entries)
+    }
+}
+
+/// A map of maps serialized in key order at both levels
+struct SortedNested<'a, V>(&'a Map<String, Map<String, V>>);
+
+impl<V: Serialize> Serialize for SortedNested<'_, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.0.iter().map(|(k, v)| (k, Sorted(v))).collect();
+        entries.sort_by_key(|&(key, _)| key);
+        serializer.collect_map(entries)
+    }
+}
+
+pub(crate) fn map<S: Serializer, V: Serialize>(
+    map: &Map<String, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Sorted(map).serialize(serializer)
+}
+
+pub(crate) fn option<S: Serializer, V: Serialize>(
+    map: &Option<Map<String, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match map {
+        Some(map) => serializer.serialize_some(&Sorted(map)),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn nested_option<S: Serializer, V: Serialize>(
+    map: &Option<Map<String, Map<String, V>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match map {
+        Some(map) => serializer.serialize_some(&SortedNested(map)),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Message, SchemaObject};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_maps_serialize_in_key_order() {
+        let keys = ["x-delta", "x-alpha", "x-echo", "x-charlie", "x-bravo"];
+        let message = Message {
+            name: None,
+            title: None,
+            summary: None,
+            description: None,
+            content_type: None,
+            headers: None,
+            correlation_id: None,
+            payload: None,
+            tags: None,
+            external_docs: None,
+            extensions: keys
+                .iter()
+                .map(|key| (key.to_string(), serde_json::json!(1)))
+                .collect(),
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(
+            json,
+            r#"{"x-alpha":1,"x-bravo":1,"x-charlie":1,"x-delta":1,"x-echo":1}"#
+        );
+
+        let properties: HashMap<_, _> = keys
+            .iter()
+            .map(|key| (key.to_string(), Box::new(crate::Schema::Bool(true))))
+            .collect();
+        let schema = SchemaObject {
+            properties: Some(properties),
+            ..SchemaObject::default()
+        };
+        let json = serde_json::to_string(&schema).unwrap();
+        assert_eq!(
+            json,
+            r#"{"properties":{"x-alpha":true,"x-bravo":true,"x-charlie":true,"x-delta":true,"x-echo":true}}"#
+        );
+    }
+}
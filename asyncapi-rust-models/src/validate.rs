@@ -0,0 +1,1300 @@
+//! Best-effort consistency checks for specifications
+//!
+//! [`AsyncApiSpec::validate`] looks for problems that serde can't catch, such as
+//! tags used on operations but never declared in `info.tags`, a URL pasted
+//! into a server's `host`, an operation on an undeclared channel or declared
+//! twice, a channel parameter missing from the address, a security requirement
+//! naming an undefined scheme, an operation message without a payload, a reply
+//! with nothing to reply with, WebSocket binding fields placed on a message
+//! instead of its channel, a correlation ID header missing from the message's
+//! headers, names that collide once tooling turns them into identifiers, or a
+//! message on channels with different default content types.
+//! Structural checks against the official AsyncAPI JSON Schema live behind the
+//! `validate-schema` feature instead.
+
+use crate::prelude::*;
+use crate::{
+    AsyncApiSpec, BindingsRef, Message, MessageRef, NO_PAYLOAD_EXTENSION, OperationReply,
+    OperationReplyRef, REDECLARED_EXTENSION, SecuritySchemeRef, check_server_host,
+};
+
+/// How serious a [`ValidationIssue`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Likely a mistake, but the document is still usable
+    Warning,
+    /// The document is inconsistent and tooling will likely reject it
+    Error,
+}
+
+/// Problem found by [`AsyncApiSpec::validate`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ValidationIssue {
+    /// A tag is used on a channel, operation or message but not declared in `info.tags`
+    UndeclaredTag {
+        /// Tag name
+        name: String,
+    },
+    /// A server host isn't a bare authority, e.g. it includes a URL scheme or path
+    InvalidServerHost {
+        /// Server name
+        server: String,
+        /// What is wrong with the host
+        reason: String,
+    },
+    /// An operation references a channel missing from `channels`
+    UnknownOperationChannel {
+        /// Operation name
+        operation: String,
+        /// The unresolved `$ref`
+        reference: String,
+    },
+    /// An operation name was declared more than once and only the first declaration
+    /// was kept
+    ///
+    /// See [`REDECLARED_EXTENSION`](crate::REDECLARED_EXTENSION).
+    RedeclaredOperation {
+        /// Operation name
+        operation: String,
+    },
+    /// A channel declares a parameter that doesn't appear in its address
+    UnusedChannelParameter {
+        /// Channel name
+        channel: String,
+        /// Parameter name
+        parameter: String,
+    },
+    /// A server or operation references a security scheme missing from
+    /// `components.securitySchemes`
+    UnknownSecurityScheme {
+        /// Where the reference is, e.g. `server 'production'`
+        location: String,
+        /// The unresolved `$ref`
+        reference: String,
+    },
+    /// A message used by an operation has no payload and isn't marked `x-no-payload`
+    MissingPayload {
+        /// Message name
+        message: String,
+    },
+    /// A channel references bindings missing from `components.channelBindings`
+    UnknownChannelBindings {
+        /// Channel name
+        channel: String,
+        /// The unresolved `$ref`
+        reference: String,
+    },
+    /// A reply references neither a message nor a reply channel
+    EmptyReply {
+        /// Where the reply is, e.g. `operation 'getUser'`
+        location: String,
+    },
+    /// A message has a `ws` binding with fields
+    ///
+    /// The WebSockets binding defines no message fields; `method`, `query` and
+    /// `headers` belong on the channel binding.
+    WebSocketMessageBinding {
+        /// Message name
+        message: String,
+        /// Offending binding fields, sorted
+        fields: Vec<String>,
+    },
+    /// A message's `correlationId` points at a header its `headers` schema doesn't define
+    ///
+    /// See [`Message::missing_correlation_header`].
+    MissingCorrelationHeader {
+        /// Message name
+        message: String,
+        /// Header named by the correlation ID location
+        header: String,
+    },
+    /// An operation ID differs from another only in case
+    ///
+    /// Code generators commonly turn operation IDs into method or file names, which
+    /// then clash.
+    DuplicateOperationId {
+        /// Operation ID
+        operation: String,
+    },
+    /// Different message definitions share a `name`
+    DuplicateMessageName {
+        /// Message name
+        message: String,
+    },
+    /// Identical message definitions under several `components.messages` keys share
+    /// a `name`
+    RepeatedMessageName {
+        /// Message name
+        message: String,
+        /// The `components.messages` keys, sorted
+        keys: Vec<String>,
+    },
+    /// A message is used on channels with different default content types
+    ///
+    /// The derive gives such a message the first channel's default, so it is
+    /// documented with the wrong content type on the others.
+    ConflictingContentTypes {
+        /// Message name
+        message: String,
+        /// The channels' default content types, sorted
+        content_types: Vec<String>,
+    },
+}
+
+impl ValidationIssue {
+    /// Get the severity of this issue
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationIssue::UndeclaredTag { .. } => Severity::Warning,
+            ValidationIssue::InvalidServerHost { .. } => Severity::Warning,
+            ValidationIssue::UnknownOperationChannel { .. } => Severity::Error,
+            ValidationIssue::RedeclaredOperation { .. } => Severity::Error,
+            ValidationIssue::UnusedChannelParameter { .. } => Severity::Warning,
+            ValidationIssue::UnknownSecurityScheme { .. } => Severity::Error,
+            ValidationIssue::UnknownChannelBindings { .. } => Severity::Error,
+            ValidationIssue::MissingPayload { .. } => Severity::Warning,
+            ValidationIssue::EmptyReply { .. } => Severity::Error,
+            ValidationIssue::WebSocketMessageBinding { .. } => Severity::Error,
+            ValidationIssue::MissingCorrelationHeader { .. } => Severity::Warning,
+            ValidationIssue::DuplicateOperationId { .. } => Severity::Error,
+            ValidationIssue::DuplicateMessageName { .. } => Severity::Error,
+            ValidationIssue::RepeatedMessageName { .. } => Severity::Warning,
+            ValidationIssue::ConflictingContentTypes { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::UndeclaredTag { name } => write!(
+                f,
+                "tag '{}' is used but not declared in info.tags; see normalize_tags()",
+                name
+            ),
+            ValidationIssue::InvalidServerHost { server, reason } => {
+                write!(f, "server '{}': {}", server, reason)
+            }
+            ValidationIssue::UnknownOperationChannel {
+                operation,
+                reference,
+            } => write!(
+                f,
+                "operation '{}': channel '{}' is not defined in channels",
+                operation, reference
+            ),
+            ValidationIssue::RedeclaredOperation { operation } => write!(
+                f,
+                "operation '{}' is declared more than once; only the first declaration is kept",
+                operation
+            ),
+            ValidationIssue::UnusedChannelParameter { channel, parameter } => write!(
+                f,
+                "channel '{}' declares parameter '{}', which does not appear in its address",
+                channel, parameter
+            ),
+            ValidationIssue::UnknownSecurityScheme {
+                location,
+                reference,
+            } => write!(
+                f,
+                "{}: security scheme '{}' is not defined in components.securitySchemes",
+                location, reference
+            ),
+            ValidationIssue::MissingPayload { message } => write!(
+                f,
+                "message '{}' is used by an operation but has no payload; mark it no_payload if intended",
+                message
+            ),
+            ValidationIssue::UnknownChannelBindings { channel, reference } => write!(
+                f,
+                "channel '{}': bindings '{}' are not defined in components.channelBindings",
+                channel, reference
+            ),
+            ValidationIssue::EmptyReply { location } => {
+                write!(f, "{}: reply has no messages and no channel", location)
+            }
+            ValidationIssue::WebSocketMessageBinding { message, fields } => write!(
+                f,
+                "message '{}': WebSocket message bindings have no fields, found '{}'; \
+                 move method/query/headers to the channel's ws binding",
+                message,
+                fields.join("', '")
+            ),
+            ValidationIssue::MissingCorrelationHeader { message, header } => write!(
+                f,
+                "message '{}' has a correlationId in header '{}', which is not defined in its \
+                 headers schema",
+                message, header
+            ),
+            ValidationIssue::DuplicateOperationId { operation } => write!(
+                f,
+                "operation '{}' differs from another operation ID only in case",
+                operation
+            ),
+            ValidationIssue::DuplicateMessageName { message } => write!(
+                f,
+                "message name '{}' is used by more than one message definition",
+                message
+            ),
+            ValidationIssue::RepeatedMessageName { message, keys } => write!(
+                f,
+                "message name '{}' is shared by components.messages '{}'; generated code \
+                 will clash even though the definitions match",
+                message,
+                keys.join("', '")
+            ),
+            ValidationIssue::ConflictingContentTypes {
+                message,
+                content_types,
+            } => write!(
+                f,
+                "message '{}' is used on channels with different default content types \
+                 ('{}'); give it its own content type",
+                message,
+                content_types.join("', '")
+            ),
+        }
+    }
+}
+
+impl AsyncApiSpec {
+    /// Check the spec for internal inconsistencies
+    ///
+    /// Returns every issue found, warnings and errors alike; an empty list means the
+    /// spec passed all checks. Use [`ValidationIssue::severity`] to decide which
+    /// issues should fail a build.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let mut servers: Vec<_> = self.servers.iter().flatten().collect();
+        servers.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, server) in servers {
+            if let Err(reason) = check_server_host(&server.host, &server.protocol) {
+                issues.push(ValidationIssue::InvalidServerHost {
+                    server: name.clone(),
+                    reason,
+                });
+            }
+            let location = format!("server '{}'", name);
+            self.check_security(&location, server.security.as_deref(), &mut issues);
+        }
+        for (name, operation) in self.operations.iter().flatten() {
+            // Refs into other documents can't be checked
+            let reference = &operation.channel.reference;
+            let resolves = reference
+                .strip_prefix("#/channels/")
+                .is_some_and(|channel| {
+                    self.channels
+                        .as_ref()
+                        .is_some_and(|c| c.contains_key(channel))
+                });
+            if reference.starts_with('#') && !resolves {
+                issues.push(ValidationIssue::UnknownOperationChannel {
+                    operation: name.clone(),
+                    reference: reference.clone(),
+                });
+            }
+            if operation.extensions.get(REDECLARED_EXTENSION)
+                == Some(&serde_json::Value::Bool(true))
+            {
+                issues.push(ValidationIssue::RedeclaredOperation {
+                    operation: name.clone(),
+                });
+            }
+            let location = format!("operation '{}'", name);
+            self.check_security(&location, operation.security.as_deref(), &mut issues);
+            // Referenced replies are checked with the component replies below
+            let inline_reply = operation
+                .reply
+                .as_ref()
+                .and_then(OperationReplyRef::as_inline);
+            if inline_reply.is_some_and(is_empty_reply) {
+                issues.push(ValidationIssue::EmptyReply { location });
+            }
+        }
+        let mut replies: Vec<_> = self
+            .components
+            .iter()
+            .flat_map(|c| c.replies.iter().flatten())
+            .collect();
+        replies.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, reply) in replies {
+            if is_empty_reply(reply) {
+                issues.push(ValidationIssue::EmptyReply {
+                    location: format!("reply '{}'", name),
+                });
+            }
+        }
+
+        let channel_bindings = self
+            .components
+            .as_ref()
+            .and_then(|c| c.channel_bindings.as_ref());
+        for (name, channel) in self.channels_sorted() {
+            for parameter in channel.unused_parameters() {
+                issues.push(ValidationIssue::UnusedChannelParameter {
+                    channel: name.clone(),
+                    parameter: parameter.to_string(),
+                });
+            }
+            let Some(BindingsRef::Reference { reference }) = &channel.bindings else {
+                continue;
+            };
+            // Refs into other documents can't be checked
+            if !reference.starts_with('#') {
+                continue;
+            }
+            let resolves = reference
+                .strip_prefix("#/components/channelBindings/")
+                .is_some_and(|b| channel_bindings.is_some_and(|c| c.contains_key(b)));
+            if !resolves {
+                issues.push(ValidationIssue::UnknownChannelBindings {
+                    channel: name.clone(),
+                    reference: reference.clone(),
+                });
+            }
+        }
+
+        for message in self.validate_message_payloads() {
+            issues.push(ValidationIssue::MissingPayload { message });
+        }
+
+        // Channel copies of a component message are reported once, under the component
+        let mut messages = self.messages_sorted();
+        let components = self.components.as_ref().and_then(|c| c.messages.as_ref());
+        for (_, channel) in self.channels_sorted() {
+            let inline = channel
+                .messages
+                .iter()
+                .flatten()
+                .filter_map(|(name, m)| m.as_inline().map(|message| (name, message)))
+                .filter(|(name, message)| {
+                    !components
+                        .and_then(|c| c.get(*name))
+                        .is_some_and(|component| same_definition(component, message))
+                });
+            messages.extend(inline);
+        }
+        for (name, message) in messages {
+            let fields = websocket_message_binding_fields(message);
+            if !fields.is_empty() {
+                issues.push(ValidationIssue::WebSocketMessageBinding {
+                    message: name.clone(),
+                    fields,
+                });
+            }
+            if let Some(header) = message.missing_correlation_header() {
+                issues.push(ValidationIssue::MissingCorrelationHeader {
+                    message: name.clone(),
+                    header: header.to_string(),
+                });
+            }
+        }
+
+        for operation in self.validate_unique_operation_ids() {
+            issues.push(ValidationIssue::DuplicateOperationId { operation });
+        }
+        for message in self.validate_unique_message_names() {
+            issues.push(ValidationIssue::DuplicateMessageName { message });
+        }
+        for (message, keys) in self.validate_repeated_message_names() {
+            issues.push(ValidationIssue::RepeatedMessageName { message, keys });
+        }
+        for (message, content_types) in self.validate_channel_content_types() {
+            issues.push(ValidationIssue::ConflictingContentTypes {
+                message,
+                content_types,
+            });
+        }
+
+        let declared = self.info.tags.as_deref().unwrap_or_default();
+        for tag in self.collect_tags() {
+            if !declared.iter().any(|d| d.name == tag.name) {
+                issues.push(ValidationIssue::UndeclaredTag {
+                    name: tag.name.clone(),
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// Get the names of messages used by operations that have no payload
+    ///
+    /// Covers operation and reply messages, following refs through channels into
+    /// `components.messages` and reply refs into `components.replies`. Messages marked `x-no-payload: true`, which the derive
+    /// emits for `#[asyncapi(no_payload)]`, are skipped, as are refs that don't
+    /// resolve. Names are sorted and deduplicated.
+    pub fn validate_message_payloads(&self) -> Vec<String> {
+        let mut missing = Vec::new();
+        for operation in self.operations.iter().flat_map(|o| o.values()) {
+            let reply_messages = operation
+                .reply
+                .as_ref()
+                .and_then(|reply| self.resolve_reply(reply))
+                .and_then(|reply| reply.messages.as_ref());
+            for message_ref in operation.messages.iter().chain(reply_messages).flatten() {
+                let Some((name, message)) = self.resolve_message(message_ref) else {
+                    continue;
+                };
+                let no_payload = message.extensions.get(NO_PAYLOAD_EXTENSION)
+                    == Some(&serde_json::Value::Bool(true));
+                if message.payload.is_none() && !no_payload {
+                    missing.push(name);
+                }
+            }
+        }
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Get the operation IDs that collide with another one ignoring case
+    ///
+    /// Operation IDs are map keys, so exact duplicates can't occur, but `sendMessage`
+    /// and `SendMessage` become the same identifier for many generators. Every ID in
+    /// a colliding group is returned, sorted.
+    pub fn validate_unique_operation_ids(&self) -> Vec<String> {
+        let mut groups: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+        for id in self.operations.iter().flat_map(|o| o.keys()) {
+            groups.entry(id.to_lowercase()).or_default().push(id);
+        }
+        let mut colliding: Vec<String> = groups
+            .into_values()
+            .filter(|ids| ids.len() > 1)
+            .flatten()
+            .cloned()
+            .collect();
+        colliding.sort();
+        colliding
+    }
+
+    /// Get the message names shared by different message definitions
+    ///
+    /// Covers `components.messages` and inline channel messages, named by their
+    /// `name` or else their key. The same definition inlined in several channels,
+    /// with or without its `name`, is not a collision; the same definition under several component keys is
+    /// reported by [`validate_repeated_message_names`](Self::validate_repeated_message_names).
+    /// Names are sorted.
+    pub fn validate_unique_message_names(&self) -> Vec<String> {
+        let component_messages = self
+            .components
+            .iter()
+            .flat_map(|c| c.messages.iter().flatten());
+        let channel_messages = self
+            .channels
+            .iter()
+            .flat_map(|channels| channels.values())
+            .flat_map(|channel| channel.messages.iter().flatten())
+            .filter_map(|(key, message)| message.as_inline().map(|m| (key, m)));
+
+        let mut definitions: BTreeMap<&str, Vec<&Message>> = BTreeMap::new();
+        for (key, message) in component_messages.chain(channel_messages) {
+            let name = message.name.as_deref().unwrap_or(key);
+            let existing = definitions.entry(name).or_default();
+            if !existing.iter().any(|m| same_definition(m, message)) {
+                existing.push(message);
+            }
+        }
+        let mut colliding: Vec<String> = definitions
+            .into_iter()
+            .filter(|(_, messages)| messages.len() > 1)
+            .map(|(name, _)| name.to_string())
+            .collect();
+        colliding.sort();
+        colliding
+    }
+
+    /// Get the component messages used on channels with different default content
+    /// types, with those content types sorted
+    ///
+    /// A message that declares its own content type, other than one of the
+    /// channels' defaults, isn't affected by them and is skipped. Messages are
+    /// sorted by name.
+    pub fn validate_channel_content_types(&self) -> Vec<(String, Vec<String>)> {
+        let mut conflicts = Vec::new();
+        for (name, message) in self.messages_sorted() {
+            let mut defaults: Vec<&str> = self
+                .channels_using_message(name)
+                .into_iter()
+                .filter_map(|(_, channel)| channel.default_content_type.as_deref())
+                .collect();
+            defaults.sort();
+            defaults.dedup();
+            let own_type_applies = message
+                .content_type
+                .as_deref()
+                .is_some_and(|own| !defaults.contains(&own));
+            if defaults.len() > 1 && !own_type_applies {
+                let defaults = defaults.into_iter().map(str::to_string).collect();
+                conflicts.push((name.clone(), defaults));
+            }
+        }
+        conflicts
+    }
+
+    /// Get the message names shared by identical definitions under several
+    /// `components.messages` keys, with those keys sorted
+    ///
+    /// The definitions agree, but code generators still emit one type per key and
+    /// the names clash. Names reported by
+    /// [`validate_unique_message_names`](Self::validate_unique_message_names) are
+    /// skipped. Names are sorted.
+    pub fn validate_repeated_message_names(&self) -> Vec<(String, Vec<String>)> {
+        let mut keys: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for (key, message) in self.messages_sorted() {
+            let name = message.name.as_deref().unwrap_or(key);
+            keys.entry(name).or_default().push(key.clone());
+        }
+        let colliding = self.validate_unique_message_names();
+        keys.into_iter()
+            .filter(|(name, keys)| keys.len() > 1 && !colliding.iter().any(|c| c == name))
+            .map(|(name, keys)| (name.to_string(), keys))
+            .collect()
+    }
+
+    /// Resolve a message ref to its name and definition
+    ///
+    /// Follows `#/channels/{channel}/messages/{message}` through the channel, and
+    /// `#/components/messages/{message}` into components.
+    fn resolve_message<'a>(&'a self, message_ref: &'a MessageRef) -> Option<(String, &'a Message)> {
+        let reference = match message_ref {
+            MessageRef::Inline(message) => {
+                let name = message.name.clone().unwrap_or_default();
+                return Some((name, message));
+            }
+            MessageRef::Reference { reference } => reference,
+        };
+        if let Some(name) = reference.strip_prefix("#/components/messages/") {
+            return self.find_message(name).map(|m| (name.to_string(), m));
+        }
+        let (channel, name) = reference
+            .strip_prefix("#/channels/")?
+            .split_once("/messages/")?;
+        let channel_message = self
+            .channels
+            .as_ref()?
+            .get(channel)?
+            .messages
+            .as_ref()?
+            .get(name)?;
+        match channel_message {
+            // Channel messages name their own definitions
+            MessageRef::Inline(message) => Some((name.to_string(), message)),
+            MessageRef::Reference { reference } => {
+                let name = reference.strip_prefix("#/components/messages/")?;
+                self.find_message(name).map(|m| (name.to_string(), m))
+            }
+        }
+    }
+
+    /// Resolve a reply to its definition, following `#/components/replies/{name}`
+    fn resolve_reply<'a>(&'a self, reply: &'a OperationReplyRef) -> Option<&'a OperationReply> {
+        match reply {
+            OperationReplyRef::Inline(reply) => Some(reply),
+            OperationReplyRef::Reference { reference } => {
+                let name = reference.strip_prefix("#/components/replies/")?;
+                self.components.as_ref()?.replies.as_ref()?.get(name)
+            }
+        }
+    }
+
+    /// Report local security scheme refs that don't resolve
+    ///
+    /// Refs into other documents can't be checked and are skipped.
+    fn check_security(
+        &self,
+        location: &str,
+        security: Option<&[SecuritySchemeRef]>,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        let schemes = self
+            .components
+            .as_ref()
+            .and_then(|c| c.security_schemes.as_ref());
+        for scheme in security.unwrap_or_default() {
+            let SecuritySchemeRef::Reference { reference } = scheme else {
+                continue;
+            };
+            if !reference.starts_with('#') {
+                continue;
+            }
+            let resolves = reference
+                .strip_prefix("#/components/securitySchemes/")
+                .is_some_and(|name| schemes.is_some_and(|s| s.contains_key(name)));
+            if !resolves {
+                issues.push(ValidationIssue::UnknownSecurityScheme {
+                    location: location.to_string(),
+                    reference: reference.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Whether a reply has nothing to reply with: no messages and no channel
+fn is_empty_reply(reply: &OperationReply) -> bool {
+    reply.channel.is_none() && reply.messages.as_deref().unwrap_or_default().is_empty()
+}
+
+/// Whether two messages are the same definition apart from `name`, e.g. a component
+/// message and its channel copy from [`AsyncApiSpec::omit_channel_message_names`]
+fn same_definition(a: &Message, b: &Message) -> bool {
+    let unnamed = |message: &Message| Message {
+        name: None,
+        ..message.clone()
+    };
+    a == b || unnamed(a) == unnamed(b)
+}
+
+/// Fields of a message's inline `ws` binding other than `bindingVersion` and `x-*`
+/// extensions
+///
+/// Messages have no typed `bindings` field, so the binding is read from the
+/// message's extensions.
+fn websocket_message_binding_fields(message: &Message) -> Vec<String> {
+    let Some(serde_json::Value::Object(ws)) = message
+        .extensions
+        .get("bindings")
+        .and_then(|bindings| bindings.get("ws"))
+    else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = ws
+        .keys()
+        .filter(|key| *key != "bindingVersion" && !key.starts_with("x-"))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChannelRef, Operation, OperationAction, SecurityScheme, Server, Tag};
+    use std::collections::HashMap;
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            name: name.to_string(),
+            description: None,
+            external_docs: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_undeclared_operation_tag() {
+        let mut spec = AsyncApiSpec::builder()
+            .title("Tagged API")
+            .version("1.0.0")
+            .channel("chat", |c| c.address("/ws/chat"))
+            .build();
+        spec.add_tag(tag("chat"));
+        spec.operations = Some(crate::IndexMap::from([(
+            "sendMessage".to_string(),
+            Operation {
+                action: OperationAction::Send,
+                channel: ChannelRef {
+                    reference: "#/channels/chat".to_string(),
+                },
+                title: None,
+                summary: None,
+                description: None,
+                messages: None,
+                reply: None,
+                tags: Some(vec![tag("chat"), tag("admin")]),
+                external_docs: None,
+                security: None,
+                bindings: None,
+                extensions: HashMap::new(),
+            },
+        )]));
+
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::UndeclaredTag {
+                name: "admin".to_string()
+            }]
+        );
+        assert_eq!(issues[0].severity(), Severity::Warning);
+        assert!(issues[0].to_string().contains("tag 'admin'"));
+
+        spec.normalize_tags();
+        assert!(spec.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_security_scheme_refs() {
+        let scheme_ref = |name: &str| SecuritySchemeRef::Reference {
+            reference: format!("#/components/securitySchemes/{}", name),
+        };
+        let mut spec = AsyncApiSpec::builder()
+            .title("Secure API")
+            .version("1.0.0")
+            .channel("chat", |c| c.address("/ws/chat"))
+            .build();
+        spec.servers = Some(std::collections::HashMap::from([(
+            "production".to_string(),
+            Server {
+                host: "api.example.com".to_string(),
+                protocol: "wss".to_string(),
+                pathname: None,
+                description: None,
+                variables: None,
+                security: Some(vec![scheme_ref("bearer")]),
+            },
+        )]));
+        spec.operations = Some(crate::IndexMap::from([(
+            "sendMessage".to_string(),
+            Operation {
+                action: OperationAction::Send,
+                channel: ChannelRef {
+                    reference: "#/channels/chat".to_string(),
+                },
+                title: None,
+                summary: None,
+                description: None,
+                messages: None,
+                reply: None,
+                tags: None,
+                external_docs: None,
+                security: Some(vec![
+                    scheme_ref("bearer"),
+                    scheme_ref("berer"),
+                    SecuritySchemeRef::Reference {
+                        reference: "./security.json#/apiKey".to_string(),
+                    },
+                ]),
+                bindings: None,
+                extensions: HashMap::new(),
+            },
+        )]));
+        spec.components = Some(crate::Components {
+            messages: None,
+            schemas: None,
+            security_schemes: Some(std::collections::HashMap::from([(
+                "bearer".to_string(),
+                SecurityScheme {
+                    scheme_type: "http".to_string(),
+                    description: None,
+                    additional: std::collections::HashMap::from([(
+                        "scheme".to_string(),
+                        serde_json::json!("bearer"),
+                    )]),
+                },
+            )])),
+            replies: None,
+            reply_addresses: None,
+            channel_bindings: None,
+        });
+
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::UnknownSecurityScheme {
+                location: "operation 'sendMessage'".to_string(),
+                reference: "#/components/securitySchemes/berer".to_string(),
+            }]
+        );
+        assert_eq!(issues[0].severity(), Severity::Error);
+        assert_eq!(
+            issues[0].to_string(),
+            "operation 'sendMessage': security scheme '#/components/securitySchemes/berer' \
+             is not defined in components.securitySchemes"
+        );
+
+        // Without components, every local ref is unresolved
+        spec.components = None;
+        assert_eq!(spec.validate().len(), 3);
+    }
+
+    #[test]
+    fn test_validate_server_hosts() {
+        let server = |host: &str| Server {
+            host: host.to_string(),
+            protocol: "wss".to_string(),
+            pathname: None,
+            description: None,
+            variables: None,
+            security: None,
+        };
+        let spec = AsyncApiSpec {
+            servers: Some(std::collections::HashMap::from([
+                ("production".to_string(), server("api.example.com:443")),
+                ("staging".to_string(), server("http://staging.example.com")),
+            ])),
+            ..AsyncApiSpec::default()
+        };
+
+        let issues = spec.validate();
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            ValidationIssue::InvalidServerHost { server, .. } if server == "staging"
+        ));
+        assert!(issues[0].to_string().starts_with("server 'staging': host"));
+    }
+
+    #[test]
+    fn test_validate_message_payloads() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Payloads", "version": "1.0.0" },
+            "channels": {
+                "chat": {
+                    "messages": {
+                        "chat.message": { "$ref": "#/components/messages/chat.message" },
+                        "chat.typing": { "$ref": "#/components/messages/chat.typing" },
+                        "chat.ping": { "$ref": "#/components/messages/chat.ping" },
+                        "chat.inline": { "name": "chat.inline" }
+                    }
+                }
+            },
+            "operations": {
+                "sendChat": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/chat" },
+                    "messages": [
+                        { "$ref": "#/channels/chat/messages/chat.message" },
+                        { "$ref": "#/channels/chat/messages/chat.typing" },
+                        { "$ref": "#/channels/chat/messages/chat.inline" },
+                        { "$ref": "#/channels/chat/messages/missing" }
+                    ],
+                    "reply": {
+                        "messages": [
+                            { "$ref": "#/components/messages/chat.ping" },
+                            { "$ref": "#/components/messages/chat.typing" }
+                        ]
+                    }
+                },
+                "joinChat": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/chat" },
+                    "reply": { "$ref": "#/components/replies/joined" }
+                }
+            },
+            "components": {
+                "messages": {
+                    "chat.message": { "payload": { "type": "object" } },
+                    "chat.typing": {},
+                    "chat.ping": { "x-no-payload": true },
+                    "chat.joined": {},
+                    "chat.unused": {}
+                },
+                "replies": {
+                    "joined": {
+                        "messages": [{ "$ref": "#/components/messages/chat.joined" }]
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            spec.validate_message_payloads(),
+            vec!["chat.inline", "chat.joined", "chat.typing"]
+        );
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::MissingPayload {
+                    message: "chat.inline".to_string()
+                },
+                ValidationIssue::MissingPayload {
+                    message: "chat.joined".to_string()
+                },
+                ValidationIssue::MissingPayload {
+                    message: "chat.typing".to_string()
+                },
+            ]
+        );
+        assert_eq!(issues[0].severity(), Severity::Warning);
+        assert!(issues[2].to_string().starts_with("message 'chat.typing'"));
+    }
+
+    #[test]
+    fn test_validate_empty_replies() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Replies", "version": "1.0.0" },
+            "channels": {
+                "requests": { "address": "requests" },
+                "responses": { "address": "responses" }
+            },
+            "operations": {
+                "withChannel": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/requests" },
+                    "reply": { "channel": { "$ref": "#/channels/responses" } }
+                },
+                "withMessages": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/requests" },
+                    "reply": { "messages": [{ "name": "pong", "payload": { "type": "object" } }] }
+                },
+                "empty": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/requests" },
+                    "reply": { "messages": [] }
+                },
+                "shared": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/requests" },
+                    "reply": { "$ref": "#/components/replies/addressOnly" }
+                }
+            },
+            "components": {
+                "replies": {
+                    "addressOnly": { "address": { "location": "$message.header#/replyTo" } },
+                    "answer": { "channel": { "$ref": "#/channels/responses" } }
+                }
+            }
+        }))
+        .unwrap();
+
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::EmptyReply {
+                    location: "operation 'empty'".to_string()
+                },
+                ValidationIssue::EmptyReply {
+                    location: "reply 'addressOnly'".to_string()
+                },
+            ]
+        );
+        assert_eq!(issues[0].severity(), Severity::Error);
+        assert_eq!(
+            issues[1].to_string(),
+            "reply 'addressOnly': reply has no messages and no channel"
+        );
+    }
+
+    #[test]
+    fn test_validate_operation_channels() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Operations", "version": "1.0.0" },
+            "channels": { "chat": { "address": "chat" } },
+            "operations": {
+                "send": {
+                    "action": "send",
+                    "channel": { "$ref": "#/channels/chat" },
+                    "x-redeclared": true
+                },
+                "lost": { "action": "send", "channel": { "$ref": "#/channels/lobby" } },
+                "remote": { "action": "send", "channel": { "$ref": "other.json#/channels/chat" } }
+            }
+        }))
+        .unwrap();
+
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::UnknownOperationChannel {
+                    operation: "lost".to_string(),
+                    reference: "#/channels/lobby".to_string()
+                },
+                ValidationIssue::RedeclaredOperation {
+                    operation: "send".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "operation 'lost': channel '#/channels/lobby' is not defined in channels"
+        );
+    }
+
+    #[test]
+    fn test_validate_websocket_binding_placement() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Bindings", "version": "1.0.0" },
+            "channels": {
+                "chat": {
+                    "address": "/ws/chat",
+                    "bindings": {
+                        "ws": {
+                            "method": "GET",
+                            "query": {
+                                "type": "object",
+                                "properties": { "token": { "type": "string" } }
+                            },
+                            "bindingVersion": "0.1.0"
+                        }
+                    },
+                    "messages": {
+                        "chat.inline": {
+                            "payload": { "type": "object" },
+                            "bindings": { "ws": { "headers": { "type": "object" } } }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "messages": {
+                    "chat.message": {
+                        "payload": { "type": "object" },
+                        "bindings": { "ws": { "bindingVersion": "0.1.0" } }
+                    },
+                    "chat.join": {
+                        "payload": { "type": "object" },
+                        "bindings": {
+                            "ws": {
+                                "query": { "type": "object" },
+                                "method": "GET",
+                                "x-note": "kept"
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::WebSocketMessageBinding {
+                    message: "chat.join".to_string(),
+                    fields: vec!["method".to_string(), "query".to_string()],
+                },
+                ValidationIssue::WebSocketMessageBinding {
+                    message: "chat.inline".to_string(),
+                    fields: vec!["headers".to_string()],
+                },
+            ]
+        );
+        assert_eq!(issues[0].severity(), Severity::Error);
+        assert_eq!(
+            issues[0].to_string(),
+            "message 'chat.join': WebSocket message bindings have no fields, found 'method', \
+             'query'; move method/query/headers to the channel's ws binding"
+        );
+    }
+
+    #[test]
+    fn test_validate_correlation_headers() {
+        let message = |location: &str| {
+            serde_json::json!({
+                "payload": { "type": "object" },
+                "headers": {
+                    "type": "object",
+                    "properties": { "requestId": { "type": "string" } }
+                },
+                "correlationId": { "location": location }
+            })
+        };
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Correlation", "version": "1.0.0" },
+            "components": {
+                "messages": {
+                    "request": message("$message.header#/requestId"),
+                    "traced": message("$message.header#/traceId"),
+                    "byPayload": message("$message.payload#/id")
+                }
+            }
+        }))
+        .unwrap();
+
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::MissingCorrelationHeader {
+                message: "traced".to_string(),
+                header: "traceId".to_string(),
+            }]
+        );
+        assert_eq!(issues[0].severity(), Severity::Warning);
+        assert_eq!(
+            issues[0].to_string(),
+            "message 'traced' has a correlationId in header 'traceId', which is not defined \
+             in its headers schema"
+        );
+    }
+
+    #[test]
+    fn test_validate_operation_id_collisions() {
+        let operation =
+            || serde_json::json!({ "action": "send", "channel": { "$ref": "#/channels/chat" } });
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Operations", "version": "1.0.0" },
+            "channels": { "chat": { "address": "/ws/chat" } },
+            "operations": {
+                "sendMessage": operation(),
+                "SendMessage": operation(),
+                "sendmessage": operation(),
+                "receiveMessage": operation()
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            spec.validate_unique_operation_ids(),
+            vec!["SendMessage", "sendMessage", "sendmessage"]
+        );
+        let issues = spec.validate();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(
+            issues[0],
+            ValidationIssue::DuplicateOperationId {
+                operation: "SendMessage".to_string()
+            }
+        );
+        assert_eq!(issues[0].severity(), Severity::Error);
+        assert_eq!(
+            issues[0].to_string(),
+            "operation 'SendMessage' differs from another operation ID only in case"
+        );
+    }
+
+    #[test]
+    fn test_validate_message_name_collisions() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Messages", "version": "1.0.0" },
+            "channels": {
+                "chat": {
+                    "messages": {
+                        "ping": { "$ref": "#/components/messages/ping" },
+                        "pong": { "name": "pong", "payload": { "type": "object" } },
+                        "join": { "name": "user.join", "payload": { "type": "string" } }
+                    }
+                },
+                "lobby": {
+                    "messages": {
+                        "pong": { "name": "pong", "payload": { "type": "object" } },
+                        "typing": { "payload": { "type": "boolean" } }
+                    }
+                }
+            },
+            "components": {
+                "messages": {
+                    "ping": { "payload": { "type": "object" } },
+                    "join": { "name": "user.join", "payload": { "type": "object" } },
+                    "joinV2": { "name": "user.join", "payload": { "type": "object" } },
+                    "typing": { "name": "typing", "payload": { "type": "boolean" } }
+                }
+            }
+        }))
+        .unwrap();
+
+        // `pong` is the same definition in both channels, and the channel's `typing`
+        // is the component without its name
+        assert_eq!(spec.validate_unique_message_names(), vec!["user.join"]);
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::DuplicateMessageName {
+                message: "user.join".to_string()
+            }]
+        );
+        assert!(
+            issues[0]
+                .to_string()
+                .starts_with("message name 'user.join'")
+        );
+    }
+
+    #[test]
+    fn test_validate_repeated_message_names() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Messages", "version": "1.0.0" },
+            "components": {
+                "messages": {
+                    "ping": { "payload": { "type": "object" } },
+                    "join": { "name": "user.join", "payload": { "type": "object" } },
+                    "joinV2": { "name": "user.join", "payload": { "type": "object" } }
+                }
+            }
+        }))
+        .unwrap();
+
+        // Identical bodies aren't a conflicting definition, but the name repeats
+        assert!(spec.validate_unique_message_names().is_empty());
+        let issues = spec.validate();
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::RepeatedMessageName {
+                message: "user.join".to_string(),
+                keys: vec!["join".to_string(), "joinV2".to_string()],
+            }]
+        );
+        assert_eq!(issues[0].severity(), Severity::Warning);
+        assert_eq!(
+            issues[0].to_string(),
+            "message name 'user.join' is shared by components.messages 'join', 'joinV2'; \
+             generated code will clash even though the definitions match"
+        );
+    }
+
+    #[test]
+    fn test_validate_channel_content_types() {
+        let spec = AsyncApiSpec::try_from(serde_json::json!({
+            "asyncapi": "3.0.0",
+            "info": { "title": "Sensors", "version": "1.0.0" },
+            "channels": {
+                "readings": {
+                    "x-default-content-type": "application/cbor",
+                    "messages": {
+                        "reading": { "$ref": "#/components/messages/reading" },
+                        "derived": { "$ref": "#/components/messages/derived" },
+                        "own": { "$ref": "#/components/messages/own" }
+                    }
+                },
+                "raw": {
+                    "x-default-content-type": "application/octet-stream",
+                    "messages": {
+                        "reading": { "$ref": "#/components/messages/reading" },
+                        "derived": { "$ref": "#/components/messages/derived" },
+                        "own": { "$ref": "#/components/messages/own" }
+                    }
+                }
+            },
+            "components": {
+                "messages": {
+                    "reading": {},
+                    "derived": { "contentType": "application/octet-stream" },
+                    "own": { "contentType": "application/json" }
+                }
+            }
+        }))
+        .unwrap();
+
+        let content_types = vec![
+            "application/cbor".to_string(),
+            "application/octet-stream".to_string(),
+        ];
+        assert_eq!(
+            spec.validate(),
+            vec![
+                ValidationIssue::ConflictingContentTypes {
+                    message: "derived".to_string(),
+                    content_types: content_types.clone(),
+                },
+                ValidationIssue::ConflictingContentTypes {
+                    message: "reading".to_string(),
+                    content_types,
+                },
+            ]
+        );
+        // The first channel by name wins
+        assert_eq!(
+            spec.message_content_type("reading"),
+            Some("application/octet-stream")
+        );
+        assert_eq!(spec.message_content_type("own"), Some("application/json"));
+    }
+}
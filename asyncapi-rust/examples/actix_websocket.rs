@@ -97,7 +97,7 @@ pub enum ChatMessage {
 #[asyncapi_channel(name = "chat", address = "/ws")]
 #[asyncapi_operation(name = "sendMessage", action = "send", channel = "chat")]
 #[asyncapi_operation(name = "receiveMessage", action = "receive", channel = "chat")]
-struct ChatApi;
+pub struct ChatApi;
 
 fn main() {
     println!("🚀 actix-web + AsyncAPI Integration Example\n");
@@ -16,12 +16,12 @@ use asyncapi_rust::{AsyncApi, AsyncApiSpec};
     version = "1.0.0",
     description = "Real-time chat application using WebSocket for bidirectional communication"
 )]
-struct ChatApi;
+pub struct ChatApi;
 
 /// Minimal API specification without description
 #[derive(AsyncApi)]
 #[asyncapi(title = "Minimal API", version = "0.1.0")]
-struct MinimalApi;
+pub struct MinimalApi;
 
 fn main() {
     println!("🚀 AsyncApi Derive Macro Example\n");
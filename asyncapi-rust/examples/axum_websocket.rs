@@ -123,7 +123,7 @@ pub enum PubSubMessage {
 #[asyncapi_channel(name = "pubsub", address = "/ws")]
 #[asyncapi_operation(name = "clientSend", action = "send", channel = "pubsub")]
 #[asyncapi_operation(name = "serverSend", action = "receive", channel = "pubsub")]
-struct PubSubApi;
+pub struct PubSubApi;
 
 fn main() {
     println!("🚀 axum WebSocket + AsyncAPI Integration Example\n");
@@ -8,7 +8,7 @@
 //! - Serializing to JSON for documentation
 
 use asyncapi_rust::{
-    AsyncApiSpec, Channel, Components, Info, Message, Operation, OperationAction, Server,
+    AsyncApiSpec, Channel, Components, IndexMap, Info, Message, Operation, OperationAction, Server,
     ToAsyncApiMessage, schemars::JsonSchema,
 };
 use serde::{Deserialize, Serialize};
@@ -108,21 +108,13 @@ fn main() {
     println!("   Version: {}", spec.asyncapi);
     println!("   Title: {}", spec.info.title);
     println!("   API Version: {}", spec.info.version);
-    if let Some(channels) = &spec.channels {
-        println!("   Channels: {}", channels.len());
-    }
-    if let Some(operations) = &spec.operations {
-        println!("   Operations: {}", operations.len());
-    }
-    if let Some(components) = &spec.components {
-        if let Some(msgs) = &components.messages {
-            println!("   Messages: {}", msgs.len());
-        }
+    for line in spec.summary_stats().to_string().lines() {
+        println!("   {}", line);
     }
 }
 
 /// Build a complete AsyncAPI specification with server, channels, and operations
-fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
+pub fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
     // Define server
     let mut servers = HashMap::new();
     servers.insert(
@@ -133,6 +125,7 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
             pathname: None,
             description: Some("Production WebSocket server".to_string()),
             variables: None,
+            security: None,
         },
     );
 
@@ -142,13 +135,19 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
         "chat".to_string(),
         Channel {
             address: Some("/ws/chat".to_string()),
+            title: Some("Chat".to_string()),
             messages: None, // Messages defined in components
             parameters: None,
+            tags: None,
+            external_docs: None,
+            bindings: None,
+            deprecated: None,
+            default_content_type: None,
         },
     );
 
     // Define operations (send and receive)
-    let mut operations = HashMap::new();
+    let mut operations = IndexMap::new();
 
     operations.insert(
         "sendMessage".to_string(),
@@ -157,6 +156,9 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
             channel: asyncapi_rust::ChannelRef {
                 reference: "#/channels/chat".to_string(),
             },
+            title: None,
+            summary: Some("Send a chat message to the room".to_string()),
+            description: None,
             messages: Some(
                 messages
                     .iter()
@@ -169,6 +171,12 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
                     })
                     .collect(),
             ),
+            reply: None,
+            tags: None,
+            external_docs: None,
+            security: None,
+            bindings: None,
+            extensions: HashMap::new(),
         },
     );
 
@@ -179,6 +187,9 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
             channel: asyncapi_rust::ChannelRef {
                 reference: "#/channels/chat".to_string(),
             },
+            title: None,
+            summary: Some("Receive chat messages from the room".to_string()),
+            description: None,
             messages: Some(
                 messages
                     .iter()
@@ -191,6 +202,12 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
                     })
                     .collect(),
             ),
+            reply: None,
+            tags: None,
+            external_docs: None,
+            security: None,
+            bindings: None,
+            extensions: HashMap::new(),
         },
     );
 
@@ -205,6 +222,10 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
     let components = Components {
         messages: Some(component_messages),
         schemas: None,
+        security_schemes: None,
+        replies: None,
+        reply_addresses: None,
+        channel_bindings: None,
     };
 
     // Build the complete spec
@@ -217,8 +238,13 @@ fn build_asyncapi_spec(messages: Vec<Message>) -> AsyncApiSpec {
                 "Real-time chat application using WebSocket for bidirectional communication"
                     .to_string(),
             ),
+            summary: None,
+            contact: None,
+            license: None,
+            tags: None,
         },
         servers: Some(servers),
+        default_content_type: None,
         channels: Some(channels),
         operations: Some(operations),
         components: Some(components),
@@ -43,7 +43,7 @@ pub enum ApiMessage {
 #[asyncapi_channel(name = "main", address = "/ws")]
 #[asyncapi_operation(name = "send", action = "send", channel = "main")]
 #[asyncapi_operation(name = "receive", action = "receive", channel = "main")]
-struct ExampleApi;
+pub struct ExampleApi;
 
 fn main() {
     println!("📚 Framework Integration Guide\n");
@@ -35,7 +35,7 @@ use asyncapi_rust::AsyncApi;
     action = "receive",
     channel = "notifications"
 )]
-struct ChatApi;
+pub struct ChatApi;
 
 fn main() {
     println!("🚀 Complete AsyncAPI Specification Example\n");
@@ -82,9 +82,10 @@ fn main() {
     if let Some(operations) = &spec.operations {
         println!("⚡ Operations ({}):", operations.len());
         for (name, operation) in operations {
-            let action = match operation.action {
-                asyncapi_rust::OperationAction::Send => "send",
-                asyncapi_rust::OperationAction::Receive => "receive",
+            let action = if operation.action_is_send() {
+                "send"
+            } else {
+                "receive"
             };
             println!("  • {} ({})", name, action);
             println!("    Channel: {}", operation.channel.reference);
@@ -36,7 +36,7 @@ use std::path::Path;
     version = "1.0.0",
     description = "A real-time API for demonstration purposes"
 )]
-struct ExampleApi;
+pub struct ExampleApi;
 
 fn main() {
     println!("🚀 Generating AsyncAPI specification file...\n");
@@ -82,7 +82,7 @@ pub enum SystemMessage {
 #[asyncapi_operation(name = "sendMessage", action = "send", channel = "chat", messages = [ChatMessage])]
 #[asyncapi_operation(name = "receiveMessage", action = "receive", channel = "chat", messages = [ChatMessage, SystemMessage])]
 #[asyncapi_messages(ChatMessage, SystemMessage)]
-struct ChatApi;
+pub struct ChatApi;
 
 fn main() {
     println!("=== Message Integration Example ===\n");
@@ -116,7 +116,7 @@ fn main() {
             if let Some(messages) = &channel.messages {
                 println!("    Channel Messages:");
                 for (msg_name, msg_ref) in messages {
-                    if let asyncapi_rust::MessageRef::Reference { reference } = msg_ref {
+                    if let Some(reference) = msg_ref.as_reference() {
                         println!("      - {}: {}", msg_name, reference);
                     }
                 }
@@ -129,9 +129,10 @@ fn main() {
     if let Some(operations) = &spec.operations {
         println!("Operations:");
         for (name, operation) in operations {
-            let action = match operation.action {
-                asyncapi_rust::OperationAction::Send => "send",
-                asyncapi_rust::OperationAction::Receive => "receive",
+            let action = if operation.action_is_send() {
+                "send"
+            } else {
+                "receive"
             };
             println!(
                 "  - {}: {} to {}",
@@ -140,7 +141,7 @@ fn main() {
             if let Some(messages) = &operation.messages {
                 println!("    Messages:");
                 for msg in messages {
-                    if let asyncapi_rust::MessageRef::Reference { reference } = msg {
+                    if let Some(reference) = msg.as_reference() {
                         println!("      - {}", reference);
                     }
                 }
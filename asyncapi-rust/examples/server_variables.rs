@@ -1,11 +1,12 @@
 //! Server Variables and Channel Parameters Example
 //!
-//! Demonstrates how to define dynamic server paths and typed channel parameters
-//! for user-specific WebSocket connections.
+//! Demonstrates how to define dynamic server paths and channel parameters for
+//! user-specific WebSocket connections.
 //!
 //! This example shows:
 //! - Server variables with pathname, examples, and enum values
-//! - Channel parameters with JSON Schema types and formats
+//! - Channel parameters with descriptions (AsyncAPI 3.0 parameters have no schema,
+//!   so `schema_type` and `format` would fail `validate_schema()`)
 //! - Multiple variables and parameters
 //! - Complete AsyncAPI 3.0 spec generation
 //!
@@ -104,16 +105,10 @@ pub enum UserMessage {
 #[asyncapi_channel(
     name = "userMessaging",
     address = "/api/{version}/ws/{userId}",
-    parameter(
-        name = "version",
-        description = "API version number",
-        schema_type = "string"
-    ),
+    parameter(name = "version", description = "API version number"),
     parameter(
         name = "userId",
-        description = "Unique identifier for the authenticated user",
-        schema_type = "integer",
-        format = "int64"
+        description = "Unique identifier for the authenticated user"
     )
 )]
 #[asyncapi_operation(
@@ -123,7 +118,7 @@ pub enum UserMessage {
 )]
 #[asyncapi_operation(name = "receiveUpdates", action = "receive", channel = "userMessaging")]
 #[asyncapi_messages(UserMessage)]
-struct UserApi;
+pub struct UserApi;
 
 fn main() {
     println!("=== Server Variables and Channel Parameters Example ===\n");
@@ -198,9 +193,10 @@ fn main() {
     println!("⚡ Operations:");
     if let Some(operations) = &spec.operations {
         for (name, operation) in operations {
-            let action_str = match operation.action {
-                asyncapi_rust::OperationAction::Send => "send",
-                asyncapi_rust::OperationAction::Receive => "receive",
+            let action_str = if operation.action_is_send() {
+                "send"
+            } else {
+                "receive"
             };
             println!("  • {} ({})", name, action_str);
             println!("    Channel: {}", operation.channel.reference);
@@ -234,7 +230,6 @@ fn main() {
     println!("\nKey Features Demonstrated:");
     println!("  • Server variables for dynamic paths (version, userId)");
     println!("  • Multiple servers (production, staging)");
-    println!("  • Channel parameters with JSON Schema types");
-    println!("  • Typed parameters (string for version, integer/int64 for userId)");
+    println!("  • Channel parameters (version, userId)");
     println!("  • Complete spec with servers, channels, operations, and messages");
 }
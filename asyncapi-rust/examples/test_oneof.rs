@@ -16,8 +16,9 @@ fn main() {
     for (idx, msg) in messages.iter().enumerate() {
         println!("\n=== Message {} ===", idx);
         println!("Name: {:?}", msg.name);
-        println!("Payload:");
         if let Some(ref payload) = msg.payload {
+            println!("Summary: {}", payload.summary_string());
+            println!("Payload:");
             let json = serde_json::to_string_pretty(payload).unwrap();
             println!("{}", json);
         }
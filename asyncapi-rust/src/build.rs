@@ -0,0 +1,243 @@
+//! Helpers for writing generated specs to disk
+//!
+//! Intended for `build.rs` scripts or small binaries that keep a committed
+//! `asyncapi.json` in sync with the code. [`write_spec`] regenerates the file,
+//! while [`check_spec`] fails if the committed file is stale, which is useful
+//! for detecting drift in CI.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use asyncapi_rust::{AsyncApi, build};
+//! use std::path::Path;
+//!
+//! #[derive(AsyncApi)]
+//! #[asyncapi(title = "Chat API", version = "1.0.0")]
+//! struct ChatApi;
+//!
+//! let spec = ChatApi::asyncapi_spec();
+//! let out = Path::new("asyncapi.json");
+//!
+//! if std::env::var_os("CI").is_some() {
+//!     // Fail with a diff if the committed spec doesn't match the code
+//!     build::check_spec(&spec, out).unwrap();
+//! } else {
+//!     build::write_spec(&spec, out).unwrap();
+//! }
+//! ```
+
+use crate::AsyncApiSpec;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Error returned by [`write_spec`] and [`check_spec`]
+#[derive(Debug)]
+pub enum SpecFileError {
+    /// Reading or writing the spec file failed
+    Io(std::io::Error),
+    /// The spec could not be serialized to JSON
+    Serialize(serde_json::Error),
+    /// The committed spec file doesn't match the generated spec
+    Stale {
+        /// Path of the committed spec file
+        path: PathBuf,
+        /// Differing lines, prefixed with `-` (committed) or `+` (generated)
+        diff: Vec<String>,
+    },
+}
+
+impl fmt::Display for SpecFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecFileError::Io(err) => write!(f, "failed to access spec file: {}", err),
+            SpecFileError::Serialize(err) => write!(f, "failed to serialize spec: {}", err),
+            SpecFileError::Stale { path, diff } => {
+                writeln!(
+                    f,
+                    "committed spec {} is stale; regenerate it with write_spec:",
+                    path.display()
+                )?;
+                for line in diff {
+                    writeln!(f, "{}", line)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SpecFileError::Io(err) => Some(err),
+            SpecFileError::Serialize(err) => Some(err),
+            SpecFileError::Stale { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SpecFileError {
+    fn from(err: std::io::Error) -> Self {
+        SpecFileError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SpecFileError {
+    fn from(err: serde_json::Error) -> Self {
+        SpecFileError::Serialize(err)
+    }
+}
+
+/// Render a spec as pretty-printed JSON with a trailing newline
+///
+/// The spec's maps serialize with their keys sorted, so the output is stable
+/// across runs. Operations keep their declaration order.
+pub fn render_spec(spec: &AsyncApiSpec) -> Result<String, SpecFileError> {
+    let mut json = serde_json::to_string_pretty(spec)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Write a spec to `out` as pretty-printed JSON
+///
+/// The file is only rewritten when its contents change, so build scripts don't
+/// trigger needless rebuilds of anything watching it.
+pub fn write_spec(spec: &AsyncApiSpec, out: &Path) -> Result<(), SpecFileError> {
+    let json = render_spec(spec)?;
+    if std::fs::read_to_string(out).is_ok_and(|existing| existing == json) {
+        return Ok(());
+    }
+    std::fs::write(out, json)?;
+    Ok(())
+}
+
+/// Check that the committed spec at `committed` matches `spec`
+///
+/// Returns [`SpecFileError::Stale`] listing the differing lines if the file is
+/// out of date. A missing file counts as stale.
+pub fn check_spec(spec: &AsyncApiSpec, committed: &Path) -> Result<(), SpecFileError> {
+    let json = render_spec(spec)?;
+    let existing = match std::fs::read_to_string(committed) {
+        Ok(existing) => existing,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let diff = diff_lines(&existing, &json);
+    if diff.is_empty() {
+        Ok(())
+    } else {
+        Err(SpecFileError::Stale {
+            path: committed.to_path_buf(),
+            diff,
+        })
+    }
+}
+
+/// Line-by-line comparison of two rendered specs
+fn diff_lines(committed: &str, generated: &str) -> Vec<String> {
+    let committed: Vec<&str> = committed.lines().collect();
+    let generated: Vec<&str> = generated.lines().collect();
+
+    let mut diff = Vec::new();
+    for index in 0..committed.len().max(generated.len()) {
+        let old = committed.get(index);
+        let new = generated.get(index);
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            diff.push(format!("-{}: {}", index + 1, old));
+        }
+        if let Some(new) = new {
+            diff.push(format!("+{}: {}", index + 1, new));
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OperationAction;
+
+    #[test]
+    fn test_write_then_check_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("asyncapi.json");
+        let spec = AsyncApiSpec::builder()
+            .title("Drift API")
+            .version("1.0.0")
+            .channel("chat", |c| c.address("/ws/chat"))
+            .channel("events", |c| c.address("/ws/events"))
+            .build();
+
+        write_spec(&spec, &out).unwrap();
+        check_spec(&spec, &out).unwrap();
+
+        let written: AsyncApiSpec =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(written.info.title, "Drift API");
+    }
+
+    #[test]
+    fn test_write_spec_keeps_operation_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("asyncapi.json");
+        let spec = AsyncApiSpec::builder()
+            .title("Order API")
+            .version("1.0.0")
+            .channel("chat", |c| c.address("/ws/chat"))
+            .operation("zSend", OperationAction::Send, "chat", |o| o)
+            .operation("aReceive", OperationAction::Receive, "chat", |o| o)
+            .build();
+
+        write_spec(&spec, &out).unwrap();
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        let send = written.find("\"zSend\"").unwrap();
+        let receive = written.find("\"aReceive\"").unwrap();
+        assert!(send < receive);
+    }
+
+    #[test]
+    fn test_check_spec_reports_stale_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("asyncapi.json");
+        let old_spec = AsyncApiSpec::builder()
+            .title("Drift API")
+            .version("1.0.0")
+            .build();
+        write_spec(&old_spec, &out).unwrap();
+
+        let new_spec = AsyncApiSpec::builder()
+            .title("Drift API")
+            .version("1.1.0")
+            .build();
+        match check_spec(&new_spec, &out) {
+            Err(SpecFileError::Stale { path, diff }) => {
+                assert_eq!(path, out);
+                assert!(
+                    diff.iter()
+                        .any(|line| line.starts_with('-') && line.contains("1.0.0"))
+                );
+                assert!(
+                    diff.iter()
+                        .any(|line| line.starts_with('+') && line.contains("1.1.0"))
+                );
+            }
+            other => panic!("Expected stale spec error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_spec_missing_file_is_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("missing.json");
+        let spec = AsyncApiSpec::default();
+
+        let err = check_spec(&spec, &out).unwrap_err();
+        assert!(matches!(err, SpecFileError::Stale { .. }));
+        assert!(err.to_string().contains("is stale"));
+    }
+}
@@ -80,11 +80,40 @@
 //! - `#[asyncapi_operation(...)]` - Operation definitions (with optional `messages` parameter)
 //! - `#[asyncapi_messages(...)]` - Include message types in components
 //!
+//! With the `operation-fn` feature, operations can also be declared on the functions that
+//! implement them with `#[asyncapi_operation_fn(api = ChatApi, ...)]`; see the
+//! `operation_fn` module.
+//!
 //! When you specify messages in operations, they are automatically added to the channel
 //! that the operation references. Operations reference channel messages
 //! (`#/channels/{channel}/messages/{message}`), while channels reference components
 //! (`#/components/messages/{message}`), following AsyncAPI 3.0 specification.
 //!
+//! ### Runtime Specs with `AsyncApiSpec::builder()`
+//!
+//! For channels registered at runtime (e.g. plugin systems), build the spec dynamically:
+//!
+//! ```rust,ignore
+//! let spec = AsyncApiSpec::builder()
+//!     .title("Chat API")
+//!     .version("1.0.0")
+//!     .channel("chat", |c| c.address("/ws/chat").message::<ChatMessage>())
+//!     .operation("sendMessage", OperationAction::Send, "chat", |o| o.message::<ChatMessage>())
+//!     .build();
+//! ```
+//!
+//! The [`spec!`] macro expands to the same builder calls with less boilerplate, which
+//! suits tests and small scripts:
+//!
+//! ```rust,ignore
+//! let spec = spec! {
+//!     title: "Chat API",
+//!     version: "1.0.0",
+//!     channels: { "chat": { address: "/ws/chat", messages: [ChatMessage] } },
+//!     operations: { "sendMessage": { action: send, channel: "chat", messages: [ChatMessage] } },
+//! };
+//! ```
+//!
 //! ## Framework Integration
 //!
 //! Works with any WebSocket framework:
@@ -103,6 +132,16 @@
 //! - **Framework agnostic**: Works with actix-ws, axum, or any serde-compatible types
 //! - **Binary protocols**: Support for mixed text/binary WebSocket messages
 //!
+//! ## Cargo Features
+//!
+//! - `validate-schema` - Adds `AsyncApiSpec::validate_schema()` for checking generated
+//!   documents against the AsyncAPI 3.0 JSON Schema, and `Message::payload_validator()` for
+//!   enforcing a message's documented schema on incoming payloads at runtime
+//! - `yaml` - Adds `AsyncApiSpec::to_yaml()` and `AsyncApiSpec::from_yaml()` for writing and
+//!   reading specs as `.yaml` files. Uses `serde_yaml` 0.9, which is deprecated upstream
+//! - `operation-fn` - Adds `#[asyncapi_operation_fn]` for declaring operations on the
+//!   functions that implement them, registered with `inventory`
+//!
 //! ## Examples
 //!
 //! See the `examples/` directory for complete working examples:
@@ -138,6 +177,10 @@
 //!
 //! Then run: `cargo run --bin generate-asyncapi`
 //!
+//! The [`build`] module wraps this pattern: [`build::write_spec`] writes the file with stable
+//! key ordering, and [`build::check_spec`] returns an error listing the changed lines when the
+//! committed file no longer matches the code, so CI can catch a stale spec.
+//!
 //! ## Further Reading
 //!
 //! - [AsyncAPI Specification](https://www.asyncapi.com/docs/reference/specification/v3.0.0)
@@ -147,7 +190,13 @@
 #![deny(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod build;
+#[cfg(feature = "operation-fn")]
+pub mod operation_fn;
+
 // Re-export proc macros from asyncapi-rust-codegen
+#[cfg(feature = "operation-fn")]
+pub use asyncapi_rust_codegen::asyncapi_operation_fn;
 pub use asyncapi_rust_codegen::{AsyncApi, ToAsyncApiMessage};
 
 // Re-export models
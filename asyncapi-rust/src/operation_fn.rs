@@ -0,0 +1,254 @@
+//! Operations declared on functions
+//!
+//! Some applications model operations as functions, e.g. the handler that sends a
+//! message. [`#[asyncapi_operation_fn(...)]`](crate::asyncapi_operation_fn) attaches
+//! an operation to such a function and registers it with [`inventory`]; the
+//! `AsyncApi` derive of the type named by `api` collects the registrations when
+//! `asyncapi_spec()` runs. Available with the `operation-fn` feature.
+//!
+//! Registered operations follow the API's `order`, `ref_style`, `ref_base` and
+//! content-type defaults like `#[asyncapi_operation]`s. An undeclared channel or a
+//! name that is already taken is reported by [`AsyncApiSpec::validate`]; under
+//! the API's `strict`, an undeclared channel or a name taken by an
+//! `#[asyncapi_operation]` is a compile error.
+//!
+//! # Example
+//!
+//! ```rust
+//! use asyncapi_rust::{AsyncApi, ToAsyncApiMessage, asyncapi_operation_fn, schemars::JsonSchema};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+//! #[serde(tag = "type")]
+//! pub enum ChatMessage {
+//!     Chat { text: String },
+//! }
+//!
+//! #[derive(AsyncApi)]
+//! #[asyncapi(title = "Chat API", version = "1.0.0")]
+//! #[asyncapi_channel(name = "chat", address = "/ws/chat")]
+//! struct ChatApi;
+//!
+//! /// Broadcast a message to everyone in the room
+//! #[asyncapi_operation_fn(api = ChatApi, action = "send", channel = "chat", messages = [ChatMessage])]
+//! fn broadcast(text: &str) -> ChatMessage {
+//!     ChatMessage::Chat { text: text.to_string() }
+//! }
+//!
+//! let spec = ChatApi::asyncapi_spec();
+//! let operation = &spec.operations.as_ref().unwrap()["broadcast"];
+//! assert_eq!(
+//!     operation.description.as_deref(),
+//!     Some("Broadcast a message to everyone in the room")
+//! );
+//! ```
+
+use crate::{
+    AsyncApiSpec, ChannelRef, Components, Message, MessageRef, Operation, OperationAction,
+    REDECLARED_EXTENSION,
+};
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+
+#[doc(hidden)]
+pub use inventory;
+
+/// Operation registered by `#[asyncapi_operation_fn(...)]`
+///
+/// Built by the attribute macro; there's normally no need to create one by hand.
+pub struct OperationFn {
+    /// `TypeId` of the `AsyncApi` type the operation belongs to
+    pub api: fn() -> TypeId,
+    /// Operation name, the function name unless overridden
+    pub name: &'static str,
+    /// Operation action
+    pub action: OperationAction,
+    /// Name of the channel the operation uses
+    pub channel: &'static str,
+    /// Operation summary
+    pub summary: Option<&'static str>,
+    /// Operation description, the function's rustdoc unless overridden
+    pub description: Option<&'static str>,
+    /// Messages of the operation's message types
+    pub messages: fn() -> Vec<Message>,
+    /// Whether the named message declares its own content type
+    pub declared_content_type: fn(&str) -> bool,
+    /// Sort hint; lower values come first, unordered operations go last
+    pub order: Option<i64>,
+}
+
+inventory::collect!(OperationFn);
+
+/// Settings of an `AsyncApi` derive that its registered operations follow
+///
+/// Emitted by the derive; there's normally no need to create one by hand.
+pub struct ApiOptions {
+    /// `#[asyncapi(strict)]`
+    pub strict: bool,
+    /// Operation message refs point at components (`ref_style = "components"`)
+    pub components_ref_style: bool,
+    /// Prefix of component message refs, including any `ref_base`
+    pub components_ref_prefix: &'static str,
+    /// Names of the channels declared with `#[asyncapi_channel(...)]`
+    pub channels: &'static [&'static str],
+    /// Names and `order` hints of the `#[asyncapi_operation(...)]`s, in emitted order
+    pub operations: &'static [(&'static str, Option<i64>)],
+}
+
+impl ApiOptions {
+    /// Whether `name` is a declared channel
+    pub const fn has_channel(&self, name: &str) -> bool {
+        let mut i = 0;
+        while i < self.channels.len() {
+            if str_eq(self.channels[i], name) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Whether `name` is taken by an `#[asyncapi_operation(...)]`
+    pub const fn has_operation(&self, name: &str) -> bool {
+        let mut i = 0;
+        while i < self.operations.len() {
+            if str_eq(self.operations[i].0, name) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+}
+
+/// `a == b`, usable in const contexts
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Get the operations registered for `Api`, sorted by name
+pub fn registered<Api: 'static>() -> Vec<&'static OperationFn> {
+    let api = TypeId::of::<Api>();
+    let mut operations: Vec<_> = inventory::iter::<OperationFn>
+        .into_iter()
+        .filter(|operation| (operation.api)() == api)
+        .collect();
+    operations.sort_by_key(|operation| operation.name);
+    operations
+}
+
+/// Add the operations registered for `Api` to `spec`
+///
+/// Operations are sorted by `order` together with the derive's operations, and
+/// otherwise appended after them, sorted by name. Their messages are registered in
+/// `components.messages` and added to the channel; messages without their own
+/// content type take the channel's default, then the document's. Operation message
+/// refs follow the API's `ref_style`.
+///
+/// An operation on an undeclared channel is still added, for
+/// [`AsyncApiSpec::validate`] to report. A name that is already taken keeps the
+/// first declaration, marked with [`REDECLARED_EXTENSION`].
+pub fn apply<Api: 'static>(spec: &mut AsyncApiSpec, options: &ApiOptions) {
+    let registered = registered::<Api>();
+    if registered.is_empty() {
+        return;
+    }
+
+    for registered in &registered {
+        let name = registered.name;
+        let channel_name = registered.channel;
+        let mut channel = spec
+            .channels
+            .as_mut()
+            .and_then(|channels| channels.get_mut(channel_name));
+        let content_type = channel
+            .as_ref()
+            .and_then(|channel| channel.default_content_type.clone())
+            .or_else(|| spec.default_content_type.clone());
+
+        let mut message_refs = Vec::new();
+        for mut message in (registered.messages)() {
+            let Some(message_name) = message.name.clone() else {
+                continue;
+            };
+            if !(registered.declared_content_type)(&message_name) && content_type.is_some() {
+                message.content_type = content_type.clone();
+            }
+            let component_ref = format!("{}{}", options.components_ref_prefix, message_name);
+            if let Some(channel) = channel.as_mut() {
+                channel
+                    .messages_mut()
+                    .entry(message_name.clone())
+                    .or_insert_with(|| MessageRef::Reference {
+                        reference: component_ref.clone(),
+                    });
+            }
+            let reference = if options.components_ref_style {
+                component_ref
+            } else {
+                format!("#/channels/{}/messages/{}", channel_name, message_name)
+            };
+            message_refs.push(MessageRef::Reference { reference });
+            spec.components
+                .get_or_insert_with(Components::default)
+                .messages
+                .get_or_insert_with(Default::default)
+                .entry(message_name)
+                .or_insert(message);
+        }
+
+        let operations = spec.operations.get_or_insert_with(Default::default);
+        if let Some(existing) = operations.get_mut(name) {
+            existing.extensions.insert(
+                REDECLARED_EXTENSION.to_string(),
+                serde_json::Value::Bool(true),
+            );
+            continue;
+        }
+        operations.insert(
+            name.to_string(),
+            Operation {
+                action: registered.action.clone(),
+                channel: ChannelRef {
+                    reference: format!("#/channels/{}", channel_name),
+                },
+                title: None,
+                summary: registered.summary.map(str::to_string),
+                description: registered.description.map(str::to_string),
+                messages: (!message_refs.is_empty()).then_some(message_refs),
+                reply: None,
+                tags: None,
+                external_docs: None,
+                security: None,
+                bindings: None,
+                extensions: HashMap::new(),
+            },
+        );
+    }
+
+    // Same ordering as the derive: by hint, unordered last, ties in insertion order
+    // The derive's hints come last so they win for redeclared names
+    let orders: BTreeMap<&str, Option<i64>> = registered
+        .iter()
+        .map(|r| (r.name, r.order))
+        .chain(options.operations.iter().copied())
+        .collect();
+    if let Some(operations) = spec.operations.as_mut() {
+        let key = |name: &String| {
+            let order = orders.get(name.as_str()).copied().flatten();
+            (order.is_none(), order)
+        };
+        operations.sort_by(|a, _, b, _| key(a).cmp(&key(b)));
+    }
+}
@@ -0,0 +1,11 @@
+//! Compile-fail tests for the derive and `spec!` macros
+//!
+//! Regenerate expected output with `TRYBUILD=overwrite cargo test --test compile_fail`.
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "operation-fn")]
+    t.compile_fail("tests/ui/operation_fn/*.rs");
+}
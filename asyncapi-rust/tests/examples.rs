@@ -0,0 +1,89 @@
+//! Guard against examples drifting from the models
+//!
+//! Each example that builds a specification is compiled into this test as a module,
+//! and its spec is checked with `validate()` (and the official JSON Schema when the
+//! `validate-schema` feature is enabled).
+
+use asyncapi_rust::AsyncApiSpec;
+
+macro_rules! example {
+    ($name:ident, $path:literal) => {
+        // Examples are binaries, so `main` is unused here and lints about public API
+        // names fire on their now-reachable types
+        #[allow(dead_code, clippy::enum_variant_names)]
+        #[path = $path]
+        mod $name;
+    };
+}
+
+example!(actix_websocket, "../examples/actix_websocket.rs");
+example!(asyncapi_derive, "../examples/asyncapi_derive.rs");
+example!(axum_websocket, "../examples/axum_websocket.rs");
+example!(chat_api, "../examples/chat_api.rs");
+example!(
+    framework_integration_guide,
+    "../examples/framework_integration_guide.rs"
+);
+example!(full_asyncapi_derive, "../examples/full_asyncapi_derive.rs");
+example!(generate_spec_file, "../examples/generate_spec_file.rs");
+example!(message_integration, "../examples/message_integration.rs");
+example!(server_variables, "../examples/server_variables.rs");
+
+fn check(example: &str, spec: AsyncApiSpec) {
+    let issues = spec.validate();
+    assert!(
+        issues.is_empty(),
+        "example '{}' has validation issues: {:?}",
+        example,
+        issues.iter().map(ToString::to_string).collect::<Vec<_>>()
+    );
+
+    #[cfg(feature = "validate-schema")]
+    if let Err(errors) = spec.validate_schema() {
+        panic!(
+            "example '{}' violates the AsyncAPI schema: {:?}",
+            example, errors
+        );
+    }
+
+    // The serialized document must read back into the same models
+    let value = serde_json::Value::from(&spec);
+    let round_trip = AsyncApiSpec::try_from(value.clone())
+        .unwrap_or_else(|e| panic!("example '{}' does not round-trip: {}", example, e));
+    assert_eq!(serde_json::Value::from(round_trip), value);
+}
+
+#[test]
+fn test_examples_produce_valid_specs() {
+    check("actix_websocket", actix_websocket::ChatApi::asyncapi_spec());
+    check("asyncapi_derive", asyncapi_derive::ChatApi::asyncapi_spec());
+    check(
+        "asyncapi_derive",
+        asyncapi_derive::MinimalApi::asyncapi_spec(),
+    );
+    check("axum_websocket", axum_websocket::PubSubApi::asyncapi_spec());
+    check(
+        "chat_api",
+        chat_api::build_asyncapi_spec(chat_api::ChatMessage::asyncapi_messages()),
+    );
+    check(
+        "framework_integration_guide",
+        framework_integration_guide::ExampleApi::asyncapi_spec(),
+    );
+    check(
+        "full_asyncapi_derive",
+        full_asyncapi_derive::ChatApi::asyncapi_spec(),
+    );
+    check(
+        "generate_spec_file",
+        generate_spec_file::ExampleApi::asyncapi_spec(),
+    );
+    check(
+        "message_integration",
+        message_integration::ChatApi::asyncapi_spec(),
+    );
+    check(
+        "server_variables",
+        server_variables::UserApi::asyncapi_spec(),
+    );
+}
@@ -215,6 +215,33 @@ fn test_asyncapi_attributes() {
     );
 }
 
+#[test]
+fn test_asyncapi_content_type_for() {
+    assert_eq!(
+        DocumentedMessage::asyncapi_content_type_for("Join"),
+        Some("application/json")
+    );
+    assert_eq!(
+        DocumentedMessage::asyncapi_content_type_for("File"),
+        Some("application/octet-stream")
+    );
+    assert_eq!(
+        DocumentedMessage::asyncapi_content_type_for("Binary"),
+        Some("application/octet-stream")
+    );
+    assert_eq!(
+        DocumentedMessage::asyncapi_content_type_for("Unknown"),
+        None
+    );
+
+    // Also available through the trait, e.g. to pick a frame type generically
+    fn is_binary<T: asyncapi_rust::ToAsyncApiMessage>(name: &str) -> bool {
+        T::asyncapi_content_type_for(name) == Some("application/octet-stream")
+    }
+    assert!(is_binary::<DocumentedMessage>("Binary"));
+    assert!(!is_binary::<SimpleMessage>("SimpleMessage"));
+}
+
 // Test AsyncApi derive macro
 #[derive(AsyncApi)]
 #[asyncapi(
@@ -409,6 +436,56 @@ fn test_asyncapi_with_messages() {
     assert_eq!(system_status.summary, Some("System status".to_string()));
 }
 
+#[test]
+fn test_asyncapi_all_message_names() {
+    assert_eq!(
+        MessageIntegrationApi::asyncapi_all_message_names(),
+        vec!["user.join", "user.leave", "system.status"]
+    );
+    assert!(SubprotocolApi::asyncapi_all_message_names().is_empty());
+}
+
+// Test AsyncApi on types that carry data: fields and variants are ignored
+#[derive(AsyncApi)]
+#[asyncapi(title = "Configured API", version = "1.0.0")]
+#[asyncapi_server(name = "local", host = "localhost:8080", protocol = "ws")]
+#[asyncapi_messages(ApiMessage)]
+#[allow(dead_code)]
+struct ConfiguredApi {
+    bind_address: String,
+    max_connections: usize,
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Environment API", version = "2.0.0")]
+#[asyncapi_messages(SystemMessage)]
+#[allow(dead_code)]
+enum EnvironmentApi {
+    Production,
+    Staging { host: String },
+}
+
+#[test]
+fn test_asyncapi_on_fielded_struct_and_enum() {
+    let spec = ConfiguredApi::asyncapi_spec();
+    assert_eq!(spec.info.title, "Configured API");
+    assert!(spec.servers.unwrap().contains_key("local"));
+    assert_eq!(
+        spec.components.unwrap().messages.unwrap().len(),
+        ApiMessage::asyncapi_message_count()
+    );
+
+    let spec = EnvironmentApi::asyncapi_spec();
+    assert_eq!(spec.info.version, "2.0.0");
+    assert!(
+        spec.components
+            .unwrap()
+            .messages
+            .unwrap()
+            .contains_key("system.status")
+    );
+}
+
 #[test]
 fn test_asyncapi_operation_with_messages() {
     // Define message types for operations
@@ -493,3 +570,2395 @@ fn test_asyncapi_operation_with_messages() {
         _ => panic!("Expected message reference"),
     }
 }
+
+// Test that a message type without variants doesn't produce an empty components object
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub enum NoMessages {}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Empty Components API", version = "1.0.0")]
+#[asyncapi_messages(NoMessages)]
+struct EmptyComponentsApi;
+
+#[test]
+fn test_empty_components_omitted() {
+    let spec = EmptyComponentsApi::asyncapi_spec();
+    assert!(spec.components.is_none());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert!(
+        json.get("components").is_none(),
+        "Empty components object should not be emitted"
+    );
+}
+
+// Test transparent newtype documented as its inner type
+/// Session token issued at login
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(transparent)]
+pub struct Token(String);
+
+#[test]
+fn test_transparent_struct_payload() {
+    let messages = Token::asyncapi_messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].name, Some("Token".to_string()));
+
+    let payload = serde_json::to_value(&messages[0].payload).unwrap();
+    assert_eq!(payload.get("type"), Some(&serde_json::json!("string")));
+    assert!(
+        payload.get("properties").is_none(),
+        "Transparent struct should not be wrapped in an object schema"
+    );
+    // The payload is the inner type's schema, without the newtype's title and docs,
+    // which describe the message instead
+    assert_eq!(
+        payload,
+        serde_json::to_value(asyncapi_rust::schemars::schema_for!(String)).unwrap()
+    );
+    assert_eq!(
+        messages[0].description.as_deref(),
+        Some("Session token issued at login")
+    );
+}
+
+// Test building a spec at runtime from derived message types
+#[test]
+fn test_spec_builder_with_derived_messages() {
+    use asyncapi_rust::{AsyncApiSpec, OperationAction};
+
+    let spec = AsyncApiSpec::builder()
+        .title("Dynamic API")
+        .version("1.0.0")
+        .channel("chat", |c| c.address("/ws").message::<ApiMessage>())
+        .operation("sendMessage", OperationAction::Send, "chat", |o| {
+            o.message::<ApiMessage>()
+        })
+        .build();
+
+    let channels = spec.channels.expect("Should have channels");
+    let chat = channels.get("chat").expect("Should have chat channel");
+    assert_eq!(chat.address, Some("/ws".to_string()));
+    assert_eq!(chat.messages.as_ref().unwrap().len(), 2);
+
+    let operations = spec.operations.expect("Should have operations");
+    let send_op = operations.get("sendMessage").unwrap();
+    assert_eq!(send_op.messages.as_ref().unwrap().len(), 2);
+
+    let messages = spec.components.unwrap().messages.unwrap();
+    assert!(messages.contains_key("user.join"));
+    assert!(messages.contains_key("user.leave"));
+}
+
+// Test payload handling for binary messages
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum BinaryPayloadMessage {
+    #[asyncapi(triggers_binary, binary_payload = "schema")]
+    Raw { data: Vec<u8> },
+
+    #[asyncapi(triggers_binary, binary_payload = "none")]
+    Opaque { data: Vec<u8> },
+}
+
+#[test]
+fn test_binary_message_payload() {
+    let messages = DocumentedMessage::asyncapi_messages();
+    let file = messages
+        .iter()
+        .find(|m| m.name.as_deref() == Some("File"))
+        .expect("File message should exist");
+
+    let payload = serde_json::to_value(&file.payload).unwrap();
+    assert_eq!(
+        payload,
+        serde_json::json!({ "type": "string", "format": "binary" })
+    );
+    assert_eq!(file.payload, Some(asyncapi_rust::Schema::binary()));
+
+    // Text messages keep their schemars schema
+    let join = messages
+        .iter()
+        .find(|m| m.name.as_deref() == Some("Join"))
+        .expect("Join message should exist");
+    let join_payload = serde_json::to_value(&join.payload).unwrap();
+    assert!(join_payload.get("properties").is_some());
+}
+
+#[test]
+fn test_binary_payload_modes() {
+    let messages = BinaryPayloadMessage::asyncapi_messages();
+
+    let raw = serde_json::to_value(&messages[0].payload).unwrap();
+    assert!(
+        raw.get("properties").is_some(),
+        "binary_payload = \"schema\" should keep the schemars schema"
+    );
+
+    assert!(messages[1].payload.is_none());
+}
+
+// Test WebSocket subprotocol operation bindings
+#[derive(AsyncApi)]
+#[asyncapi(title = "Subprotocol API", version = "1.0.0")]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+#[asyncapi_operation(
+    name = "sendMessage",
+    action = "send",
+    channel = "chat",
+    ws(subprotocol = "json")
+)]
+struct SubprotocolApi;
+
+#[test]
+fn test_operation_ws_subprotocol_binding() {
+    let spec = SubprotocolApi::asyncapi_spec();
+    let json = serde_json::to_value(&spec).unwrap();
+
+    assert_eq!(
+        json["operations"]["sendMessage"]["bindings"]["ws"]["x-subprotocol"],
+        serde_json::json!("json")
+    );
+}
+
+// Test derive-generated specs against the AsyncAPI 3.0 JSON Schema
+#[cfg(feature = "validate-schema")]
+#[test]
+fn test_validate_schema() {
+    #[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+    #[serde(tag = "type")]
+    pub enum ChatMessage {
+        #[serde(rename = "chat.message")]
+        ChatMessage { username: String, text: String },
+    }
+
+    #[allow(clippy::duplicated_attributes)]
+    #[derive(AsyncApi)]
+    #[asyncapi(title = "Chat API", version = "1.0.0")]
+    #[asyncapi_server(name = "production", host = "api.example.com", protocol = "wss")]
+    #[asyncapi_channel(name = "chat", address = "/ws/chat")]
+    #[asyncapi_operation(name = "sendMessage", action = "send", channel = "chat", messages = [ChatMessage])]
+    #[asyncapi_operation(name = "receiveMessage", action = "receive", channel = "chat", messages = [ChatMessage])]
+    #[asyncapi_messages(ChatMessage)]
+    struct ChatApi;
+
+    let spec = ChatApi::asyncapi_spec();
+    assert_eq!(spec.validate_schema(), Ok(()));
+
+    // Operations must reference messages; inline definitions are not allowed
+    let mut broken = spec.clone();
+    let send_op = broken
+        .operations
+        .as_mut()
+        .unwrap()
+        .get_mut("sendMessage")
+        .unwrap();
+    let inline = ChatMessage::asyncapi_messages().remove(0);
+    send_op.messages = Some(vec![asyncapi_rust::MessageRef::Inline(Box::new(inline))]);
+
+    let errors = broken.validate_schema().unwrap_err();
+    assert!(!errors.is_empty());
+
+    // The AsyncAPI 3.0 Parameter Object has no schema, so typed parameters are reported
+    let errors = RoomsApi::asyncapi_spec().validate_schema().unwrap_err();
+    assert!(errors.iter().any(|e| e.starts_with("/channels/room:")));
+}
+
+// Committed YAML files must not churn between runs
+#[cfg(feature = "yaml")]
+#[test]
+fn test_to_yaml_is_deterministic() {
+    #[allow(clippy::duplicated_attributes)]
+    #[derive(AsyncApi)]
+    #[asyncapi(title = "Chat API", version = "1.0.0")]
+    #[asyncapi_server(name = "production", host = "api.example.com", protocol = "wss")]
+    #[asyncapi_server(name = "staging", host = "staging.example.com", protocol = "wss")]
+    #[asyncapi_channel(name = "chat", address = "/ws/chat")]
+    #[asyncapi_channel(name = "events", address = "/ws/events")]
+    #[asyncapi_operation(name = "send", action = "send", channel = "chat", messages = [TaggedMessage, BasicMessage])]
+    #[asyncapi_operation(name = "receive", action = "receive", channel = "events", messages = [RenamedMessage])]
+    struct YamlApi;
+
+    let first = YamlApi::asyncapi_spec().to_yaml().unwrap();
+    let second = YamlApi::asyncapi_spec().to_yaml().unwrap();
+    assert_eq!(first, second);
+
+    // Including channel parameters
+    assert_eq!(
+        RoomsApi::asyncapi_spec().to_yaml().unwrap(),
+        RoomsApi::asyncapi_spec().to_yaml().unwrap()
+    );
+}
+
+// Test message headers with correlation IDs
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct RequestHeaders {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum TracedMessage {
+    #[asyncapi(headers = RequestHeaders, correlation_id = "$message.header#/requestId")]
+    Request { query: String },
+
+    #[asyncapi(headers = RequestHeaders, correlation_id = "$message.header#/traceId")]
+    Mismatched { query: String },
+}
+
+#[test]
+fn test_headers_and_correlation_id() {
+    let messages = TracedMessage::asyncapi_messages();
+
+    let request = &messages[0];
+    let json = serde_json::to_value(request).unwrap();
+    assert_eq!(
+        json["correlationId"]["location"],
+        serde_json::json!("$message.header#/requestId")
+    );
+    assert!(json["headers"]["properties"].get("requestId").is_some());
+    assert_eq!(request.missing_correlation_header(), None);
+
+    let mismatched = &messages[1];
+    assert_eq!(mismatched.missing_correlation_header(), Some("traceId"));
+}
+
+// Test that a message type shared by several operations is only generated once
+static COUNTED_SCHEMA_CALLS: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Serialize, Deserialize, ToAsyncApiMessage)]
+pub struct CountedMessage {
+    pub text: String,
+}
+
+impl JsonSchema for CountedMessage {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "CountedMessage".into()
+    }
+
+    fn json_schema(
+        _generator: &mut asyncapi_rust::schemars::SchemaGenerator,
+    ) -> asyncapi_rust::schemars::Schema {
+        COUNTED_SCHEMA_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        asyncapi_rust::schemars::json_schema!({
+            "type": "object",
+            "properties": { "text": { "type": "string" } },
+            "required": ["text"]
+        })
+    }
+}
+
+#[allow(clippy::duplicated_attributes)]
+#[derive(AsyncApi)]
+#[asyncapi(title = "Shared Message API", version = "1.0.0")]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+#[asyncapi_operation(name = "sendMessage", action = "send", channel = "chat", messages = [CountedMessage])]
+#[asyncapi_operation(name = "receiveMessage", action = "receive", channel = "chat", messages = [CountedMessage])]
+#[asyncapi_messages(CountedMessage)]
+struct SharedMessageApi;
+
+#[test]
+fn test_shared_message_type_generated_once() {
+    let spec = SharedMessageApi::asyncapi_spec();
+
+    assert_eq!(
+        COUNTED_SCHEMA_CALLS.load(std::sync::atomic::Ordering::SeqCst),
+        1,
+        "Schema should be generated once per message type"
+    );
+
+    let messages = spec.components.unwrap().messages.unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages.contains_key("CountedMessage"));
+}
+
+// Test that operation message types are registered in components
+#[derive(AsyncApi)]
+#[asyncapi(title = "Operation Messages API", version = "1.0.0")]
+#[asyncapi_channel(name = "system", address = "/ws/system")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "system", messages = [SystemMessage])]
+struct OperationMessagesApi;
+
+#[test]
+fn test_operation_messages_registered_in_components() {
+    let spec = OperationMessagesApi::asyncapi_spec();
+
+    let messages = spec.components.unwrap().messages.unwrap();
+    assert!(messages.contains_key("system.status"));
+}
+
+// Test operation message ref styles
+#[derive(AsyncApi)]
+#[asyncapi(title = "Channel Refs API", version = "1.0.0", ref_style = "channel")]
+#[asyncapi_channel(name = "system", address = "/ws/system")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "system", messages = [SystemMessage])]
+struct ChannelRefStyleApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Component Refs API",
+    version = "1.0.0",
+    ref_style = "components"
+)]
+#[asyncapi_channel(name = "system", address = "/ws/system")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "system", messages = [SystemMessage])]
+struct ComponentsRefStyleApi;
+
+#[test]
+fn test_ref_style_channel() {
+    let spec = ChannelRefStyleApi::asyncapi_spec();
+    let operations = spec.operations.unwrap();
+    let messages = operations["receiveStatus"].messages.as_ref().unwrap();
+    assert_eq!(
+        messages[0].as_reference(),
+        Some("#/channels/system/messages/system.status")
+    );
+
+    // Default matches the explicit channel style
+    let default_spec = OperationMessagesApi::asyncapi_spec();
+    let default_operations = default_spec.operations.unwrap();
+    let default_messages = default_operations["receiveStatus"]
+        .messages
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        messages[0].as_reference(),
+        default_messages[0].as_reference()
+    );
+}
+
+#[test]
+fn test_ref_style_components() {
+    let spec = ComponentsRefStyleApi::asyncapi_spec();
+    let operations = spec.operations.unwrap();
+    let messages = operations["receiveStatus"].messages.as_ref().unwrap();
+    assert_eq!(
+        messages[0].as_reference(),
+        Some("#/components/messages/system.status")
+    );
+
+    // Channel messages still reference components, and the target exists
+    let channels = spec.channels.unwrap();
+    assert!(
+        channels["system"]
+            .messages
+            .as_ref()
+            .unwrap()
+            .contains_key("system.status")
+    );
+    let components = spec.components.unwrap();
+    assert!(components.messages.unwrap().contains_key("system.status"));
+}
+
+// Test external component refs for multi-file documents
+#[derive(AsyncApi)]
+#[asyncapi(title = "Split API", version = "1.0.0", ref_base = "./messages.json")]
+#[asyncapi_channel(name = "system", address = "/ws/system")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "system", messages = [SystemMessage])]
+struct ExternalRefsApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Split API",
+    version = "1.0.0",
+    ref_style = "components",
+    ref_base = "./messages.json"
+)]
+#[asyncapi_channel(name = "system", address = "/ws/system")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "system", messages = [SystemMessage])]
+struct ExternalComponentRefsApi;
+
+#[test]
+fn test_ref_base_external_refs() {
+    let spec = ExternalRefsApi::asyncapi_spec();
+    let channels = spec.channels.unwrap();
+    let channel_messages = channels["system"].messages.as_ref().unwrap();
+    assert_eq!(
+        channel_messages["system.status"].as_reference(),
+        Some("./messages.json#/components/messages/system.status")
+    );
+    // Operations point at the local channel, which holds the external ref
+    let operations = spec.operations.unwrap();
+    assert_eq!(
+        operations["receiveStatus"].messages.as_ref().unwrap()[0].as_reference(),
+        Some("#/channels/system/messages/system.status")
+    );
+
+    let spec = ExternalComponentRefsApi::asyncapi_spec();
+    let operations = spec.operations.unwrap();
+    assert_eq!(
+        operations["receiveStatus"].messages.as_ref().unwrap()[0].as_reference(),
+        Some("./messages.json#/components/messages/system.status")
+    );
+    // Components are still generated so they can be written to the external file
+    assert!(
+        spec.components
+            .unwrap()
+            .messages
+            .unwrap()
+            .contains_key("system.status")
+    );
+}
+
+#[test]
+fn test_ref_base_to_bundle() {
+    for spec in [
+        ExternalRefsApi::asyncapi_spec(),
+        ExternalComponentRefsApi::asyncapi_spec(),
+    ] {
+        let bundle = spec.to_bundle().unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("./messages.json"));
+
+        let channels = bundle.channels.as_ref().unwrap();
+        assert_eq!(
+            channels["system"].messages.as_ref().unwrap()["system.status"].as_reference(),
+            Some("#/components/messages/system.status")
+        );
+        assert_eq!(bundle.components, spec.components);
+        assert!(bundle.validate().is_empty());
+    }
+
+    // Without the components the external refs can't be bundled
+    let mut spec = ExternalRefsApi::asyncapi_spec();
+    spec.components = None;
+    assert_eq!(
+        spec.to_bundle(),
+        Err(asyncapi_rust::AsyncApiError::UnresolvedRef {
+            reference: "./messages.json#/components/messages/system.status".to_string()
+        })
+    );
+}
+
+// Test inlining channel messages without their names
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Operation Messages API",
+    version = "1.0.0",
+    omit_channel_message_names
+)]
+#[asyncapi_channel(name = "system", address = "/ws/system")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "system", messages = [SystemMessage])]
+struct OmitChannelMessageNamesApi;
+
+#[test]
+fn test_omit_channel_message_names() {
+    let with_refs = serde_json::to_value(OperationMessagesApi::asyncapi_spec()).unwrap();
+    let spec = OmitChannelMessageNamesApi::asyncapi_spec();
+    let inlined = serde_json::to_value(&spec).unwrap();
+
+    assert_eq!(
+        with_refs["channels"]["system"]["messages"]["system.status"]["$ref"],
+        "#/components/messages/system.status"
+    );
+    let component = &inlined["components"]["messages"]["system.status"];
+    assert_eq!(component["name"], "system.status");
+    let channel_message = &inlined["channels"]["system"]["messages"]["system.status"];
+    assert!(channel_message.get("name").is_none());
+    let mut expected = component.clone();
+    expected.as_object_mut().unwrap().remove("name");
+    assert_eq!(channel_message, &expected);
+
+    // Everything outside the channel messages is unchanged
+    let mut without_channels = inlined.clone();
+    without_channels["channels"] = with_refs["channels"].clone();
+    assert_eq!(without_channels, with_refs);
+    assert!(spec.validate().is_empty());
+}
+
+// Test that field doc comments survive as property descriptions
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct RoomInfo {
+    /// Display name of the room
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct DocumentedJoin {
+    /// The room name
+    pub room: String,
+    /// Details about the room
+    pub info: RoomInfo,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum DocumentedEvent {
+    #[serde(rename = "room.left")]
+    Left {
+        /// The room that was left
+        room: String,
+    },
+}
+
+fn property_description(payload: &serde_json::Value, property: &str) -> Option<String> {
+    payload["properties"][property]["description"]
+        .as_str()
+        .map(str::to_string)
+}
+
+#[test]
+fn test_field_doc_comments_become_property_descriptions() {
+    let messages = DocumentedJoin::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        property_description(&payload, "room"),
+        Some("The room name".to_string())
+    );
+    // Descriptions next to a $ref must survive the conversion too
+    assert_eq!(
+        property_description(&payload, "info"),
+        Some("Details about the room".to_string())
+    );
+    assert!(payload["properties"]["info"]["$ref"].is_string());
+
+    let messages = DocumentedEvent::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        property_description(&payload, "room"),
+        Some("The room that was left".to_string())
+    );
+}
+
+// Test that multi-line rustdoc becomes the message description verbatim
+/// Sent when a user joins a room.
+///
+/// - The `room` must already exist
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct RustdocJoin {
+    pub room: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum RustdocEvent {
+    /// Room was closed.
+    ///
+    /// Clients should reconnect elsewhere.
+    Closed,
+    /// Ignored in favour of the attribute
+    #[asyncapi(description = "Room was reopened")]
+    Reopened,
+}
+
+#[test]
+fn test_multi_line_rustdoc_becomes_description() {
+    let messages = RustdocJoin::asyncapi_messages();
+    assert_eq!(
+        messages[0].description.as_deref(),
+        Some("Sent when a user joins a room.\n\n- The `room` must already exist")
+    );
+
+    let messages = RustdocEvent::asyncapi_messages();
+    assert_eq!(
+        messages[0].description.as_deref(),
+        Some("Room was closed.\n\nClients should reconnect elsewhere.")
+    );
+    assert_eq!(
+        messages[1].description.as_deref(),
+        Some("Room was reopened")
+    );
+}
+
+// Test info tags from doc comments and explicit attributes
+/// Tagged API
+///
+/// Tags: chat, realtime
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Tagged API",
+    version = "1.0.0",
+    tag(name = "realtime", description = "Realtime messaging"),
+    tag(name = "admin")
+)]
+struct TaggedApi;
+
+#[test]
+fn test_info_tags_deduplicated() {
+    let spec = TaggedApi::asyncapi_spec();
+    let tags = spec.info.tags.expect("Should have tags");
+
+    let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["chat", "realtime", "admin"]);
+    assert_eq!(tags[0].description, None);
+    assert_eq!(tags[1].description, Some("Realtime messaging".to_string()));
+
+    let untagged = SubprotocolApi::asyncapi_spec();
+    assert!(untagged.info.tags.is_none());
+}
+
+// Test typed parameter schema examples
+#[derive(AsyncApi)]
+#[asyncapi(title = "Rooms API", version = "1.0.0")]
+#[asyncapi_server(
+    name = "production",
+    host = "{region}.example.com",
+    protocol = "wss",
+    variable(name = "region", examples = ["us", "eu"])
+)]
+#[asyncapi_channel(
+    name = "room",
+    address = "/rooms/{roomId}/{slug}",
+    parameter(name = "roomId", schema_type = "integer", examples = [42, 7]),
+    parameter(name = "slug", examples = ["general"])
+)]
+struct RoomsApi;
+
+#[test]
+fn test_parameter_examples_keep_json_types() {
+    let spec = RoomsApi::asyncapi_spec();
+    let json = serde_json::to_value(&spec).unwrap();
+
+    let parameters = &json["channels"]["room"]["parameters"];
+    assert_eq!(parameters["roomId"]["schema"]["type"], "integer");
+    assert_eq!(
+        parameters["roomId"]["schema"]["examples"],
+        serde_json::json!([42, 7])
+    );
+    assert!(parameters["slug"]["schema"].get("type").is_none());
+    assert_eq!(
+        parameters["slug"]["schema"]["examples"],
+        serde_json::json!(["general"])
+    );
+
+    // Server variable examples stay strings
+    assert_eq!(
+        json["servers"]["production"]["variables"]["region"]["examples"],
+        serde_json::json!(["us", "eu"])
+    );
+}
+
+// Test declared parameters missing from the address are reported by validate()
+#[derive(AsyncApi)]
+#[asyncapi(title = "Stale Parameters API", version = "1.0.0")]
+#[asyncapi_channel(
+    name = "room",
+    address = "/ws/rooms",
+    parameter(name = "roomId", description = "No longer in the address")
+)]
+struct StaleParametersApi;
+
+#[test]
+fn test_unused_channel_parameters_reported_by_validate() {
+    let spec = StaleParametersApi::asyncapi_spec();
+    assert_eq!(
+        spec.channels.as_ref().unwrap()["room"].unused_parameters(),
+        vec!["roomId"]
+    );
+
+    let issues = spec.validate();
+    assert_eq!(
+        issues,
+        vec![asyncapi_rust::ValidationIssue::UnusedChannelParameter {
+            channel: "room".to_string(),
+            parameter: "roomId".to_string(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), asyncapi_rust::Severity::Warning);
+}
+
+// Test channel parameters inferred from the address
+#[derive(AsyncApi)]
+#[asyncapi(title = "Inferred Parameters API", version = "1.0.0")]
+#[asyncapi_channel(name = "room", address = "/ws/{userId}/{room}")]
+struct InferredParametersApi;
+
+#[test]
+fn test_channel_parameters_inferred_from_address() {
+    let spec = InferredParametersApi::asyncapi_spec();
+    let channels = spec.channels.unwrap();
+    let parameters = channels["room"].parameters.as_ref().unwrap();
+    assert_eq!(parameters.len(), 2);
+
+    for name in ["userId", "room"] {
+        let schema = serde_json::to_value(parameters[name].schema.as_ref().unwrap()).unwrap();
+        assert_eq!(schema, serde_json::json!({ "type": "string" }));
+    }
+}
+
+// Test container-level content type inherited by enum variants
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(triggers_binary)]
+pub enum BinaryFrame {
+    Audio(Vec<u8>),
+    Video(Vec<u8>),
+    #[asyncapi(content_type = "application/json")]
+    Control {
+        command: String,
+    },
+}
+
+#[test]
+fn test_enum_container_triggers_binary_inherited() {
+    let messages = BinaryFrame::asyncapi_messages();
+    assert_eq!(messages.len(), 3);
+
+    for message in &messages[..2] {
+        assert_eq!(
+            message.content_type,
+            Some("application/octet-stream".to_string())
+        );
+        let payload = serde_json::to_value(message.payload.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({ "type": "string", "format": "binary" })
+        );
+    }
+
+    let control = &messages[2];
+    assert_eq!(control.name, Some("Control".to_string()));
+    assert_eq!(control.content_type, Some("application/json".to_string()));
+}
+
+// Test schema depth guard with a self-referential type
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct CommentThread {
+    pub text: String,
+    pub replies: Vec<CommentThread>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct Level3 {
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(max_schema_depth = 2)]
+pub struct ShallowLimitMessage {
+    pub nested: Vec<Vec<Level3>>,
+}
+
+#[test]
+fn test_recursive_type_schema_converts() {
+    // schemars breaks the cycle with a $ref, so the depth stays bounded
+    let messages = CommentThread::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert!(payload["properties"]["replies"]["items"]["$ref"].is_string());
+}
+
+#[test]
+#[should_panic(expected = "schema exceeds maximum depth of 2")]
+fn test_schema_depth_limit_exceeded() {
+    let _ = ShallowLimitMessage::asyncapi_messages();
+}
+
+#[test]
+fn test_asyncapi_messages_iter() {
+    assert_eq!(
+        DocumentedMessage::asyncapi_messages_iter().count(),
+        DocumentedMessage::asyncapi_message_count()
+    );
+
+    // Filter without collecting every message first
+    let binary = DocumentedMessage::asyncapi_messages_iter()
+        .find(|message| message.content_type.as_deref() == Some("application/octet-stream"))
+        .expect("Should have a binary message");
+    assert_eq!(binary.name, Some("File".to_string()));
+
+    let names: Vec<String> = DocumentedMessage::asyncapi_messages_iter()
+        .filter_map(|message| message.name)
+        .collect();
+    assert_eq!(names, DocumentedMessage::asyncapi_message_names());
+
+    // The trait method is available to generic code
+    fn count_messages<T: asyncapi_rust::ToAsyncApiMessage + JsonSchema>() -> usize {
+        <T as asyncapi_rust::ToAsyncApiMessage>::asyncapi_messages_iter().count()
+    }
+    assert_eq!(count_messages::<DocumentedMessage>(), 4);
+}
+
+// Test fields with custom serde(with) modules
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct TimestampedMessage {
+    pub text: String,
+    /// Unix timestamp in seconds
+    #[serde(with = "chrono::serde::ts_seconds")]
+    #[schemars(with = "i64")]
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum TimestampedEvent {
+    #[serde(rename = "edited")]
+    Edited {
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        #[schemars(with = "i64")]
+        edited_at: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+#[test]
+fn test_serde_with_fields_use_schemars_output() {
+    let messages = TimestampedMessage::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    let sent_at = &payload["properties"]["sent_at"];
+    assert_eq!(sent_at["type"], "integer");
+    assert_eq!(sent_at["description"], "Unix timestamp in seconds");
+
+    // Round-trips through the wire format documented by the schema
+    let json = serde_json::json!({ "text": "hi", "sent_at": 1_700_000_000 });
+    let message: TimestampedMessage = serde_json::from_value(json).unwrap();
+    assert_eq!(message.sent_at.timestamp(), 1_700_000_000);
+
+    let messages = TimestampedEvent::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(payload["properties"]["edited_at"]["type"], "integer");
+}
+
+// Test schema conversion of schemars output that used to be hard to deserialize
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum UnusualSchemaMessage {
+    #[serde(rename = "dated")]
+    Dated {
+        id: i64,
+        timestamp: Option<chrono::NaiveDateTime>,
+        deadline: Option<Option<chrono::DateTime<chrono::Utc>>>,
+    },
+    #[serde(rename = "mapped")]
+    Mapped {
+        counts: std::collections::HashMap<String, u32>,
+        attributes: std::collections::BTreeMap<String, serde_json::Value>,
+        pair: (String, i32),
+    },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(deny_unknown_fields)]
+pub struct StrictMessage {
+    pub tags: Option<Vec<Option<String>>>,
+    pub extra: serde_json::Value,
+}
+
+#[test]
+fn test_unusual_schemars_output_converts() {
+    let messages = UnusualSchemaMessage::asyncapi_messages();
+    assert_eq!(messages.len(), 2);
+    for message in &messages {
+        let payload = message.payload.as_ref().expect("Should have payload");
+        // Round-trips through JSON without losing keywords
+        let json = serde_json::to_value(payload).unwrap();
+        let reparsed: asyncapi_rust::Schema = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), json);
+    }
+
+    let mapped = serde_json::to_value(messages[1].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        mapped["properties"]["counts"]["additionalProperties"]["type"],
+        "integer"
+    );
+
+    let messages = StrictMessage::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(payload["additionalProperties"], false);
+    assert_eq!(payload["properties"]["extra"], true);
+}
+
+#[test]
+fn test_deny_unknown_fields_additional_properties_false() {
+    use asyncapi_rust::Schema;
+
+    let messages = StrictMessage::asyncapi_messages();
+    let payload = messages[0].payload.as_ref().expect("Should have payload");
+    let Schema::Object(object) = payload else {
+        panic!("Expected schema object");
+    };
+    assert!(matches!(
+        object.additional_properties.as_deref(),
+        Some(Schema::Bool(false))
+    ));
+
+    // Round-trips back to the same schemars output
+    let json = serde_json::to_value(payload).unwrap();
+    let expected =
+        serde_json::to_value(asyncapi_rust::schemars::schema_for!(StrictMessage)).unwrap();
+    assert_eq!(
+        json["additionalProperties"],
+        expected["additionalProperties"]
+    );
+    let reparsed: Schema = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), json);
+}
+
+// Test nullable normalization of Option<T> fields
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct Nickname(String);
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct NullableDefaultMessage {
+    pub count: Option<u32>,
+    /// Optional tuple of coordinates
+    pub location: Option<(f64, f64)>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(nullable = "any_of")]
+pub struct NullableAnyOfMessage {
+    pub count: Option<u32>,
+    pub nickname: Option<Nickname>,
+}
+
+#[test]
+fn test_nullable_normalization() {
+    let messages = NullableDefaultMessage::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        payload["properties"]["count"]["type"],
+        serde_json::json!(["integer", "null"])
+    );
+    assert_eq!(
+        payload["properties"]["location"]["type"],
+        serde_json::json!(["array", "null"])
+    );
+    assert!(payload["properties"]["location"].get("anyOf").is_none());
+
+    let messages = NullableAnyOfMessage::asyncapi_messages();
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        payload["properties"]["count"]["anyOf"],
+        serde_json::json!([{ "type": "integer", "format": "uint32", "minimum": 0 }, { "type": "null" }])
+    );
+    assert_eq!(
+        payload["properties"]["nickname"]["anyOf"][1],
+        serde_json::json!({ "type": "null" })
+    );
+}
+
+// Test operation ordering via the `order` hint
+#[allow(clippy::duplicated_attributes)] // False positive - different operations can reference same channel
+#[derive(AsyncApi)]
+#[asyncapi(title = "Ordered API", version = "1.0.0")]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+#[asyncapi_operation(name = "unordered", action = "send", channel = "chat")]
+#[asyncapi_operation(name = "sendLast", action = "send", channel = "chat", order = 20)]
+#[asyncapi_operation(
+    name = "receiveFirst",
+    action = "receive",
+    channel = "chat",
+    order = 10
+)]
+#[asyncapi_operation(name = "receiveLast", action = "receive", channel = "chat", order = 20)]
+struct OrderedApi;
+
+#[test]
+fn test_operations_sorted_by_order_hint() {
+    let spec = OrderedApi::asyncapi_spec();
+    let operations = spec.operations.as_ref().expect("Should have operations");
+    let names: Vec<&str> = operations.keys().map(String::as_str).collect();
+    assert_eq!(
+        names,
+        vec!["receiveFirst", "sendLast", "receiveLast", "unordered"]
+    );
+
+    // Order is preserved through serialization
+    let json = serde_json::to_string(&spec).unwrap();
+    let first = json.find("\"receiveFirst\"").unwrap();
+    let last = json.find("\"unordered\"").unwrap();
+    assert!(first < last);
+}
+
+// Test reply_channel shorthand
+#[derive(AsyncApi)]
+#[asyncapi(title = "Reply API", version = "1.0.0")]
+#[asyncapi_channel(name = "requests", address = "/ws/requests")]
+#[asyncapi_channel(name = "responses", address = "/ws/responses")]
+#[asyncapi_operation(
+    name = "askQuestion",
+    action = "send",
+    channel = "requests",
+    reply_channel = "responses"
+)]
+#[asyncapi_operation(name = "receiveAnswer", action = "receive", channel = "responses")]
+struct ReplyApi;
+
+#[test]
+fn test_operation_reply_channel() {
+    let spec = ReplyApi::asyncapi_spec();
+    let operations = spec.operations.as_ref().expect("Should have operations");
+
+    let reply = operations["askQuestion"]
+        .reply
+        .as_ref()
+        .and_then(|reply| reply.as_inline())
+        .expect("Should have inline reply");
+    assert_eq!(
+        reply.channel.as_ref().unwrap().reference,
+        "#/channels/responses"
+    );
+    assert!(reply.address.is_none());
+    assert!(reply.messages.is_none());
+    assert!(operations["receiveAnswer"].reply.is_none());
+}
+
+// Test metadata-only messages
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum AuditMessage {
+    /// Login recorded
+    #[serde(rename = "audit.login")]
+    #[asyncapi(summary = "Login recorded", no_payload)]
+    Login { user_id: String, ip: String },
+
+    #[serde(rename = "audit.logout")]
+    Logout { user_id: String },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(description = "Documented elsewhere", no_payload)]
+pub struct PrivateMessage {
+    pub secret: String,
+}
+
+#[test]
+fn test_no_payload_messages() {
+    let messages = AuditMessage::asyncapi_messages();
+    let login = &messages[0];
+    assert!(login.payload.is_none());
+    assert_eq!(login.summary.as_deref(), Some("Login recorded"));
+    assert_eq!(login.content_type.as_deref(), Some("application/json"));
+    assert!(messages[1].payload.is_some());
+
+    let messages = PrivateMessage::asyncapi_messages();
+    assert!(messages[0].payload.is_none());
+    assert_eq!(
+        messages[0].description.as_deref(),
+        Some("Documented elsewhere")
+    );
+    let json = serde_json::to_value(&messages[0]).unwrap();
+    assert!(json.get("payload").is_none());
+    assert_eq!(json["x-no-payload"], true);
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Audit API", version = "1.0.0")]
+#[asyncapi_channel(name = "audit", address = "/ws/audit")]
+#[asyncapi_operation(name = "receiveAudit", action = "receive", channel = "audit", messages = [AuditMessage])]
+struct AuditApi;
+
+#[test]
+fn test_no_payload_messages_pass_payload_check() {
+    let mut spec = AuditApi::asyncapi_spec();
+    assert!(spec.validate_message_payloads().is_empty());
+    assert!(spec.validate().is_empty());
+
+    // A payload lost without `no_payload` is reported
+    let components = spec.components.as_mut().unwrap().messages.as_mut().unwrap();
+    components.get_mut("audit.logout").unwrap().payload = None;
+    assert_eq!(spec.validate_message_payloads(), vec!["audit.logout"]);
+}
+
+// Test asyncapi name overrides independent of serde rename
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum VersionedMessage {
+    #[serde(rename = "chat.message")]
+    #[asyncapi(name = "ChatMessageV2", summary = "Chat message")]
+    Chat { text: String },
+
+    #[serde(rename = "chat.typing")]
+    Typing { user: String },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(name = "Heartbeat")]
+pub struct PingFrame {
+    pub sequence: u64,
+}
+
+#[test]
+fn test_asyncapi_name_override() {
+    assert_eq!(
+        VersionedMessage::asyncapi_message_names(),
+        vec!["ChatMessageV2", "chat.typing"]
+    );
+
+    let messages = VersionedMessage::asyncapi_messages();
+    assert_eq!(messages[0].name.as_deref(), Some("ChatMessageV2"));
+    assert_eq!(messages[0].title.as_deref(), Some("ChatMessageV2"));
+    // The payload still documents serde's wire discriminator
+    let payload = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(payload["properties"]["type"]["const"], "chat.message");
+    assert!(payload["properties"].get("text").is_some());
+
+    let messages = PingFrame::asyncapi_messages();
+    assert_eq!(messages[0].name.as_deref(), Some("Heartbeat"));
+    assert!(messages[0].payload.is_some());
+}
+
+// Test reply(...) messages registering components
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum QuestionMessage {
+    #[serde(rename = "question")]
+    Question { text: String },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum AnswerMessage {
+    #[serde(rename = "answer")]
+    Answer { text: String },
+    #[serde(rename = "answer.error")]
+    Error { reason: String },
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Reply Messages API", version = "1.0.0")]
+#[asyncapi_channel(name = "questions", address = "/ws/questions")]
+#[asyncapi_channel(name = "answers", address = "/ws/answers")]
+#[asyncapi_operation(
+    name = "ask",
+    action = "send",
+    channel = "questions",
+    messages = [QuestionMessage],
+    reply(channel = "answers", messages = [AnswerMessage])
+)]
+struct ReplyMessagesApi;
+
+#[test]
+fn test_operation_reply_messages_registered() {
+    let spec = ReplyMessagesApi::asyncapi_spec();
+
+    // Both request and reply types are components, without #[asyncapi_messages]
+    let components = spec
+        .find_message("answer")
+        .expect("Reply message component");
+    assert!(components.payload.is_some());
+    assert!(spec.find_message("answer.error").is_some());
+    assert!(spec.find_message("question").is_some());
+
+    let reply = spec.operations.as_ref().unwrap()["ask"]
+        .reply
+        .as_ref()
+        .and_then(|reply| reply.as_inline())
+        .expect("Should have inline reply");
+    assert_eq!(
+        reply.channel.as_ref().unwrap().reference,
+        "#/channels/answers"
+    );
+    let references: Vec<&str> = reply
+        .messages
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter_map(|m| m.as_reference())
+        .collect();
+    assert_eq!(
+        references,
+        vec![
+            "#/channels/answers/messages/answer",
+            "#/channels/answers/messages/answer.error"
+        ]
+    );
+
+    // Every reply reference resolves through the reply channel to a component
+    let answers = &spec.channels.as_ref().unwrap()["answers"];
+    for reference in references {
+        let name = reference.rsplit('/').next().unwrap();
+        let channel_ref = answers.messages.as_ref().unwrap()[name]
+            .as_reference()
+            .unwrap();
+        assert_eq!(channel_ref, format!("#/components/messages/{}", name));
+        assert!(spec.find_message(name).is_some());
+    }
+}
+
+// Test deprecated messages with and without a reason
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum LegacyChatMessage {
+    #[serde(rename = "chat")]
+    #[asyncapi(deprecated = "use chat.v2 instead")]
+    Chat { text: String },
+    #[serde(rename = "typing")]
+    #[asyncapi(deprecated)]
+    Typing,
+    #[serde(rename = "chat.v2")]
+    ChatV2 { text: String },
+}
+
+#[test]
+fn test_deprecated_message_reason() {
+    let messages = LegacyChatMessage::asyncapi_messages();
+    let chat = serde_json::to_value(&messages[0]).unwrap();
+    assert_eq!(chat["x-deprecated"], true);
+    assert_eq!(chat["x-deprecated-reason"], "use chat.v2 instead");
+    assert_eq!(chat["payload"]["deprecated"], true);
+
+    let typing = serde_json::to_value(&messages[1]).unwrap();
+    assert_eq!(typing["x-deprecated"], true);
+    assert!(typing.get("x-deprecated-reason").is_none());
+
+    let chat_v2 = serde_json::to_value(&messages[2]).unwrap();
+    assert!(chat_v2.get("x-deprecated").is_none());
+    assert!(chat_v2["payload"].get("deprecated").is_none());
+
+    // Extensions survive a round trip through the models
+    let round_trip: asyncapi_rust::Message = serde_json::from_value(chat.clone()).unwrap();
+    assert_eq!(
+        round_trip.extensions["x-deprecated-reason"],
+        "use chat.v2 instead"
+    );
+    assert_eq!(serde_json::to_value(&round_trip).unwrap(), chat);
+}
+
+// Test title and version from shared consts
+const SHARED_TITLE: &str = "Shared API";
+mod shared_meta {
+    pub const VERSION: &str = "3.1.4";
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = SHARED_TITLE, version = shared_meta::VERSION)]
+struct PublicSharedApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = SHARED_TITLE, version = shared_meta::VERSION, description = "Admin surface")]
+struct AdminSharedApi;
+
+#[test]
+fn test_title_and_version_from_consts() {
+    let public = PublicSharedApi::asyncapi_spec();
+    let admin = AdminSharedApi::asyncapi_spec();
+    assert_eq!(public.info.title, "Shared API");
+    assert_eq!(public.info.version, "3.1.4");
+    assert_eq!(admin.info.title, public.info.title);
+    assert_eq!(admin.info.version, public.info.version);
+}
+
+// Test channels sharing reusable bindings
+#[allow(clippy::duplicated_attributes)]
+#[derive(AsyncApi)]
+#[asyncapi(title = "Orders API", version = "1.0.0")]
+#[asyncapi_channel(
+    name = "ordersCreated",
+    address = "orders.created",
+    bindings_ref = "ordersTopic"
+)]
+#[asyncapi_channel(
+    name = "ordersCancelled",
+    address = "orders.cancelled",
+    bindings_ref = "ordersTopic"
+)]
+#[asyncapi_channel(name = "audit", address = "audit")]
+struct KafkaOrdersApi;
+
+#[test]
+fn test_channels_share_referenced_bindings() {
+    let spec = KafkaOrdersApi::asyncapi_spec();
+    let channels = spec.channels.as_ref().unwrap();
+    for name in ["ordersCreated", "ordersCancelled"] {
+        let bindings = serde_json::to_value(&channels[name].bindings).unwrap();
+        assert_eq!(
+            bindings,
+            serde_json::json!({ "$ref": "#/components/channelBindings/ordersTopic" })
+        );
+    }
+    assert!(channels["audit"].bindings.is_none());
+
+    // The bindings themselves aren't declared yet
+    let issues = spec.validate();
+    assert_eq!(issues.len(), 2);
+    assert!(
+        issues
+            .iter()
+            .all(|issue| issue.severity() == asyncapi_rust::Severity::Error)
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "channel 'ordersCancelled': bindings '#/components/channelBindings/ordersTopic' \
+         are not defined in components.channelBindings"
+    );
+
+    let spec = spec.with_channel_bindings(
+        "ordersTopic",
+        std::collections::HashMap::from([(
+            "kafka".to_string(),
+            serde_json::json!({ "partitions": 6, "replicas": 3, "bindingVersion": "0.5.0" }),
+        )]),
+    );
+    assert!(spec.validate().is_empty());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["components"]["channelBindings"]["ordersTopic"]["kafka"]["partitions"],
+        6
+    );
+}
+
+// Test reusable replies shared by several RPC operations
+#[derive(AsyncApi)]
+#[asyncapi(title = "RPC API", version = "1.0.0")]
+#[asyncapi_channel(name = "questions", address = "/ws/questions")]
+#[asyncapi_channel(name = "answers", address = "/ws/answers")]
+#[asyncapi_reply(name = "answer", channel = "answers", messages = [AnswerMessage])]
+#[asyncapi_operation(
+    name = "ask",
+    action = "send",
+    channel = "questions",
+    messages = [QuestionMessage],
+    reply(ref = "answer")
+)]
+#[asyncapi_operation(
+    name = "askAgain",
+    action = "send",
+    channel = "questions",
+    reply(ref = "answer")
+)]
+struct SharedReplyApi;
+
+#[test]
+fn test_operations_reference_component_replies() {
+    let spec = SharedReplyApi::asyncapi_spec();
+    let operations = spec.operations.as_ref().unwrap();
+    for name in ["ask", "askAgain"] {
+        assert_eq!(
+            operations[name].reply.as_ref().unwrap().as_reference(),
+            Some("#/components/replies/answer")
+        );
+    }
+
+    let components = spec.components.as_ref().unwrap();
+    let reply = &components.replies.as_ref().unwrap()["answer"];
+    assert_eq!(
+        reply.channel.as_ref().unwrap().reference,
+        "#/channels/answers"
+    );
+    let references: Vec<&str> = reply
+        .messages
+        .iter()
+        .flatten()
+        .filter_map(|m| m.as_reference())
+        .collect();
+    assert_eq!(
+        references,
+        vec![
+            "#/channels/answers/messages/answer",
+            "#/channels/answers/messages/answer.error"
+        ]
+    );
+
+    // Reply messages are registered on the reply channel and in components
+    let answers = spec.channels.as_ref().unwrap()["answers"]
+        .messages
+        .as_ref()
+        .unwrap();
+    assert!(answers.contains_key("answer.error"));
+    assert!(spec.find_message("answer").is_some());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["operations"]["askAgain"]["reply"],
+        serde_json::json!({ "$ref": "#/components/replies/answer" })
+    );
+    assert!(json["components"]["replies"]["answer"].is_object());
+}
+
+// Test pruning keeps messages used only by a component reply
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct Ack {
+    pub id: String,
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Ack API", version = "1.0.0")]
+#[asyncapi_channel(name = "commands", address = "/ws/commands")]
+#[asyncapi_reply(name = "ackReply", messages = [Ack])]
+#[asyncapi_operation(
+    name = "sendCommand",
+    action = "send",
+    channel = "commands",
+    messages = [QuestionMessage],
+    reply(ref = "ackReply")
+)]
+struct AckReplyApi;
+
+#[test]
+fn test_prune_keeps_reply_only_messages() {
+    let mut spec = AckReplyApi::asyncapi_spec();
+    assert!(spec.find_message("Ack").is_some());
+
+    assert_eq!(spec.prune_unused_components(), 0);
+    assert!(spec.find_message("Ack").is_some());
+    assert!(spec.validate().is_empty());
+
+    // Inline replies count as uses too
+    let operation = spec
+        .operations
+        .as_mut()
+        .unwrap()
+        .get_mut("sendCommand")
+        .unwrap();
+    let reply = spec
+        .components
+        .as_mut()
+        .unwrap()
+        .replies
+        .as_mut()
+        .unwrap()
+        .remove("ackReply");
+    operation.reply = reply.map(asyncapi_rust::OperationReplyRef::Inline);
+    assert_eq!(spec.prune_unused_components(), 0);
+    assert!(spec.find_message("Ack").is_some());
+
+    spec.operations
+        .as_mut()
+        .unwrap()
+        .get_mut("sendCommand")
+        .unwrap()
+        .reply = None;
+    assert_eq!(spec.prune_unused_components(), 1);
+    assert!(spec.find_message("Ack").is_none());
+}
+
+// Test defaultContentType, explicit and picked from server protocols
+fn broker_content_types() -> asyncapi_rust::ContentTypeDefaults {
+    asyncapi_rust::ContentTypeDefaults::default().protocol("mqtt", "application/octet-stream")
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Telemetry", version = "1.0.0", content_type_defaults = broker_content_types)]
+#[asyncapi_server(name = "broker", host = "mqtt.example.com", protocol = "mqtt")]
+struct TelemetryApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Telemetry",
+    version = "1.0.0",
+    default_content_type = "application/cbor",
+    content_type_defaults = broker_content_types
+)]
+#[asyncapi_server(name = "broker", host = "mqtt.example.com", protocol = "mqtt")]
+struct CborTelemetryApi;
+
+#[test]
+fn test_default_content_type() {
+    let spec = TelemetryApi::asyncapi_spec();
+    assert_eq!(
+        spec.default_content_type.as_deref(),
+        Some("application/octet-stream")
+    );
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(json["defaultContentType"], "application/octet-stream");
+
+    let spec = CborTelemetryApi::asyncapi_spec();
+    assert_eq!(
+        spec.default_content_type.as_deref(),
+        Some("application/cbor")
+    );
+
+    // Without either key nothing is emitted
+    let json = serde_json::to_value(ReplyApi::asyncapi_spec()).unwrap();
+    assert!(json.get("defaultContentType").is_none());
+}
+
+// Test adjacently tagged enums whose content is a shared struct
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct Address {
+    pub street: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct Customer {
+    pub id: u64,
+    pub address: Address,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "kind", content = "data")]
+pub enum CustomerEvent {
+    #[serde(rename = "customer.created")]
+    Created(Customer),
+    #[serde(rename = "customer.moved")]
+    Moved {
+        customer: Customer,
+        previous: Address,
+    },
+    #[serde(rename = "customer.deleted")]
+    Deleted,
+}
+
+#[test]
+fn test_adjacently_tagged_enum_refs() {
+    let messages = CustomerEvent::asyncapi_messages();
+    assert_eq!(messages.len(), 3);
+
+    // The payload follows the content property's $ref, and the $defs it needs,
+    // including nested ones, come along
+    let created = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(created["properties"]["kind"]["const"], "customer.created");
+    assert_eq!(created["properties"]["data"]["$ref"], "#/$defs/Customer");
+    assert_eq!(
+        created["$defs"]["Customer"]["properties"]["address"]["$ref"],
+        "#/$defs/Address"
+    );
+    assert!(created["$defs"]["Address"].is_object());
+
+    let moved = serde_json::to_value(messages[1].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        moved["properties"]["data"]["properties"]["customer"]["$ref"],
+        "#/$defs/Customer"
+    );
+    assert!(moved["$defs"]["Customer"].is_object());
+    assert!(moved["$defs"]["Address"].is_object());
+
+    // Unit variants have no content and need no $defs
+    let deleted = serde_json::to_value(messages[2].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(deleted["properties"]["kind"]["const"], "customer.deleted");
+    assert!(deleted.get("$defs").is_none());
+
+    #[cfg(feature = "validate-schema")]
+    {
+        let event = CustomerEvent::Created(Customer {
+            id: 7,
+            address: Address {
+                street: "Main St".to_string(),
+            },
+        });
+        let value = serde_json::to_value(&event).unwrap();
+        assert!(messages[0].validate_payload(&value).is_ok());
+        assert!(
+            messages[0]
+                .validate_payload(
+                    &serde_json::json!({"kind": "customer.created", "data": {"id": 7}})
+                )
+                .is_err()
+        );
+    }
+}
+
+// Test generic enums documented through a concrete instantiation
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct User {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+#[asyncapi(instantiate = "Event<User>")]
+pub enum Event<T> {
+    Created(T),
+    Deleted { id: u64 },
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Users", version = "1.0.0")]
+#[asyncapi_messages(Event::<User>)]
+struct UserEventApi;
+
+#[test]
+fn test_generic_enum_instantiation() {
+    assert_eq!(
+        Event::<User>::asyncapi_message_names(),
+        vec!["Created", "Deleted"]
+    );
+
+    let messages = Event::<User>::asyncapi_messages();
+    assert_eq!(messages.len(), 2);
+    let created = serde_json::to_value(messages[0].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(created["properties"]["type"]["const"], "Created");
+    assert_eq!(created["$ref"], "#/$defs/User");
+    assert_eq!(
+        created["$defs"]["User"]["properties"]["name"]["type"],
+        "string"
+    );
+    let deleted = serde_json::to_value(messages[1].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(deleted["properties"]["id"]["type"], "integer");
+
+    // Usable through the trait like any other message type
+    fn names<T: asyncapi_rust::ToAsyncApiMessage>() -> Vec<&'static str> {
+        T::asyncapi_message_names()
+    }
+    assert_eq!(names::<Event<User>>(), vec!["Created", "Deleted"]);
+
+    let spec = UserEventApi::asyncapi_spec();
+    let components = spec.components.unwrap().messages.unwrap();
+    assert!(components.contains_key("Created"));
+    assert!(components.contains_key("Deleted"));
+}
+
+// Test operations declared on functions
+#[cfg(feature = "operation-fn")]
+#[derive(AsyncApi)]
+#[asyncapi(title = "Notifications", version = "1.0.0")]
+#[asyncapi_channel(name = "alerts", address = "/ws/alerts")]
+#[asyncapi_operation(
+    name = "subscribe",
+    action = "receive",
+    channel = "alerts",
+    summary = "Subscribe to alerts",
+    description = "Receive alerts as they are raised",
+    messages = [TaggedMessage]
+)]
+struct NotificationApi;
+
+/// Raise an alert for every subscriber
+///
+/// Alerts are delivered at most once.
+#[cfg(feature = "operation-fn")]
+#[asyncapi_rust::asyncapi_operation_fn(
+    api = NotificationApi,
+    action = "send",
+    channel = "alerts",
+    messages = [RenamedMessage]
+)]
+fn raise_alert() {}
+
+#[cfg(feature = "operation-fn")]
+#[asyncapi_rust::asyncapi_operation_fn(
+    api = NotificationApi,
+    name = "ackAlert",
+    action = "receive",
+    channel = "alerts",
+    summary = "Acknowledge an alert"
+)]
+fn acknowledge_alert() {}
+
+#[cfg(feature = "operation-fn")]
+#[derive(AsyncApi)]
+#[asyncapi(title = "Other", version = "1.0.0")]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+struct OtherNotificationApi;
+
+// Registered for another API, so NotificationApi must not pick it up
+#[cfg(feature = "operation-fn")]
+#[asyncapi_rust::asyncapi_operation_fn(api = OtherNotificationApi, action = "send", channel = "chat")]
+fn unrelated() {}
+
+#[cfg(feature = "operation-fn")]
+#[test]
+fn test_operation_fns() {
+    raise_alert();
+    acknowledge_alert();
+    unrelated();
+
+    let registered: Vec<_> = asyncapi_rust::operation_fn::registered::<NotificationApi>()
+        .iter()
+        .map(|operation| operation.name)
+        .collect();
+    assert_eq!(registered, vec!["ackAlert", "raise_alert"]);
+
+    let spec = NotificationApi::asyncapi_spec();
+    let operations = spec.operations.as_ref().unwrap();
+    let names: Vec<_> = operations.keys().map(String::as_str).collect();
+    assert_eq!(names, vec!["subscribe", "ackAlert", "raise_alert"]);
+
+    // Derive-declared operations carry summary and description too
+    let subscribe = &operations["subscribe"];
+    assert_eq!(subscribe.summary.as_deref(), Some("Subscribe to alerts"));
+    assert_eq!(
+        subscribe.description.as_deref(),
+        Some("Receive alerts as they are raised")
+    );
+
+    let raise = &operations["raise_alert"];
+    assert!(raise.action_is_send());
+    assert_eq!(raise.channel.reference, "#/channels/alerts");
+    assert_eq!(raise.summary, None);
+    assert_eq!(
+        raise.description.as_deref(),
+        Some("Raise an alert for every subscriber\n\nAlerts are delivered at most once.")
+    );
+    let refs: Vec<_> = raise
+        .messages
+        .iter()
+        .flatten()
+        .filter_map(|m| m.as_reference())
+        .collect();
+    assert!(refs.contains(&"#/channels/alerts/messages/chat.message"));
+
+    let ack = &operations["ackAlert"];
+    assert!(ack.action_is_receive());
+    assert_eq!(ack.summary.as_deref(), Some("Acknowledge an alert"));
+    assert!(ack.messages.is_none());
+
+    // Function messages land in the channel and components
+    let channel_messages = spec.channels.as_ref().unwrap()["alerts"]
+        .messages
+        .as_ref()
+        .unwrap();
+    assert!(channel_messages.contains_key("chat.message"));
+    assert!(channel_messages.contains_key("Echo"));
+    assert!(spec.find_message("chat.message").is_some());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["operations"]["ackAlert"]["summary"],
+        "Acknowledge an alert"
+    );
+    assert!(spec.validate().is_empty());
+
+    // Other APIs only see their own registrations
+    let other = OtherNotificationApi::asyncapi_spec();
+    let names: Vec<_> = other.operations.as_ref().unwrap().keys().collect();
+    assert_eq!(names, vec!["unrelated"]);
+}
+
+// Function operations follow the API's order, ref_style and content-type defaults
+#[cfg(feature = "operation-fn")]
+#[allow(clippy::duplicated_attributes)] // False positive - different operations can reference same channel
+#[derive(AsyncApi)]
+#[asyncapi(title = "Alarms", version = "1.0.0", ref_style = "components")]
+#[asyncapi_channel(
+    name = "alarms",
+    address = "/ws/alarms",
+    default_content_type = "application/cbor"
+)]
+#[asyncapi_operation(name = "listen", action = "receive", channel = "alarms", order = 20)]
+#[asyncapi_operation(name = "mute", action = "send", channel = "alarms")]
+struct AlarmApi;
+
+#[cfg(feature = "operation-fn")]
+#[asyncapi_rust::asyncapi_operation_fn(
+    api = AlarmApi,
+    action = "send",
+    channel = "alarms",
+    messages = [TaggedMessage],
+    order = 10
+)]
+fn sound_alarm() {}
+
+#[cfg(feature = "operation-fn")]
+#[asyncapi_rust::asyncapi_operation_fn(api = AlarmApi, action = "send", channel = "sirens")]
+fn sound_siren() {}
+
+#[cfg(feature = "operation-fn")]
+#[asyncapi_rust::asyncapi_operation_fn(api = AlarmApi, name = "mute", action = "send", channel = "alarms")]
+fn mute_again() {}
+
+#[cfg(feature = "operation-fn")]
+#[test]
+fn test_operation_fns_follow_api_settings() {
+    sound_alarm();
+    sound_siren();
+    mute_again();
+
+    let spec = AlarmApi::asyncapi_spec();
+    let operations = spec.operations.as_ref().unwrap();
+    let names: Vec<_> = operations.keys().map(String::as_str).collect();
+    assert_eq!(names, vec!["sound_alarm", "listen", "mute", "sound_siren"]);
+
+    let refs: Vec<_> = operations["sound_alarm"]
+        .messages
+        .iter()
+        .flatten()
+        .filter_map(|m| m.as_reference())
+        .collect();
+    assert_eq!(
+        refs,
+        vec![
+            "#/components/messages/Echo",
+            "#/components/messages/Broadcast"
+        ]
+    );
+    let echo = spec.find_message("Echo").unwrap();
+    assert_eq!(echo.content_type.as_deref(), Some("application/cbor"));
+
+    // Problems that would panic at runtime are reported by validate() instead
+    let issues = spec.validate();
+    assert!(
+        issues.contains(&asyncapi_rust::ValidationIssue::UnknownOperationChannel {
+            operation: "sound_siren".to_string(),
+            reference: "#/channels/sirens".to_string(),
+        })
+    );
+    assert!(
+        issues.contains(&asyncapi_rust::ValidationIssue::RedeclaredOperation {
+            operation: "mute".to_string(),
+        })
+    );
+    assert_eq!(issues.len(), 2);
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Admin API", version = "1.0.0")]
+#[asyncapi(tag(name = "admin", description = "Administrative operations"))]
+#[asyncapi(tag(name = "ops", external_docs = "https://example.com/ops"))]
+#[asyncapi_channel(name = "commands", address = "/ws/admin")]
+#[asyncapi_channel(name = "results", address = "/ws/admin/results")]
+#[asyncapi_operation(
+    name = "runCommand",
+    action = "send",
+    channel = "commands",
+    title = "Run command",
+    summary = "Run an administrative command",
+    description = "Commands are executed in the order received",
+    tags = ["admin", "ops"],
+    security = ["adminToken"],
+    deprecated = "Use the REST admin endpoint",
+    ws(subprotocol = "json"),
+    reply(channel = "results", messages = [BasicMessage]),
+    order = 1,
+    messages = [TaggedMessage]
+)]
+#[asyncapi_operation(
+    name = "watchResults",
+    action = "receive",
+    channel = "results",
+    deprecated
+)]
+struct AdminApi;
+
+#[test]
+fn test_operation_all_keys() {
+    let spec = AdminApi::asyncapi_spec();
+    let json = serde_json::to_value(&spec).unwrap();
+    let operation = &json["operations"]["runCommand"];
+
+    assert_eq!(operation["action"], "send");
+    assert_eq!(operation["channel"]["$ref"], "#/channels/commands");
+    assert_eq!(operation["title"], "Run command");
+    assert_eq!(operation["summary"], "Run an administrative command");
+    assert_eq!(
+        operation["description"],
+        "Commands are executed in the order received"
+    );
+    assert_eq!(
+        operation["tags"],
+        serde_json::json!([
+            {"name": "admin", "description": "Administrative operations"},
+            {"name": "ops", "externalDocs": {"url": "https://example.com/ops"}}
+        ])
+    );
+    assert_eq!(json["info"]["tags"], operation["tags"]);
+    assert!(json.get("tags").is_none());
+    assert_eq!(
+        operation["security"],
+        serde_json::json!([{"$ref": "#/components/securitySchemes/adminToken"}])
+    );
+    assert_eq!(operation["x-deprecated"], true);
+    assert_eq!(
+        operation["x-deprecated-reason"],
+        "Use the REST admin endpoint"
+    );
+    assert_eq!(operation["bindings"]["ws"]["x-subprotocol"], "json");
+    assert_eq!(operation["reply"]["channel"]["$ref"], "#/channels/results");
+    assert_eq!(
+        operation["reply"]["messages"],
+        serde_json::json!([
+            {"$ref": "#/channels/results/messages/Ping"},
+            {"$ref": "#/channels/results/messages/Pong"}
+        ])
+    );
+    let messages: Vec<_> = operation["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["$ref"].as_str().unwrap())
+        .collect();
+    assert!(messages.contains(&"#/channels/commands/messages/Echo"));
+    assert!(messages.contains(&"#/channels/commands/messages/Broadcast"));
+
+    // The ordered operation comes first, and a bare flag has no reason
+    let names: Vec<_> = spec.operations.as_ref().unwrap().keys().collect();
+    assert_eq!(names, vec!["runCommand", "watchResults"]);
+    let watch = &json["operations"]["watchResults"];
+    assert_eq!(watch["x-deprecated"], true);
+    assert!(watch.get("x-deprecated-reason").is_none());
+    assert!(watch.get("title").is_none());
+    assert!(watch.get("tags").is_none());
+
+    // The security scheme itself is supplied outside the derive
+    let mut spec = spec;
+    assert_eq!(spec.validate().len(), 1);
+    spec.components
+        .get_or_insert_with(Default::default)
+        .security_schemes
+        .get_or_insert_with(Default::default)
+        .insert(
+            "adminToken".to_string(),
+            asyncapi_rust::SecurityScheme {
+                scheme_type: "httpApiKey".to_string(),
+                description: None,
+                additional: Default::default(),
+            },
+        );
+    assert!(spec.validate().is_empty());
+
+    // Extensions read back into the model
+    let round_trip: asyncapi_rust::AsyncApiSpec = serde_json::from_value(json).unwrap();
+    let operation = &round_trip.operations.as_ref().unwrap()["runCommand"];
+    assert_eq!(operation.title.as_deref(), Some("Run command"));
+    assert_eq!(operation.extensions["x-deprecated"], true);
+}
+
+// Test schema documents for non-JSON content types
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum TelemetryMessage {
+    #[asyncapi(
+        content_type = "application/x-protobuf",
+        schema_file = "tests/schemas/telemetry.proto"
+    )]
+    Reading { sensor: String, value: f64 },
+    #[asyncapi(
+        content_type = "application/avro",
+        schema = r#"{"type": "record", "name": "Alert", "fields": [{"name": "level", "type": "int"}]}"#,
+        deprecated
+    )]
+    Alert { level: i32 },
+    /// Avro without a schema document
+    #[asyncapi(content_type = "application/avro")]
+    Heartbeat,
+    #[asyncapi(
+        content_type = "application/x-msgpack",
+        schema = "{}",
+        schema_format = "application/vnd.custom.msgpack"
+    )]
+    Packed,
+}
+
+#[test]
+fn test_schema_documents_by_content_type() {
+    let messages = TelemetryMessage::asyncapi_messages();
+    let payload = |name: &str| {
+        let message = messages
+            .iter()
+            .find(|m| m.name.as_deref() == Some(name))
+            .unwrap();
+        serde_json::to_value(message.payload.as_ref().unwrap()).unwrap()
+    };
+
+    let reading = payload("Reading");
+    assert_eq!(
+        reading["schemaFormat"],
+        asyncapi_rust::PROTOBUF_SCHEMA_FORMAT
+    );
+    assert_eq!(reading["schema"], include_str!("schemas/telemetry.proto"));
+    assert!(reading.get("properties").is_none());
+
+    // Avro schemas are JSON, so they're embedded as such, and left unmarked
+    let alert = payload("Alert");
+    assert_eq!(alert["schemaFormat"], asyncapi_rust::AVRO_SCHEMA_FORMAT);
+    assert_eq!(alert["schema"]["type"], "record");
+    assert_eq!(alert["schema"]["fields"][0]["name"], "level");
+    assert!(alert.get("deprecated").is_none());
+
+    // Without a schema document the schemars schema is kept
+    let heartbeat = payload("Heartbeat");
+    assert!(heartbeat.get("schemaFormat").is_none());
+    assert_eq!(heartbeat["type"], "object");
+
+    let packed = payload("Packed");
+    assert_eq!(packed["schemaFormat"], "application/vnd.custom.msgpack");
+    assert_eq!(packed["schema"], serde_json::json!({}));
+    assert_eq!(
+        TelemetryMessage::asyncapi_content_type_for("Packed"),
+        Some("application/x-msgpack")
+    );
+}
+
+// Test humanized default titles
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+#[asyncapi(humanize_titles)]
+pub enum RoomEvent {
+    UserJoin {
+        username: String,
+    },
+    #[asyncapi(title = "Someone left")]
+    UserLeave {
+        username: String,
+    },
+    #[serde(rename = "room.closed")]
+    RoomClosed,
+}
+
+#[test]
+fn test_humanize_titles() {
+    let messages = RoomEvent::asyncapi_messages();
+    let titles: Vec<_> = messages
+        .iter()
+        .map(|m| (m.name.as_deref().unwrap(), m.title.as_deref().unwrap()))
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            ("UserJoin", "User Join"),
+            ("UserLeave", "Someone left"),
+            ("room.closed", "room.closed"),
+        ]
+    );
+
+    // Without the flag, titles default to the message name
+    let messages = RenamedMessage::asyncapi_messages();
+    assert_eq!(messages[0].title, messages[0].name);
+}
+
+// Test deprecated channels
+#[allow(clippy::duplicated_attributes)] // False positive - channels share the deprecated flag
+#[derive(AsyncApi)]
+#[asyncapi(title = "Legacy API", version = "1.0.0")]
+#[asyncapi_channel(name = "legacy", address = "/ws/v1", deprecated = true)]
+#[asyncapi_channel(name = "old", address = "/ws/v0", deprecated)]
+#[asyncapi_channel(name = "current", address = "/ws/v2")]
+struct LegacyApi;
+
+#[test]
+fn test_deprecated_channel() {
+    let spec = LegacyApi::asyncapi_spec();
+    let channels = spec.channels.as_ref().unwrap();
+    assert_eq!(channels["legacy"].deprecated, Some(true));
+    assert_eq!(channels["old"].deprecated, Some(true));
+    assert_eq!(channels["current"].deprecated, None);
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(json["channels"]["legacy"]["x-deprecated"], true);
+    assert!(json["channels"]["current"].get("x-deprecated").is_none());
+
+    let round_trip: asyncapi_rust::AsyncApiSpec = serde_json::from_value(json).unwrap();
+    assert_eq!(round_trip, spec);
+}
+
+// Test Info metadata split across several #[asyncapi(...)] attributes
+#[derive(AsyncApi)]
+#[asyncapi(title = "Split Metadata API", version = "2.0.0")]
+#[asyncapi(
+    summary = "Chat over WebSocket",
+    description = "Rooms, presence and typing indicators"
+)]
+struct SplitMetadataApi;
+
+#[test]
+fn test_info_from_multiple_asyncapi_attributes() {
+    let spec = SplitMetadataApi::asyncapi_spec();
+    assert_eq!(spec.info.title, "Split Metadata API");
+    assert_eq!(spec.info.version, "2.0.0");
+    assert_eq!(spec.info.summary.as_deref(), Some("Chat over WebSocket"));
+    assert_eq!(
+        spec.info.description.as_deref(),
+        Some("Rooms, presence and typing indicators")
+    );
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(json["info"]["x-summary"], "Chat over WebSocket");
+    assert!(json["info"].get("summary").is_none());
+}
+
+// Test Info contact and license
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Licensed API",
+    version = "1.0.0",
+    contact(name = "API Support", email = "support@example.com"),
+    license(name = "MIT", url = "https://opensource.org/licenses/MIT")
+)]
+struct LicensedApi;
+
+#[test]
+fn test_info_contact_and_license() {
+    let spec = LicensedApi::asyncapi_spec();
+    assert_eq!(
+        spec.info.contact,
+        Some(asyncapi_rust::Contact {
+            name: Some("API Support".to_string()),
+            url: None,
+            email: Some("support@example.com".to_string()),
+        })
+    );
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["info"]["contact"],
+        serde_json::json!({"name": "API Support", "email": "support@example.com"})
+    );
+    assert_eq!(
+        json["info"]["license"],
+        serde_json::json!({"name": "MIT", "url": "https://opensource.org/licenses/MIT"})
+    );
+
+    let json = serde_json::to_value(SplitMetadataApi::asyncapi_spec()).unwrap();
+    assert!(json["info"].get("contact").is_none());
+    assert!(json["info"].get("license").is_none());
+}
+
+// Test tags and externalDocs on component messages and their schemas
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+#[asyncapi(tags = ["rooms"], external_docs = "https://example.com/rooms")]
+pub enum RoomLifecycle {
+    Opened {
+        room: String,
+    },
+    #[asyncapi(
+        tags = ["rooms", "moderation"],
+        external_docs(url = "https://example.com/moderation", description = "Moderation policy")
+    )]
+    #[schemars(extend("externalDocs" = {"url": "https://example.com/rooms/closed"}))]
+    Closed {
+        room: String,
+        reason: String,
+    },
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Room API", version = "1.0.0")]
+#[asyncapi(tag(
+    name = "rooms",
+    description = "Room lifecycle",
+    external_docs = "https://example.com/rooms/guide"
+))]
+#[asyncapi_channel(
+    name = "rooms",
+    address = "/ws/rooms",
+    external_docs = "https://example.com/rooms/channel"
+)]
+#[asyncapi_operation(
+    name = "watchRooms",
+    action = "receive",
+    channel = "rooms",
+    messages = [RoomLifecycle],
+    external_docs(url = "https://example.com/rooms/watch", description = "Watching rooms")
+)]
+#[asyncapi_messages(RoomLifecycle)]
+struct RoomLifecycleApi;
+
+#[test]
+fn test_component_message_tags_and_external_docs() {
+    let spec = RoomLifecycleApi::asyncapi_spec();
+    let json = serde_json::to_value(&spec).unwrap();
+
+    // Channels only reference the messages; the details live in components
+    assert_eq!(
+        json["channels"]["rooms"]["messages"]["Opened"],
+        serde_json::json!({"$ref": "#/components/messages/Opened"})
+    );
+
+    let opened = &json["components"]["messages"]["Opened"];
+    assert_eq!(
+        opened["tags"],
+        serde_json::json!([{
+            "name": "rooms",
+            "description": "Room lifecycle",
+            "externalDocs": {"url": "https://example.com/rooms/guide"}
+        }])
+    );
+    assert_eq!(
+        opened["externalDocs"],
+        serde_json::json!({"url": "https://example.com/rooms"})
+    );
+
+    let closed = &json["components"]["messages"]["Closed"];
+    assert_eq!(closed["tags"][0]["name"], "rooms");
+    assert_eq!(closed["tags"][1], serde_json::json!({"name": "moderation"}));
+    assert_eq!(
+        closed["externalDocs"],
+        serde_json::json!({
+            "description": "Moderation policy",
+            "url": "https://example.com/moderation"
+        })
+    );
+    assert_eq!(
+        closed["payload"]["externalDocs"]["url"],
+        "https://example.com/rooms/closed"
+    );
+
+    assert_eq!(
+        json["channels"]["rooms"]["externalDocs"],
+        serde_json::json!({"url": "https://example.com/rooms/channel"})
+    );
+    assert_eq!(
+        json["operations"]["watchRooms"]["externalDocs"],
+        serde_json::json!({
+            "description": "Watching rooms",
+            "url": "https://example.com/rooms/watch"
+        })
+    );
+
+    // Messages without tags or docs leave both out
+    let messages = BasicMessage::asyncapi_messages();
+    assert!(
+        messages
+            .iter()
+            .all(|m| m.tags.is_none() && m.external_docs.is_none())
+    );
+}
+
+// Test an Info description read from a Markdown file
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Overview API",
+    version = "1.0.0",
+    description_file = "tests/docs/api_overview.md"
+)]
+struct OverviewApi;
+
+#[test]
+fn test_description_file() {
+    let spec = OverviewApi::asyncapi_spec();
+    let description = spec.info.description.unwrap();
+    assert_eq!(description, include_str!("docs/api_overview.md"));
+    assert!(description.starts_with("# Chat API\n"));
+    assert!(description.contains("- Join a room with `room.join`"));
+}
+
+// Test operations taking one direction of a bidirectional protocol enum
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum GameProtocol {
+    #[serde(rename = "move")]
+    #[asyncapi(direction = "send")]
+    Move { x: u32, y: u32 },
+    #[serde(rename = "resign")]
+    #[asyncapi(direction = "send")]
+    Resign,
+    #[serde(rename = "board")]
+    #[asyncapi(direction = "receive")]
+    Board { cells: Vec<u8> },
+    #[serde(rename = "ping")]
+    Ping,
+}
+
+#[allow(clippy::duplicated_attributes)] // False positive - different operations can reference same channel
+#[derive(AsyncApi)]
+#[asyncapi(title = "Game API", version = "1.0.0")]
+#[asyncapi_channel(name = "game", address = "/ws/game")]
+#[asyncapi_operation(name = "sendMove", action = "send", channel = "game", messages_from = GameProtocol)]
+#[asyncapi_operation(name = "receiveBoard", action = "receive", channel = "game", messages_from = GameProtocol)]
+struct GameApi;
+
+#[test]
+fn test_messages_from_by_direction() {
+    assert_eq!(
+        GameProtocol::asyncapi_message_names_for("send"),
+        vec!["move", "resign", "ping"]
+    );
+    assert_eq!(
+        GameProtocol::asyncapi_message_names_for("receive"),
+        vec!["board", "ping"]
+    );
+
+    let spec = GameApi::asyncapi_spec();
+    let operations = spec.operations.as_ref().unwrap();
+    let refs = |operation: &str| -> Vec<&str> {
+        operations[operation]
+            .messages
+            .iter()
+            .flatten()
+            .filter_map(|m| m.as_reference())
+            .collect()
+    };
+    assert_eq!(
+        refs("sendMove"),
+        vec![
+            "#/channels/game/messages/move",
+            "#/channels/game/messages/resign",
+            "#/channels/game/messages/ping",
+        ]
+    );
+    assert_eq!(
+        refs("receiveBoard"),
+        vec![
+            "#/channels/game/messages/board",
+            "#/channels/game/messages/ping",
+        ]
+    );
+
+    // The channel holds both halves, and components every message once
+    let channels = spec.channels.as_ref().unwrap();
+    assert_eq!(channels["game"].messages.as_ref().unwrap().len(), 4);
+    let components = spec.components.as_ref().unwrap();
+    assert_eq!(components.messages.as_ref().unwrap().len(), 4);
+    assert!(spec.validate().is_empty());
+}
+
+// Test content type precedence: message, channel, document, application/json
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum SensorMessage {
+    #[serde(rename = "sensor.reading")]
+    Reading { value: f64 },
+    #[serde(rename = "sensor.log")]
+    #[asyncapi(content_type = "text/plain")]
+    Log { line: String },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum SensorAlert {
+    #[serde(rename = "sensor.alert")]
+    Alert { level: u8 },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+pub struct SensorStatus {
+    pub online: bool,
+}
+
+#[derive(AsyncApi)]
+#[asyncapi(
+    title = "Sensor API",
+    version = "1.0.0",
+    default_content_type = "application/octet-stream"
+)]
+#[asyncapi_channel(
+    name = "readings",
+    address = "/ws/readings",
+    default_content_type = "application/cbor"
+)]
+#[asyncapi_channel(name = "alerts", address = "/ws/alerts")]
+#[asyncapi_operation(name = "receiveReadings", action = "receive", channel = "readings", messages = [SensorMessage])]
+#[asyncapi_operation(name = "receiveAlerts", action = "receive", channel = "alerts", messages = [SensorAlert])]
+struct SensorApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Status API", version = "1.0.0")]
+#[asyncapi_channel(name = "status", address = "/ws/status")]
+#[asyncapi_operation(name = "receiveStatus", action = "receive", channel = "status", messages = [SensorStatus])]
+struct StatusApi;
+
+#[test]
+fn test_channel_default_content_type() {
+    assert_eq!(
+        SensorMessage::asyncapi_declared_content_type_for("sensor.log"),
+        Some("text/plain")
+    );
+    assert_eq!(
+        SensorMessage::asyncapi_declared_content_type_for("sensor.reading"),
+        None
+    );
+
+    let spec = SensorApi::asyncapi_spec();
+    let content_type = |name: &str| spec.find_message(name).unwrap().content_type.clone();
+    // The message's own content type wins
+    assert_eq!(content_type("sensor.log").as_deref(), Some("text/plain"));
+    // Then the channel's default
+    assert_eq!(
+        content_type("sensor.reading").as_deref(),
+        Some("application/cbor")
+    );
+    // Then the document's
+    assert_eq!(
+        content_type("sensor.alert").as_deref(),
+        Some("application/octet-stream")
+    );
+    let reading = spec.find_message("sensor.reading").unwrap();
+    assert_eq!(
+        spec.content_type_of("readings", reading),
+        "application/cbor"
+    );
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["channels"]["readings"]["x-default-content-type"],
+        "application/cbor"
+    );
+    assert!(
+        json["channels"]["alerts"]
+            .get("x-default-content-type")
+            .is_none()
+    );
+
+    // And finally application/json
+    let spec = StatusApi::asyncapi_spec();
+    assert_eq!(
+        spec.find_message("SensorStatus")
+            .unwrap()
+            .content_type
+            .as_deref(),
+        Some("application/json")
+    );
+}
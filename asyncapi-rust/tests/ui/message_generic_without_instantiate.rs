@@ -0,0 +1,11 @@
+use asyncapi_rust::{ToAsyncApiMessage, schemars::JsonSchema};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum Event<T> {
+    Created { item: T },
+    Deleted { id: u64 },
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use asyncapi_rust::{ToAsyncApiMessage, schemars::JsonSchema};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[serde(tag = "type")]
+pub enum ChatMessage {
+    #[asyncapi(summary, description = "Sent when a user joins")]
+    Join { username: String },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use asyncapi_rust::{ToAsyncApiMessage, schemars::JsonSchema};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(schema_file = "schemas/missing.json")]
+pub struct Packed {
+    id: u64,
+}
+
+fn main() {}
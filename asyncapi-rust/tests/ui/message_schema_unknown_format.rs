@@ -0,0 +1,10 @@
+use asyncapi_rust::{ToAsyncApiMessage, schemars::JsonSchema};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, ToAsyncApiMessage)]
+#[asyncapi(content_type = "application/x-msgpack", schema = "{}")]
+pub struct Packed {
+    id: u64,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use asyncapi_rust::asyncapi_operation_fn;
+
+#[asyncapi_operation_fn(action = "send", channel = "chat")]
+fn broadcast() {}
+
+fn main() {}
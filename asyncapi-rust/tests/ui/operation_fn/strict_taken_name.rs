@@ -0,0 +1,12 @@
+use asyncapi_rust::{AsyncApi, asyncapi_operation_fn};
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Chat", version = "1.0.0", strict)]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+#[asyncapi_operation(name = "broadcast", action = "send", channel = "chat")]
+struct ChatApi;
+
+#[asyncapi_operation_fn(api = ChatApi, action = "send", channel = "chat")]
+fn broadcast() {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use asyncapi_rust::{AsyncApi, asyncapi_operation_fn};
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Chat", version = "1.0.0", strict)]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+struct ChatApi;
+
+#[asyncapi_operation_fn(api = ChatApi, action = "send", channel = "lobby")]
+fn broadcast() {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use asyncapi_rust::AsyncApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "API", version = "1.0.0")]
+#[asyncapi_server(
+    name = "production",
+    host = "api.example.com",
+    protocol = "wss",
+    pathname = "/api/{version}/ws/{userId}",
+    variable(name = "version")
+)]
+struct MissingVariableApi;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use asyncapi_rust::AsyncApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "Test API", version = "1.0.0")]
+#[asyncapi_server(
+    name = "production",
+    host = "api.example.com:{port}",
+    protocol = "wss",
+    variable(name = "port", default = "443", examples = ["443", 8443])
+)]
+struct Api;
+
+fn main() {}
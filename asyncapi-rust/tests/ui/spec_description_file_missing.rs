@@ -0,0 +1,7 @@
+use asyncapi_rust::AsyncApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "API", version = "1.0.0", description_file = "docs/missing.md")]
+struct OverviewApi;
+
+fn main() {}
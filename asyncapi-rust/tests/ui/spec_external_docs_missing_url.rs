@@ -0,0 +1,8 @@
+use asyncapi_rust::AsyncApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "API", version = "1.0.0")]
+#[asyncapi_channel(name = "chat", address = "/ws/chat", external_docs(description = "Chat guide"))]
+struct ChatApi;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use asyncapi_rust::AsyncApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "API", version = "1.0.0", strict = true)]
+#[asyncapi_channel(name = "chat", address = "/ws/chat")]
+#[asyncapi_operation(name = "sendMessage", action = "send", channel = "chat", ws)]
+struct FlagApi;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use asyncapi_rust::AsyncApi;
+
+#[derive(AsyncApi)]
+#[asyncapi(title = "API", version = "1.0.0", license(url = "https://opensource.org/licenses/MIT"))]
+struct LicensedApi;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use asyncapi_rust::spec;
+
+fn main() {
+    let _ = spec! {
+        title: "Chat API",
+        channels: {
+            "chat": { adress: "/ws/chat" },
+        },
+    };
+}